@@ -0,0 +1,728 @@
+//! Derive macros for `anaso_site_api_models`.
+//!
+//! See `anaso_site_api_models::stela::from_form` for the attributes.
+
+#![forbid(unsafe_code)]
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::{
+    meta::ParseNestedMeta, parse_macro_input, Data, DeriveInput, Error, Expr, ExprArray, Fields,
+    Ident, Lit, LitBool, LitStr, Result, Type,
+};
+
+/// Implement `FromFormCallData`, and `FormDefinition` for structs.
+#[proc_macro_derive(FromFormCallData, attributes(form))]
+pub fn derive_from_form_call_data(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let expanded = match &input.data {
+        Data::Struct(_) => derive_struct(&input),
+        Data::Enum(_) => derive_enum(&input),
+        Data::Union(_) => Err(Error::new_spanned(
+            &input.ident,
+            "FromFormCallData can't be derived for unions",
+        )),
+    };
+    expanded.unwrap_or_else(Error::into_compile_error).into()
+}
+
+/// Struct-level `#[form(...)]`.
+#[derive(Default)]
+struct FormAttrs {
+    name: Option<LitStr>,
+    header: Option<LitStr>,
+    subheader: Option<LitStr>,
+    submit: Option<LitStr>,
+}
+
+/// Field-level `#[form(...)]`.
+#[derive(Default)]
+struct FieldAttrs {
+    name: Option<LitStr>,
+    title: Option<LitStr>,
+    input: Option<LitStr>,
+    min: Option<Lit>,
+    max: Option<Lit>,
+    step: Option<Lit>,
+    filter: Option<LitStr>,
+    esperanto: bool,
+    options: Vec<LitStr>,
+    custom: bool,
+    style: Option<LitStr>,
+    show_if: Option<LitStr>,
+    require_if: Option<LitStr>,
+    sitekey: Option<LitStr>,
+    size: Option<LitStr>,
+    theme: Option<LitStr>,
+    appearance: Option<LitStr>,
+    execution: Option<LitStr>,
+    retry: Option<LitStr>,
+    language: Option<LitStr>,
+    action: Option<LitStr>,
+    confirm: Option<LitStr>,
+    confirm_title: Option<LitStr>,
+    autocomplete: Option<LitStr>,
+}
+
+fn parse_attrs(
+    attrs: &[syn::Attribute],
+    mut f: impl FnMut(ParseNestedMeta) -> Result<()>,
+) -> Result<()> {
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("form")) {
+        attr.parse_nested_meta(&mut f)?;
+    }
+    Ok(())
+}
+
+fn form_attrs(input: &DeriveInput) -> Result<FormAttrs> {
+    let mut attrs = FormAttrs::default();
+    parse_attrs(&input.attrs, |meta| {
+        let slot = if meta.path.is_ident("name") {
+            &mut attrs.name
+        } else if meta.path.is_ident("header") {
+            &mut attrs.header
+        } else if meta.path.is_ident("subheader") {
+            &mut attrs.subheader
+        } else if meta.path.is_ident("submit") {
+            &mut attrs.submit
+        } else {
+            return Err(meta.error("expected `name`, `header`, `subheader`, or `submit`"));
+        };
+        *slot = Some(meta.value()?.parse()?);
+        Ok(())
+    })?;
+    Ok(attrs)
+}
+
+fn field_attrs(field: &syn::Field) -> Result<FieldAttrs> {
+    let mut attrs = FieldAttrs::default();
+    parse_attrs(&field.attrs, |meta| {
+        let path = &meta.path;
+        if path.is_ident("name") {
+            attrs.name = Some(meta.value()?.parse()?);
+        } else if path.is_ident("title") {
+            attrs.title = Some(meta.value()?.parse()?);
+        } else if path.is_ident("input") {
+            attrs.input = Some(meta.value()?.parse()?);
+        } else if path.is_ident("min") {
+            attrs.min = Some(meta.value()?.parse()?);
+        } else if path.is_ident("max") {
+            attrs.max = Some(meta.value()?.parse()?);
+        } else if path.is_ident("step") {
+            attrs.step = Some(meta.value()?.parse()?);
+        } else if path.is_ident("filter") {
+            attrs.filter = Some(meta.value()?.parse()?);
+        } else if path.is_ident("esperanto") {
+            attrs.esperanto = flag(&meta)?;
+        } else if path.is_ident("custom") {
+            attrs.custom = flag(&meta)?;
+        } else if path.is_ident("style") {
+            attrs.style = Some(meta.value()?.parse()?);
+        } else if path.is_ident("show_if") {
+            attrs.show_if = Some(meta.value()?.parse()?);
+        } else if path.is_ident("require_if") {
+            attrs.require_if = Some(meta.value()?.parse()?);
+        } else if path.is_ident("options") {
+            let array: ExprArray = meta.value()?.parse()?;
+            for elem in array.elems {
+                match elem {
+                    Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(lit),
+                        ..
+                    }) => attrs.options.push(lit),
+                    elem => return Err(Error::new_spanned(elem, "expected a string literal")),
+                }
+            }
+        } else if path.is_ident("sitekey") {
+            attrs.sitekey = Some(meta.value()?.parse()?);
+        } else if path.is_ident("size") {
+            attrs.size = Some(meta.value()?.parse()?);
+        } else if path.is_ident("theme") {
+            attrs.theme = Some(meta.value()?.parse()?);
+        } else if path.is_ident("appearance") {
+            attrs.appearance = Some(meta.value()?.parse()?);
+        } else if path.is_ident("execution") {
+            attrs.execution = Some(meta.value()?.parse()?);
+        } else if path.is_ident("retry") {
+            attrs.retry = Some(meta.value()?.parse()?);
+        } else if path.is_ident("language") {
+            attrs.language = Some(meta.value()?.parse()?);
+        } else if path.is_ident("action") {
+            attrs.action = Some(meta.value()?.parse()?);
+        } else if path.is_ident("confirm") {
+            attrs.confirm = Some(meta.value()?.parse()?);
+        } else if path.is_ident("confirm_title") {
+            attrs.confirm_title = Some(meta.value()?.parse()?);
+        } else if path.is_ident("autocomplete") {
+            attrs.autocomplete = Some(meta.value()?.parse()?);
+        } else {
+            return Err(meta.error(
+                "expected `name`, `title`, `input`, `min`, `max`, `step`, `filter`, \
+                 `esperanto`, `options`, `custom`, `style`, `show_if`, `require_if`, \
+                 `sitekey`, `size`, `theme`, `appearance`, `execution`, `retry`, \
+                 `language`, `action`, `confirm`, `confirm_title`, or `autocomplete`",
+            ));
+        }
+        Ok(())
+    })?;
+    Ok(attrs)
+}
+
+/// `flag` alone, or `flag = true`.
+fn flag(meta: &ParseNestedMeta) -> Result<bool> {
+    match meta.value() {
+        Ok(value) => Ok(value.parse::<LitBool>()?.value),
+        Err(_) => Ok(true),
+    }
+}
+
+/// `Option<TextFilter>` from `"FLAG | FLAG"`.
+fn opt_filter(filter: &Option<LitStr>) -> Result<TokenStream2> {
+    let stela = quote!(::anaso_site_api_models::stela);
+    let Some(filter) = filter else {
+        return Ok(quote!(::std::option::Option::None));
+    };
+    let flags = filter
+        .value()
+        .split('|')
+        .map(|flag| syn::parse_str::<Ident>(flag.trim()))
+        .collect::<Result<Vec<_>>>()
+        .map_err(|_| Error::new(filter.span(), "expected `FLAG | FLAG`"))?;
+    Ok(quote!(::std::option::Option::Some(#(#stela::TextFilter::#flags)|*)))
+}
+
+/// `Option<Enum>` from the wire name of one of `variants`.
+fn opt_variant(attr: &Option<LitStr>, ty: &str, variants: &[(&str, &str)]) -> Result<TokenStream2> {
+    let stela = quote!(::anaso_site_api_models::stela);
+    let Some(attr) = attr else {
+        return Ok(quote!(::std::option::Option::None));
+    };
+    let value = attr.value();
+    let Some((_, variant)) = variants.iter().find(|(name, _)| *name == value) else {
+        let names: Vec<_> = variants
+            .iter()
+            .map(|(name, _)| format!("`{name}`"))
+            .collect();
+        return Err(Error::new(
+            attr.span(),
+            format!("expected one of {}", names.join(", ")),
+        ));
+    };
+    let ty = Ident::new(ty, Span::call_site());
+    let variant = Ident::new(variant, Span::call_site());
+    Ok(quote!(::std::option::Option::Some(#stela::#ty::#variant)))
+}
+
+/// `Option<FormCondition>` from `"name"` (checked) or `"name = value"`.
+fn opt_condition(condition: &Option<LitStr>) -> TokenStream2 {
+    let stela = quote!(::anaso_site_api_models::stela);
+    let Some(condition) = condition else {
+        return quote!(::std::option::Option::None);
+    };
+    let condition = match condition.value().split_once('=') {
+        Some((name, value)) => {
+            let (name, value) = (name.trim(), value.trim());
+            quote!(#stela::FormCondition::Equals(::std::sync::Arc::new(
+                #stela::FormConditionValue {
+                    name: #name.to_string(),
+                    value: #value.to_string(),
+                }
+            )))
+        }
+        None => {
+            let name = condition.value();
+            let name = name.trim();
+            quote!(#stela::FormCondition::Checked(::std::sync::Arc::new(
+                #stela::FormConditionInput {
+                    name: #name.to_string(),
+                }
+            )))
+        }
+    };
+    quote!(::std::option::Option::Some(#condition))
+}
+
+fn opt_string(lit: &Option<LitStr>) -> TokenStream2 {
+    match lit {
+        Some(lit) => quote!(::std::option::Option::Some(#lit.to_string())),
+        None => quote!(::std::option::Option::None),
+    }
+}
+
+fn opt_int(lit: &Option<Lit>) -> Result<TokenStream2> {
+    match lit {
+        Some(Lit::Int(lit)) => Ok(quote!(::std::option::Option::Some(#lit))),
+        Some(lit) => Err(Error::new_spanned(lit, "expected an integer")),
+        None => Ok(quote!(::std::option::Option::None)),
+    }
+}
+
+fn opt_float(lit: &Option<Lit>) -> Result<TokenStream2> {
+    let value = match lit {
+        Some(Lit::Int(lit)) => lit.base10_parse::<f64>()?,
+        Some(Lit::Float(lit)) => lit.base10_parse::<f64>()?,
+        Some(lit) => return Err(Error::new_spanned(lit, "expected a number")),
+        None => return Ok(quote!(::std::option::Option::None)),
+    };
+    let value = proc_macro2::Literal::f64_suffixed(value);
+    Ok(quote!(::std::option::Option::Some(#value)))
+}
+
+/// The last path segment of `ty`, and its single generic argument if any.
+fn type_name(ty: &Type) -> Option<(&Ident, Option<&Type>)> {
+    let Type::Path(path) = ty else { return None };
+    if path.qself.is_some() {
+        return None;
+    }
+    let segment = path.path.segments.last()?;
+    let arg = match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            syn::GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    };
+    Some((&segment.ident, arg))
+}
+
+/// `T` if `ty` is `Option<T>`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    match type_name(ty) {
+        Some((ident, arg)) if ident == "Option" => arg,
+        _ => None,
+    }
+}
+
+fn is_bool(ty: &Type) -> bool {
+    type_name(ty).is_some_and(|(ident, _)| ident == "bool")
+}
+
+fn is_number(ty: &Type) -> bool {
+    const NUMBERS: &[&str] = &["i32", "i64", "u32", "u64", "usize", "f32", "f64"];
+    let ty = option_inner(ty).unwrap_or(ty);
+    type_name(ty).is_some_and(|(ident, _)| NUMBERS.iter().any(|n| ident == n))
+}
+
+/// `FormInput` expression for one field.
+fn input_expr(ident: &Ident, ty: &Type, attrs: &FieldAttrs) -> Result<(LitStr, TokenStream2)> {
+    let stela = quote!(::anaso_site_api_models::stela);
+    let arc = quote!(::std::sync::Arc::new);
+    let name = attrs
+        .name
+        .clone()
+        .unwrap_or_else(|| LitStr::new(&ident.to_string(), ident.span()));
+    let title = opt_string(&attrs.title);
+    let required = option_inner(ty).is_none();
+    let kind = match &attrs.input {
+        Some(kind) => kind.value(),
+        None if is_bool(ty) => "checkbox".to_string(),
+        None if is_number(ty) => "number".to_string(),
+        None => "text".to_string(),
+    };
+    let (min, max) = if kind == "number" {
+        (opt_float(&attrs.min)?, opt_float(&attrs.max)?)
+    } else {
+        (opt_int(&attrs.min)?, opt_int(&attrs.max)?)
+    };
+    let span = attrs
+        .input
+        .as_ref()
+        .map_or_else(|| ident.span(), LitStr::span);
+
+    let expr = match kind.as_str() {
+        "text" => {
+            let filter = opt_filter(&attrs.filter)?;
+            let esperanto = attrs.esperanto;
+            quote!(#stela::FormInput::Text(#arc(#stela::FormInputText {
+                title: #title,
+                name: ::std::option::Option::Some(#name.to_string()),
+                initial_value: ::std::option::Option::None,
+                length_min: #min,
+                length_max: #max,
+                esperanto: #esperanto,
+                filter: #filter,
+            })))
+        }
+        "markdown" => quote!(#stela::FormInput::Markdown(#arc(#stela::FormInputMarkdown {
+            title: #title,
+            name: #name.to_string(),
+            initial_value: ::std::option::Option::None,
+            length_min: #min,
+            length_max: #max,
+        }))),
+        "checkbox" => quote!(#stela::FormInput::Checkbox(#arc(#stela::FormInputCheckbox {
+            title: #title,
+            name: #name.to_string(),
+            default_checked: ::std::option::Option::None,
+        }))),
+        "radio" => {
+            if attrs.options.is_empty() {
+                return Err(Error::new(span, "radio inputs need `options = [...]`"));
+            }
+            let options = &attrs.options;
+            quote!(#stela::FormInput::Radio(#arc(#stela::FormInputRadio {
+                title: #title,
+                name: #name.to_string(),
+                initial_index: ::std::option::Option::None,
+                options: ::std::vec![#(#stela::RadioButton {
+                    value: #options.to_string(),
+                    title: ::std::option::Option::Some(#options.to_string()),
+                }),*],
+            })))
+        }
+        "image" => quote!(#stela::FormInput::Image(#arc(#stela::FormInputImage {
+            title: #title,
+            name: ::std::option::Option::Some(#name.to_string()),
+            initial_image: ::std::option::Option::None,
+            preview_style: #stela::ImagePreviewStyle::LargeRectangle,
+            constraints: ::std::default::Default::default(),
+            crop_aspect: ::std::option::Option::None,
+        }))),
+        "turnstile" => {
+            let Some(sitekey) = &attrs.sitekey else {
+                return Err(Error::new(
+                    span,
+                    "turnstile inputs need `sitekey = \"...\"`",
+                ));
+            };
+            let size = opt_variant(
+                &attrs.size,
+                "TurnstileSize",
+                &[
+                    ("normal", "Normal"),
+                    ("compact", "Compact"),
+                    ("flexible", "Flexible"),
+                ],
+            )?;
+            let theme = opt_variant(
+                &attrs.theme,
+                "TurnstileTheme",
+                &[("light", "Light"), ("dark", "Dark"), ("auto", "Auto")],
+            )?;
+            let appearance = opt_variant(
+                &attrs.appearance,
+                "TurnstileAppearance",
+                &[
+                    ("always", "Always"),
+                    ("execute", "Execute"),
+                    ("interaction-only", "InteractionOnly"),
+                ],
+            )?;
+            let execution = opt_variant(
+                &attrs.execution,
+                "TurnstileExecution",
+                &[("render", "Render"), ("execute", "Execute")],
+            )?;
+            let retry = opt_variant(
+                &attrs.retry,
+                "TurnstileRetry",
+                &[("auto", "Auto"), ("never", "Never")],
+            )?;
+            // Checked at compile time by the same functions that check
+            // them at runtime.
+            let language = match &attrs.language {
+                Some(tag) => quote_spanned!(tag.span()=> ::std::option::Option::Some({
+                    const _: () = ::std::assert!(
+                        #stela::TurnstileLanguage::is_valid(#tag),
+                        "expected `auto` or a language code such as `eo` or `pt-BR`",
+                    );
+                    #stela::TurnstileLanguage::from_valid(#tag)
+                })),
+                None => quote!(::std::option::Option::None),
+            };
+            let action = match &attrs.action {
+                Some(action) => quote_spanned!(action.span()=> ::std::option::Option::Some({
+                    const _: () = ::std::assert!(
+                        #stela::turnstile::is_valid_data(
+                            #action,
+                            #stela::turnstile::ACTION_MAX_LEN,
+                        ),
+                        "expected 1 to 32 of `a-z`, `A-Z`, `0-9`, `-` and `_`",
+                    );
+                    #action.to_string()
+                })),
+                None => quote!(::std::option::Option::None),
+            };
+            quote!(#stela::FormInput::CfTurnstile(#arc(#stela::FormInputCfTurnstile {
+                class: ::std::option::Option::None,
+                sitekey: #sitekey.to_string(),
+                response_field_name: ::std::option::Option::Some(#name.to_string()),
+                size: #size,
+                language: #language,
+                theme: #theme,
+                appearance: #appearance,
+                execution: #execution,
+                retry: #retry,
+                action: #action,
+                cdata: ::std::option::Option::None,
+            })))
+        }
+        "select" => {
+            if attrs.options.is_empty() {
+                return Err(Error::new(span, "select inputs need `options = [...]`"));
+            }
+            let options = &attrs.options;
+            quote!(#stela::FormInput::Select(#arc(#stela::FormInputSelect {
+                title: #title,
+                name: #name.to_string(),
+                initial_value: ::std::option::Option::None,
+                placeholder: ::std::option::Option::None,
+                options: ::std::vec![#(#stela::SelectOption {
+                    value: #options.to_string(),
+                    title: ::std::option::Option::Some(#options.to_string()),
+                }),*],
+                required: #required,
+            })))
+        }
+        "multiselect" => {
+            if attrs.options.is_empty() && !attrs.custom {
+                return Err(Error::new(
+                    span,
+                    "multiselect inputs need `options = [...]` or `custom`",
+                ));
+            }
+            let options = &attrs.options;
+            let style = match attrs.style.as_ref().map(LitStr::value).as_deref() {
+                None | Some("checkboxes") => quote!(Checkboxes),
+                Some("chips") => quote!(Chips),
+                Some(_) => {
+                    return Err(Error::new_spanned(
+                        &attrs.style,
+                        "expected `checkboxes` or `chips`",
+                    ))
+                }
+            };
+            let allow_custom = attrs.custom;
+            let custom_filter = opt_filter(&attrs.filter)?;
+            quote!(#stela::FormInput::MultiSelect(#arc(#stela::FormInputMultiSelect {
+                title: #title,
+                name: #name.to_string(),
+                style: #stela::MultiSelectStyle::#style,
+                options: ::std::vec![#(#stela::SelectOption {
+                    value: #options.to_string(),
+                    title: ::std::option::Option::Some(#options.to_string()),
+                }),*],
+                initial_values: ::std::vec::Vec::new(),
+                min_selected: #min,
+                max_selected: #max,
+                allow_custom: #allow_custom,
+                custom_filter: #custom_filter,
+                custom_length_max: ::std::option::Option::None,
+            })))
+        }
+        "number" => {
+            let step = opt_float(&attrs.step)?;
+            quote!(#stela::FormInput::Number(#arc(#stela::FormInputNumber {
+                title: #title,
+                name: #name.to_string(),
+                initial_value: ::std::option::Option::None,
+                min: #min,
+                max: #max,
+                step: #step,
+                required: #required,
+            })))
+        }
+        "date" | "time" | "datetime" => {
+            let variant = match kind.as_str() {
+                "date" => quote!(Date),
+                "time" => quote!(Time),
+                _ => quote!(DateTime),
+            };
+            quote!(#stela::FormInput::DateTime(#arc(#stela::FormInputDateTime {
+                title: #title,
+                name: #name.to_string(),
+                kind: #stela::DateTimeKind::#variant,
+                initial_value: ::std::option::Option::None,
+                min: ::std::option::Option::None,
+                max: ::std::option::Option::None,
+                required: #required,
+            })))
+        }
+        "email" => quote!(#stela::FormInput::Email(#arc(#stela::FormInputEmail {
+            title: #title,
+            name: #name.to_string(),
+            initial_value: ::std::option::Option::None,
+            required: #required,
+        }))),
+        "url" => quote!(#stela::FormInput::Url(#arc(#stela::FormInputUrl {
+            title: #title,
+            name: #name.to_string(),
+            initial_value: ::std::option::Option::None,
+            required: #required,
+        }))),
+        "password" => {
+            let confirm_name = opt_string(&attrs.confirm);
+            let confirm_title = opt_string(&attrs.confirm_title);
+            let autocomplete = opt_string(&attrs.autocomplete);
+            quote!(#stela::FormInput::Password(#arc(#stela::FormInputPassword {
+                title: #title,
+                name: #name.to_string(),
+                length_min: #min,
+                length_max: #max,
+                confirm_name: #confirm_name,
+                confirm_title: #confirm_title,
+                autocomplete: #autocomplete,
+            })))
+        }
+        _ => {
+            return Err(Error::new(
+                span,
+                "expected `text`, `markdown`, `checkbox`, `radio`, `select`, `multiselect`, \
+                 `number`, `date`, `time`, `datetime`, `email`, `url`, `password`, `image`, \
+                 or `turnstile`",
+            ))
+        }
+    };
+    if attrs.show_if.is_none() && attrs.require_if.is_none() {
+        return Ok((name, expr));
+    }
+    if required {
+        return Err(Error::new_spanned(
+            ty,
+            "fields with `show_if` or `require_if` must be `Option`s",
+        ));
+    }
+    let show_when = opt_condition(&attrs.show_if);
+    let require_when = opt_condition(&attrs.require_if);
+    let expr = quote!(#stela::FormInput::Conditional(#arc(#stela::FormInputConditional {
+        show_when: #show_when,
+        require_when: #require_when,
+        inputs: ::std::vec![#expr],
+    })));
+    Ok((name, expr))
+}
+
+fn derive_struct(input: &DeriveInput) -> Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        unreachable!()
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "FromFormCallData needs a struct with named fields",
+        ));
+    };
+    let attrs = form_attrs(input)?;
+    let Some(form_name) = &attrs.name else {
+        return Err(Error::new(
+            Span::call_site(),
+            "missing `#[form(name = \"...\")]`",
+        ));
+    };
+
+    let stela = quote!(::anaso_site_api_models::stela);
+    let mut inputs = Vec::new();
+    let mut idents = Vec::new();
+    let mut reads = Vec::new();
+    for field in &fields.named {
+        let ident = field.ident.as_ref().expect("named field");
+        let ty = &field.ty;
+        let (name, expr) = input_expr(ident, ty, &field_attrs(field)?)?;
+        inputs.push(expr);
+        idents.push(ident);
+        reads.push(quote!(reader.read::<#ty>(#name)));
+    }
+    if let Some(submit) = &attrs.submit {
+        inputs.push(quote!(#stela::FormInput::Motions(::std::sync::Arc::new(
+            #stela::FormInputMotions {
+                vertical_list: ::std::option::Option::None,
+                motions: ::std::vec![#stela::VisualMotion {
+                    title: ::std::option::Option::Some(#submit.to_string()),
+                    aria_label: ::std::option::Option::None,
+                    icon: ::std::option::Option::None,
+                    image: ::std::option::Option::None,
+                    initial_toggle: ::std::option::Option::None,
+                    variant: #stela::MotionVariant::Button,
+                    color: #stela::MotionColor::Primary,
+                    motion: #stela::Motion::Submit(::std::sync::Arc::new(#stela::MotionSubmit {})),
+                }],
+            }
+        ))));
+    }
+
+    let ident = &input.ident;
+    let header = opt_string(&attrs.header);
+    let subheader = opt_string(&attrs.subheader);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #stela::from_form::FormDefinition for #ident #ty_generics #where_clause {
+            const FORM_NAME: &'static str = #form_name;
+
+            fn section_form() -> #stela::SectionForm {
+                #stela::SectionForm {
+                    header: #header,
+                    subheader: #subheader,
+                    form_name: #form_name.to_string(),
+                    extra_data: ::std::option::Option::None,
+                    noscript_text: ::std::option::Option::None,
+                    inputs: ::std::vec![#(#inputs),*],
+                    steps: ::std::vec::Vec::new(),
+                    initial_step: ::std::option::Option::None,
+                    step_progress: #stela::StepProgress::Unknown,
+                    autosave: ::std::option::Option::None,
+                    etag: ::std::option::Option::None,
+                }
+            }
+        }
+
+        impl #impl_generics #stela::from_form::FromFormCallData for #ident #ty_generics #where_clause {
+            fn from_form_call_data(
+                data: &#stela::FormCallData,
+            ) -> ::std::result::Result<Self, ::std::boxed::Box<#stela::FormResponse>> {
+                let form = <Self as #stela::from_form::FormDefinition>::section_form();
+                let mut reader = #stela::from_form::FieldReader::new(data, &form)?;
+                match (#(#reads,)*) {
+                    (#(::std::option::Option::Some(#idents),)*) => {
+                        ::std::result::Result::Ok(Self { #(#idents),* })
+                    }
+                    _ => ::std::result::Result::Err(reader.finish()),
+                }
+            }
+        }
+    })
+}
+
+fn derive_enum(input: &DeriveInput) -> Result<TokenStream2> {
+    let Data::Enum(data) = &input.data else {
+        unreachable!()
+    };
+    let stela = quote!(::anaso_site_api_models::stela);
+    let mut arms = Vec::new();
+    for variant in &data.variants {
+        let Fields::Unnamed(fields) = &variant.fields else {
+            return Err(Error::new_spanned(
+                variant,
+                "expected a variant with one unnamed field",
+            ));
+        };
+        let mut fields = fields.unnamed.iter();
+        let (Some(field), None) = (fields.next(), fields.next()) else {
+            return Err(Error::new_spanned(
+                variant,
+                "expected a variant with one unnamed field",
+            ));
+        };
+        let ty = field.ty.to_token_stream();
+        let variant = &variant.ident;
+        arms.push(quote! {
+            if data.form_name == <#ty as #stela::from_form::FormDefinition>::FORM_NAME {
+                return <#ty as #stela::from_form::FromFormCallData>::from_form_call_data(data)
+                    .map(Self::#variant);
+            }
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #stela::from_form::FromFormCallData for #ident #ty_generics #where_clause {
+            fn from_form_call_data(
+                data: &#stela::FormCallData,
+            ) -> ::std::result::Result<Self, ::std::boxed::Box<#stela::FormResponse>> {
+                #(#arms)*
+                ::std::result::Result::Err(#stela::from_form::unknown_form(data))
+            }
+        }
+    })
+}
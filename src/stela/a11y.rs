@@ -0,0 +1,278 @@
+//! Accessibility audit for Stela pages.
+//!
+//! Findings are reported as [`Lint`]s with codes from `STL0101` up, so they can
+//! be filtered and asserted on the same way as [`Page::lint`].
+//!
+//! Headings are assumed to render as: the page hero title at level 1, section
+//! titles at level 2, and post titles, form headers, and section heroes one
+//! level below their section title. The first heading may be at any level, so
+//! a page without a hero title can start at its section titles.
+
+use crate::stela::{
+    lint::{Lint, LintCode, Linter},
+    FormInput, Hero, Image, Navbar, Page, Section, SectionForm, VisualMotion,
+};
+
+impl Page {
+    /// Check the page for accessibility problems.
+    pub fn a11y_audit(&self) -> Vec<Lint> {
+        let mut auditor = Auditor::default();
+        auditor.page(self);
+        auditor.linter.lints
+    }
+}
+
+impl Navbar {
+    /// Check the navbar for accessibility problems.
+    pub fn a11y_audit(&self) -> Vec<Lint> {
+        let mut auditor = Auditor::default();
+        auditor.navbar(self);
+        auditor.linter.lints
+    }
+}
+
+fn has_text(text: Option<&str>) -> bool {
+    text.is_some_and(|text| !text.trim().is_empty())
+}
+
+#[derive(Default)]
+struct Auditor {
+    linter: Linter,
+    /// Level of the last heading, if there was one.
+    last_heading: Option<u8>,
+}
+
+impl Auditor {
+    fn scoped(&mut self, segment: impl Into<String>, f: impl FnOnce(&mut Self)) {
+        self.linter.path_push(segment.into());
+        f(self);
+        self.linter.path_pop();
+    }
+
+    fn push(&mut self, code: LintCode, message: impl Into<String>) {
+        self.linter.push(code, message.into());
+    }
+
+    fn heading(&mut self, field: &str, text: Option<&str>, level: u8) {
+        if !has_text(text) {
+            return;
+        }
+        if let Some(last) = self.last_heading.filter(|last| level > last + 1) {
+            self.scoped(field, |a| {
+                a.push(
+                    LintCode::HeadingLevelSkipped,
+                    format!("level {level} heading follows level {last}"),
+                );
+            });
+        }
+        self.last_heading = Some(level);
+    }
+
+    fn image(&mut self, field: &str, image: Option<&Image>, fallback: Option<&str>) {
+        if image.is_some_and(|image| image.alt.is_none()) && !has_text(fallback) {
+            self.scoped(field, |a| {
+                a.push(LintCode::MissingImageAlt, "image has no alt text");
+            });
+        }
+    }
+
+    fn page(&mut self, page: &Page) {
+        if let Some(hero) = &page.hero {
+            self.scoped("hero", |a| a.hero(hero, 1));
+        }
+        if let Some(sidebar) = &page.sidebar {
+            self.scoped("sidebar", |a| {
+                for (i, card) in sidebar.cards.iter().enumerate() {
+                    a.scoped(format!("cards[{i}]"), |a| {
+                        a.visual_motions("motions", &card.motions)
+                    });
+                }
+            });
+        }
+        for (i, visual) in page.sections.iter().enumerate() {
+            self.scoped(format!("sections[{i}]"), |a| {
+                a.heading("title", visual.title.as_deref(), 2);
+                let level = if has_text(visual.title.as_deref()) {
+                    3
+                } else {
+                    2
+                };
+                a.scoped("section", |a| a.section(&visual.section, level));
+            });
+        }
+    }
+
+    fn navbar(&mut self, navbar: &Navbar) {
+        self.image(
+            "left_side_icon_image",
+            Some(&navbar.left_side_icon_image),
+            navbar.left_side_secondary_text.as_deref(),
+        );
+        self.visual_motions("right_side_motions", &navbar.right_side_motions);
+        self.visual_motions("side_motions", &navbar.side_motions);
+    }
+
+    fn hero(&mut self, hero: &Hero, level: u8) {
+        self.heading("title", hero.title.as_deref(), level);
+        let fallback = hero.primary_image_fallback_text.as_deref();
+        self.image(
+            "primary_image_light",
+            hero.primary_image_light.as_ref(),
+            fallback,
+        );
+        self.image(
+            "primary_image_dark",
+            hero.primary_image_dark.as_ref(),
+            fallback,
+        );
+        self.visual_motions("motions", &hero.motions);
+    }
+
+    fn section(&mut self, section: &Section, level: u8) {
+        match section {
+            Section::Form(form) => self.scoped("Form", |a| a.form(form, level)),
+            Section::Hero(hero) => self.scoped("Hero.hero", |a| a.hero(&hero.hero, level)),
+            Section::Post(post) => self.scoped("Post", |a| {
+                a.heading("title", post.title.as_deref(), level);
+                a.image("image", post.image.as_ref(), None);
+                a.visual_motions("motions_tl", &post.motions_tl);
+                a.visual_motions("motions_tr", &post.motions_tr);
+                a.visual_motions("motions_br", &post.motions_br);
+                a.visual_motions("motions_bl", &post.motions_bl);
+            }),
+            Section::Tiles(tiles) => self.scoped("Tiles", |a| {
+                for (i, tile) in tiles.tiles.iter().enumerate() {
+                    let named = has_text(tile.header.as_deref())
+                        || has_text(tile.body_text.as_deref())
+                        || has_text(tile.image.as_ref().and_then(|i| i.alt.as_deref()));
+                    if !named {
+                        a.scoped(format!("tiles[{i}]"), |a| {
+                            a.push(
+                                LintCode::MissingAccessibleName,
+                                "tile has no header, body text, or image alt text",
+                            );
+                        });
+                    }
+                }
+            }),
+            Section::Sponsor(_) | Section::Unknown => {}
+        }
+    }
+
+    fn form(&mut self, form: &SectionForm, level: u8) {
+        self.heading("header", form.header.as_deref(), level);
+        for (i, input) in form.inputs.iter().enumerate() {
+            self.scoped(format!("inputs[{i}]"), |a| a.input(input));
+        }
+        for (i, step) in form.steps.iter().enumerate() {
+            for (j, input) in step.inputs.iter().enumerate() {
+                self.scoped(format!("steps[{i}].inputs[{j}]"), |a| a.input(input));
+            }
+        }
+    }
+
+    fn label(&mut self, title: Option<&str>) {
+        if !has_text(title) {
+            self.push(
+                LintCode::MissingInputLabel,
+                "input has no title to label it",
+            );
+        }
+    }
+
+    fn input(&mut self, input: &FormInput) {
+        match input {
+            FormInput::Checkbox(checkbox) => {
+                self.scoped("Checkbox", |a| a.label(checkbox.title.as_deref()))
+            }
+            FormInput::Tabs(tabs) => self.scoped("Tabs", |a| {
+                for (i, tab) in tabs.tabs.iter().enumerate() {
+                    a.scoped(format!("tabs[{i}]"), |a| {
+                        a.label(Some(&tab.title));
+                        a.scoped("input", |a| a.input(&tab.input));
+                    });
+                }
+            }),
+            FormInput::Image(image) => self.scoped("Image", |a| {
+                a.label(image.title.as_deref());
+                a.image("initial_image", image.initial_image.as_ref(), None);
+            }),
+            FormInput::Markdown(markdown) => {
+                self.scoped("Markdown", |a| a.label(markdown.title.as_deref()))
+            }
+            FormInput::Motions(motions) => {
+                self.scoped("Motions", |a| a.visual_motions("motions", &motions.motions))
+            }
+            FormInput::Radio(radio) => self.scoped("Radio", |a| {
+                a.label(radio.title.as_deref());
+                for (i, option) in radio.options.iter().enumerate() {
+                    a.scoped(format!("options[{i}]"), |a| {
+                        a.label(option.title.as_deref())
+                    });
+                }
+            }),
+            FormInput::Conditional(conditional) => self.scoped("Conditional", |a| {
+                for (i, input) in conditional.inputs.iter().enumerate() {
+                    a.scoped(format!("inputs[{i}]"), |a| a.input(input));
+                }
+            }),
+            FormInput::Subsection(subsection) => self.scoped("Subsection", |a| {
+                for (i, input) in subsection.inputs.iter().enumerate() {
+                    a.scoped(format!("inputs[{i}]"), |a| a.input(input));
+                }
+            }),
+            FormInput::Text(text) => self.scoped("Text", |a| a.label(text.title.as_deref())),
+            FormInput::Select(select) => self.scoped("Select", |a| {
+                a.label(select.title.as_deref());
+                for (i, option) in select.options.iter().enumerate() {
+                    a.scoped(format!("options[{i}]"), |a| {
+                        a.label(option.title.as_deref())
+                    });
+                }
+            }),
+            FormInput::MultiSelect(multi) => self.scoped("MultiSelect", |a| {
+                a.label(multi.title.as_deref());
+                for (i, option) in multi.options.iter().enumerate() {
+                    a.scoped(format!("options[{i}]"), |a| {
+                        a.label(option.title.as_deref())
+                    });
+                }
+            }),
+            FormInput::Number(number) => {
+                self.scoped("Number", |a| a.label(number.title.as_deref()))
+            }
+            FormInput::DateTime(date_time) => {
+                self.scoped("DateTime", |a| a.label(date_time.title.as_deref()))
+            }
+            FormInput::Email(email) => self.scoped("Email", |a| a.label(email.title.as_deref())),
+            FormInput::Url(url) => self.scoped("Url", |a| a.label(url.title.as_deref())),
+            FormInput::Password(password) => self.scoped("Password", |a| {
+                a.label(password.title.as_deref());
+                if password.confirm_name.is_some() {
+                    a.scoped("confirm_title", |a| {
+                        a.label(password.confirm_title.as_deref())
+                    });
+                }
+            }),
+            // Turnstile labels itself, in the language it detects unless
+            // `language` says otherwise.
+            FormInput::CfTurnstile(_) | FormInput::Unknown => {}
+        }
+    }
+
+    fn visual_motions(&mut self, field: &str, motions: &[VisualMotion]) {
+        for (i, visual) in motions.iter().enumerate() {
+            let named = has_text(visual.title.as_deref())
+                || has_text(visual.aria_label.as_deref())
+                || has_text(visual.image.as_ref().and_then(|i| i.alt.as_deref()));
+            if !named {
+                self.scoped(format!("{field}[{i}]"), |a| {
+                    a.push(
+                        LintCode::MissingAccessibleName,
+                        "motion has no title, aria_label, or image alt text",
+                    );
+                });
+            }
+        }
+    }
+}
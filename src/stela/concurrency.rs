@@ -0,0 +1,307 @@
+//! Catching edits of outdated versions, so the last submit doesn't silently
+//! win.
+//!
+//! 1. The API serves an edit form with the version of what it edits in
+//!    [`SectionForm::etag`], and keeps the values the form was filled with.
+//! 2. The client sends the etag back in [`FormCallData::etag`]. Without it,
+//!    the submission isn't checked; see [`FormCallData::is_stale`].
+//! 3. In `form_submit`, the API checks [`FormCallData::is_stale`] against the
+//!    current etag. For a stale submission, [`SectionForm::merge_stale`] of
+//!    the current form merges the user's changes into the current values.
+//!    If that works, the merged data is saved as usual. If not, the API
+//!    answers with
+//!    [`FormResponse::with_conflict`](crate::stela::FormResponse::with_conflict),
+//!    which carries the current values and etag, so the user can redo their
+//!    changes and submit again.
+//!
+//! Text and markdown inputs are merged line by line with [`merge_text`].
+//! Other inputs only merge when at most one side changed them.
+
+use std::{collections::HashMap, fmt};
+
+use crate::stela::{FormCallData, FormConflict, FormInput, FormValue, SectionForm};
+
+impl FormCallData {
+    /// Whether the data was submitted from a form with another etag than
+    /// `current`.
+    ///
+    /// Submissions without an etag are never stale, so a client that doesn't
+    /// send one back always overwrites. To require the check, reject
+    /// submissions to a form with an etag whose [`FormCallData::etag`] is
+    /// `None`.
+    pub fn is_stale(&self, current: &str) -> bool {
+        self.etag.as_deref().is_some_and(|etag| etag != current)
+    }
+}
+
+impl SectionForm {
+    /// Merge a stale submission into the current values. `self` is the
+    /// current form, with the current etag.
+    ///
+    /// `base` are the values `data`'s form was filled with, and `current` are
+    /// the values now, by input `name` as they would be submitted. Each input
+    /// keeps the side that changed it. Inputs changed on both sides are merged
+    /// with [`merge_text`] if they are text or markdown, and conflict
+    /// otherwise.
+    ///
+    /// Passwords and Turnstile tokens aren't merged: the merged data has the
+    /// submitted ones, and the conflict has none.
+    ///
+    /// The merged data gets the current etag. The conflict lists every input
+    /// that couldn't be merged.
+    pub fn merge_stale(
+        &self,
+        data: &FormCallData,
+        base: &HashMap<String, FormValue>,
+        current: &HashMap<String, FormValue>,
+    ) -> Result<FormCallData, FormConflict> {
+        let secrets = self.secret_names();
+        let mut names: Vec<&String> = data
+            .fields
+            .keys()
+            .chain(base.keys())
+            .chain(current.keys())
+            .filter(|name| !secrets.contains(&name.as_str()))
+            .collect();
+        names.sort();
+        names.dedup();
+
+        let mut fields: HashMap<String, FormValue> = data
+            .fields
+            .iter()
+            .filter(|(name, _)| secrets.contains(&name.as_str()))
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        let mut conflicting = Vec::new();
+        for name in names {
+            let ours = data.fields.get(name);
+            let theirs = current.get(name);
+            match self.merge_value(name, base.get(name), ours, theirs) {
+                Ok(Some(value)) => {
+                    fields.insert(name.clone(), value);
+                }
+                Ok(None) => {}
+                Err(()) => conflicting.push(name.clone()),
+            }
+        }
+
+        if !conflicting.is_empty() {
+            return Err(FormConflict {
+                etag: self.etag.clone(),
+                fields: current
+                    .iter()
+                    .filter(|(name, _)| !secrets.contains(&name.as_str()))
+                    .map(|(name, value)| (name.clone(), value.clone()))
+                    .collect(),
+                conflicting,
+            });
+        }
+        Ok(FormCallData {
+            form_name: data.form_name.clone(),
+            extra_data: data.extra_data.clone(),
+            fields,
+            step: data.step,
+            etag: self.etag.clone(),
+        })
+    }
+
+    /// The merged value of input `name`, or `None` if it's not submitted.
+    ///
+    /// The sides are compared as [`FormCallData::value`] reads them, so a
+    /// checkbox submitted as `"on"` is the same as a stored `true`.
+    fn merge_value(
+        &self,
+        name: &str,
+        base: Option<&FormValue>,
+        ours: Option<&FormValue>,
+        theirs: Option<&FormValue>,
+    ) -> Result<Option<FormValue>, ()> {
+        let [typed_base, typed_ours, typed_theirs] =
+            [base, ours, theirs].map(|value| self.typed_value(name, value));
+        if typed_ours == typed_base || typed_ours == typed_theirs {
+            return Ok(theirs.cloned());
+        }
+        if typed_theirs == typed_base {
+            return Ok(ours.cloned());
+        }
+        if !matches!(
+            self.input(name),
+            Some(FormInput::Text(_) | FormInput::Markdown(_))
+        ) {
+            return Err(());
+        }
+        let (Some(base), Some(ours), Some(theirs)) = (text(base), text(ours), text(theirs)) else {
+            return Err(());
+        };
+        let merged = merge_text(base, ours, theirs).map_err(|_| ())?;
+        Ok((!merged.is_empty()).then_some(FormValue::Text(merged)))
+    }
+
+    /// `value` of input `name` read by the input's kind, if the form has it.
+    fn typed_value(&self, name: &str, value: Option<&FormValue>) -> Option<FormValue> {
+        let Some(input) = self.input(name) else {
+            return value.cloned();
+        };
+        let data = FormCallData {
+            form_name: self.form_name.clone(),
+            extra_data: None,
+            fields: value
+                .map(|value| (name.to_string(), value.clone()))
+                .into_iter()
+                .collect(),
+            step: None,
+            etag: None,
+        };
+        data.value(input)
+    }
+}
+
+/// The text of a text input. An empty one isn't submitted.
+fn text(value: Option<&FormValue>) -> Option<&str> {
+    match value {
+        None => Some(""),
+        Some(value) => value.as_text(),
+    }
+}
+
+/// Most lines [`merge_text`] compares: the changed lines of the base times
+/// those of one side.
+pub const MAX_COMPARED_LINES: usize = 1_000_000;
+
+/// Lines that were changed differently on both sides.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MergeConflict {
+    /// The lines as they were.
+    pub base: String,
+    /// The lines as changed by the user.
+    pub ours: String,
+    /// The lines as changed by someone else.
+    pub theirs: String,
+}
+
+impl fmt::Display for MergeConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} was changed to both {:?} and {:?}",
+            self.base, self.ours, self.theirs
+        )
+    }
+}
+
+impl std::error::Error for MergeConflict {}
+
+/// Three-way merge of texts, line by line.
+///
+/// `ours` and `theirs` were both edited from `base`. Lines changed on one side
+/// take that side; lines changed the same way on both sides are kept once.
+/// Fails with the first lines changed differently on both sides, including
+/// insertions at the same place. Texts whose changed parts are too long to
+/// compare, more than [`MAX_COMPARED_LINES`] lines on one side times lines on
+/// the other, conflict as a whole.
+pub fn merge_text(base: &str, ours: &str, theirs: &str) -> Result<String, MergeConflict> {
+    if ours == theirs || theirs == base {
+        return Ok(ours.to_string());
+    }
+    if ours == base {
+        return Ok(theirs.to_string());
+    }
+
+    let conflict = || MergeConflict {
+        base: base.to_string(),
+        ours: ours.to_string(),
+        theirs: theirs.to_string(),
+    };
+    let base: Vec<&str> = base.split_inclusive('\n').collect();
+    let ours: Vec<&str> = ours.split_inclusive('\n').collect();
+    let theirs: Vec<&str> = theirs.split_inclusive('\n').collect();
+    let in_ours = matching_lines(&base, &ours).ok_or_else(conflict)?;
+    let in_theirs = matching_lines(&base, &theirs).ok_or_else(conflict)?;
+
+    let mut merged = String::new();
+    let (mut i, mut a, mut b) = (0, 0, 0);
+    loop {
+        // The next base line both sides kept.
+        let stable = (i..base.len()).find_map(|j| Some((j, in_ours[j]?, in_theirs[j]?)));
+        if stable == Some((i, a, b)) {
+            merged.push_str(base[i]);
+            (i, a, b) = (i + 1, a + 1, b + 1);
+            continue;
+        }
+        let (j, end_a, end_b) = stable.unwrap_or((base.len(), ours.len(), theirs.len()));
+        let chunk = [&base[i..j], &ours[a..end_a], &theirs[b..end_b]];
+        let [base_chunk, ours_chunk, theirs_chunk] = chunk.map(|lines| lines.concat());
+        if ours_chunk == base_chunk || ours_chunk == theirs_chunk {
+            merged.push_str(&theirs_chunk);
+        } else if theirs_chunk == base_chunk {
+            merged.push_str(&ours_chunk);
+        } else {
+            return Err(MergeConflict {
+                base: base_chunk,
+                ours: ours_chunk,
+                theirs: theirs_chunk,
+            });
+        }
+        if stable.is_none() {
+            return Ok(merged);
+        }
+        (i, a, b) = (j, end_a, end_b);
+    }
+}
+
+/// For each line of `base`, the line of `other` it matches in a longest
+/// common subsequence. `None` if the lines between the common start and end
+/// are too many to compare.
+fn matching_lines(base: &[&str], other: &[&str]) -> Option<Vec<Option<usize>>> {
+    let prefix = base.iter().zip(other).take_while(|(a, b)| a == b).count();
+    let suffix = base[prefix..]
+        .iter()
+        .rev()
+        .zip(other[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    // Lines in the common start and end match their counterparts.
+    let mut matches: Vec<Option<usize>> = (0..base.len())
+        .map(|i| {
+            if i < prefix {
+                Some(i)
+            } else if i >= base.len() - suffix {
+                Some(other.len() - (base.len() - i))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    let base = &base[prefix..base.len() - suffix];
+    let other = &other[prefix..other.len() - suffix];
+    if base.len().saturating_mul(other.len()) > MAX_COMPARED_LINES {
+        return None;
+    }
+
+    // lengths[i][j] is the length of the longest common subsequence of
+    // base[i..] and other[j..].
+    let mut lengths = vec![vec![0usize; other.len() + 1]; base.len() + 1];
+    for i in (0..base.len()).rev() {
+        for j in (0..other.len()).rev() {
+            lengths[i][j] = if base[i] == other[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < base.len() && j < other.len() {
+        if base[i] == other[j] {
+            matches[prefix + i] = Some(prefix + j);
+            (i, j) = (i + 1, j + 1);
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    Some(matches)
+}
@@ -0,0 +1,171 @@
+//! Evaluation of [`FormCondition`]s, shared by clients and the API.
+//!
+//! Clients call [`FormInputConditional::is_shown`] with the values entered so
+//! far to decide what to display. [`SectionForm::validate`] and
+//! [`FieldReader`](crate::stela::from_form::FieldReader) call the same
+//! functions on the submission, so a hidden input is never required and never
+//! read.
+//!
+//! Conditions look at the submitted values as
+//! [`FormCallData::value`] reads them, whether or not the input they name is
+//! itself shown.
+
+use crate::stela::{
+    FormCallData, FormCondition, FormInput, FormInputConditional, FormValue, SectionForm,
+};
+
+impl FormCondition {
+    /// Whether the condition holds for `data`, submitted to `form`.
+    ///
+    /// A condition that depends on an [`Unknown`](Self::Unknown) one doesn't
+    /// hold, and neither does its negation.
+    /// [`FormInputConditional::is_shown`] shows inputs behind it anyway.
+    pub fn holds(&self, form: &SectionForm, data: &FormCallData) -> bool {
+        self.evaluate(form, data).unwrap_or(false)
+    }
+
+    /// Whether the condition holds, or `None` if that depends on an unknown
+    /// condition.
+    fn evaluate(&self, form: &SectionForm, data: &FormCallData) -> Option<bool> {
+        Some(match self {
+            Self::Equals(condition) => match value(form, data, &condition.name) {
+                Some(FormValue::Bool(checked)) => condition.value == checked.to_string(),
+                Some(value) => text(&value) == Some(condition.value.as_str()),
+                None => false,
+            },
+            Self::Contains(condition) => match value(form, data, &condition.name) {
+                Some(FormValue::List(values)) => values
+                    .iter()
+                    .any(|value| text(value) == Some(condition.value.as_str())),
+                Some(value) => text(&value).is_some_and(|text| text.contains(&condition.value)),
+                None => false,
+            },
+            Self::Checked(condition) => {
+                value(form, data, &condition.name) == Some(FormValue::Bool(true))
+            }
+            Self::Filled(condition) => {
+                value(form, data, &condition.name).is_some_and(|value| is_filled(&value))
+            }
+            Self::All(conditions) => {
+                let results: Vec<_> = conditions
+                    .iter()
+                    .map(|condition| condition.evaluate(form, data))
+                    .collect();
+                if results.contains(&Some(false)) {
+                    false
+                } else if results.contains(&None) {
+                    return None;
+                } else {
+                    true
+                }
+            }
+            Self::Any(conditions) => {
+                let results: Vec<_> = conditions
+                    .iter()
+                    .map(|condition| condition.evaluate(form, data))
+                    .collect();
+                if results.contains(&Some(true)) {
+                    true
+                } else if results.contains(&None) {
+                    return None;
+                } else {
+                    false
+                }
+            }
+            Self::Not(condition) => !condition.evaluate(form, data)?,
+            Self::Unknown => return None,
+        })
+    }
+
+    /// Names of the inputs this condition looks at.
+    pub fn names(&self) -> Vec<&str> {
+        match self {
+            Self::Equals(condition) | Self::Contains(condition) => vec![&condition.name],
+            Self::Checked(condition) | Self::Filled(condition) => vec![&condition.name],
+            Self::All(conditions) | Self::Any(conditions) => {
+                conditions.iter().flat_map(Self::names).collect()
+            }
+            Self::Not(condition) => condition.names(),
+            Self::Unknown => Vec::new(),
+        }
+    }
+}
+
+/// The value of the input named `name`, read by its kind if the form has it.
+fn value(form: &SectionForm, data: &FormCallData, name: &str) -> Option<FormValue> {
+    match form.input(name) {
+        Some(input) => data.value(input),
+        None => data.fields.get(name).cloned(),
+    }
+}
+
+/// Whether `value` is a checked checkbox, a non-empty list, or non-empty
+/// text.
+pub(crate) fn is_filled(value: &FormValue) -> bool {
+    match value {
+        FormValue::Bool(checked) => *checked,
+        FormValue::List(values) => !values.is_empty(),
+        value => text(value).is_some_and(|text| !text.is_empty()),
+    }
+}
+
+fn text(value: &FormValue) -> Option<&str> {
+    match value {
+        FormValue::Text(text) | FormValue::Image(text) | FormValue::Option(text) => Some(text),
+        FormValue::Bool(_) | FormValue::List(_) => None,
+    }
+}
+
+impl FormInputConditional {
+    /// Whether to show the inputs. They are shown if `show_when` depends on
+    /// an unknown condition, so newer forms show everything.
+    pub fn is_shown(&self, form: &SectionForm, data: &FormCallData) -> bool {
+        self.show_when
+            .as_ref()
+            .is_none_or(|condition| condition.evaluate(form, data) != Some(false))
+    }
+
+    /// Whether the inputs must be filled in. Hidden inputs never are, and
+    /// neither are inputs whose `require_when` depends on an unknown
+    /// condition.
+    pub fn is_required(&self, form: &SectionForm, data: &FormCallData) -> bool {
+        self.is_shown(form, data)
+            && self
+                .require_when
+                .as_ref()
+                .is_some_and(|condition| condition.holds(form, data))
+    }
+}
+
+impl SectionForm {
+    /// Whether the input named `name` is shown, given the values in `data`.
+    ///
+    /// False if any [`FormInputConditional`] around it is hidden, or if the
+    /// form has no such input.
+    pub fn is_shown(&self, name: &str, data: &FormCallData) -> bool {
+        fn find(
+            form: &SectionForm,
+            data: &FormCallData,
+            inputs: &[FormInput],
+            name: &str,
+        ) -> Option<bool> {
+            inputs.iter().find_map(|input| match input {
+                FormInput::Subsection(subsection) => find(form, data, &subsection.inputs, name),
+                FormInput::Tabs(tabs) => tabs
+                    .tabs
+                    .iter()
+                    .find_map(|tab| find(form, data, std::slice::from_ref(&tab.input), name)),
+                FormInput::Conditional(conditional) => find(form, data, &conditional.inputs, name)
+                    .map(|shown| shown && conditional.is_shown(form, data)),
+                _ => (input.name() == Some(name)).then_some(true),
+            })
+        }
+        find(self, data, &self.inputs, name)
+            .or_else(|| {
+                self.steps
+                    .iter()
+                    .find_map(|step| find(self, data, &step.inputs, name))
+            })
+            .unwrap_or(false)
+    }
+}
@@ -0,0 +1,153 @@
+//! Errors that lenient deserialization recovered from.
+//!
+//! Malformed fields fall back to their default and malformed list elements are
+//! skipped, so one bad motion doesn't take down a whole page. Use [`from_json`]
+//! or [`Page::from_json_with_diagnostics`](crate::stela::Page::from_json_with_diagnostics)
+//! to find out what was dropped, and where.
+
+use std::{cell::RefCell, fmt::Write};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::stela::lenient::Tracked;
+
+thread_local! {
+    static SINK: RefCell<Option<Vec<Diagnostic>>> = const { RefCell::new(None) };
+    static PATH: RefCell<Vec<Segment>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A recovered deserialization error.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Where the fallback was applied, such as `sections[3].section.Post.motions_tr[1]`.
+    ///
+    /// Empty for the document root.
+    pub path: String,
+    /// The serde error message.
+    pub error: String,
+    /// What was done instead of failing.
+    pub fallback: Fallback,
+}
+
+/// What lenient deserialization did with a malformed value.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum Fallback {
+    /// The field was replaced with its default, usually `None`.
+    Default,
+    /// The list element was dropped.
+    Skipped,
+    /// The list wasn't a list, and was replaced with an empty one.
+    Empty,
+}
+
+/// Deserialize JSON, collecting every error recovered from along with its path.
+///
+/// Fails only if the JSON is invalid or the root itself doesn't match `T`.
+pub fn from_json<T: DeserializeOwned>(json: &str) -> serde_json::Result<(T, Vec<Diagnostic>)> {
+    let value = serde_json::from_str::<Value>(json)?;
+    let (result, diagnostics) = collect(|| T::deserialize(Tracked(&value)));
+    Ok((result?, diagnostics))
+}
+
+/// Run `f`, collecting every error recovered from while it deserializes.
+///
+/// Paths are relative to the outermost lenient field unless the value is
+/// deserialized through [`from_json`]. Calls may be nested; each only sees its
+/// own diagnostics.
+pub fn collect<T>(f: impl FnOnce() -> T) -> (T, Vec<Diagnostic>) {
+    let outer = SINK.with(|sink| sink.replace(Some(Vec::new())));
+    let value = f();
+    let diagnostics = SINK.with(|sink| sink.replace(outer)).unwrap_or_default();
+    (value, diagnostics)
+}
+
+/// Run `f`, keeping the diagnostics it produced only if it succeeds.
+///
+/// Errors recovered from inside a value that is then thrown away anyway are noise.
+pub(crate) fn attempt<T, E>(f: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+    if !is_collecting() {
+        return f();
+    }
+    let (result, inner) = collect(f);
+    if result.is_ok() {
+        SINK.with(|sink| {
+            if let Some(diagnostics) = sink.borrow_mut().as_mut() {
+                diagnostics.extend(inner);
+            }
+        });
+    }
+    result
+}
+
+/// Record a recovered error at the current path, if anyone is collecting.
+pub(crate) fn report(error: impl std::fmt::Display, fallback: Fallback) {
+    SINK.with(|sink| {
+        if let Some(diagnostics) = sink.borrow_mut().as_mut() {
+            diagnostics.push(Diagnostic {
+                path: current_path(),
+                error: error.to_string(),
+                fallback,
+            });
+        }
+    });
+}
+
+fn is_collecting() -> bool {
+    SINK.with(|sink| sink.borrow().is_some())
+}
+
+/// One step of a JSON path.
+enum Segment {
+    /// Object key or enum variant.
+    Key(String),
+    /// Array index.
+    Index(usize),
+}
+
+/// Pops the segment pushed by [`enter_key`] or [`enter_index`] when dropped.
+pub(crate) struct PathGuard(bool);
+
+impl Drop for PathGuard {
+    fn drop(&mut self) {
+        if self.0 {
+            PATH.with(|path| path.borrow_mut().pop());
+        }
+    }
+}
+
+fn enter(segment: impl FnOnce() -> Segment) -> PathGuard {
+    if !is_collecting() {
+        return PathGuard(false);
+    }
+    PATH.with(|path| path.borrow_mut().push(segment()));
+    PathGuard(true)
+}
+
+/// Descend into an object key or enum variant until the guard is dropped.
+///
+/// Does nothing unless diagnostics are being collected.
+pub(crate) fn enter_key(key: &str) -> PathGuard {
+    enter(|| Segment::Key(key.to_string()))
+}
+
+/// Descend into an array element until the guard is dropped.
+///
+/// Does nothing unless diagnostics are being collected.
+pub(crate) fn enter_index(index: usize) -> PathGuard {
+    enter(|| Segment::Index(index))
+}
+
+fn current_path() -> String {
+    PATH.with(|path| {
+        let mut out = String::new();
+        for segment in path.borrow().iter() {
+            match segment {
+                Segment::Key(key) if out.is_empty() => out.push_str(key),
+                Segment::Key(key) => write!(out, ".{key}").unwrap(),
+                Segment::Index(index) => write!(out, "[{index}]").unwrap(),
+            }
+        }
+        out
+    })
+}
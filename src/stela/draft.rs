@@ -0,0 +1,263 @@
+//! Drafts of forms, saved on the device while the user types.
+//!
+//! A form with [`SectionForm::autosave`] saves a [`FormDraft::capture`] of its
+//! values under [`SectionForm::draft_key`], such as in `localStorage`, as
+//! inputs change. When the form is shown again, [`SectionForm::restore_draft`]
+//! fills in the inputs from the draft, if it was saved from the same version of
+//! the form and isn't too old. Remove the draft once the form is submitted.
+//! Inputs the draft has no value for keep their initial value, so capture
+//! unchecked checkboxes as `false`.
+//!
+//! Drafts leave out passwords and Turnstile tokens, like
+//! [`SectionForm::preserved_fields`].
+
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+
+use crate::stela::{wizard::WizardState, FormCallData, FormInput, FormValue, Image, SectionForm};
+
+/// Start of every [`SectionForm::draft_key`].
+pub const DRAFT_KEY_PREFIX: &str = "stela-draft:";
+
+/// Values entered into a form, saved before submitting.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct FormDraft {
+    /// [`FormAutosave::version`](crate::stela::FormAutosave::version) of the
+    /// form it was saved from.
+    pub version: u32,
+    /// When it was saved, in milliseconds since the Unix epoch.
+    pub saved_at: u64,
+    /// Values entered, by input `name`.
+    pub fields: HashMap<String, FormValue>,
+    /// Step of a multi-step form the user was on.
+    #[serde(default)]
+    pub step: Option<usize>,
+}
+
+/// Why a draft can't be restored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DraftRejection {
+    /// The form doesn't autosave.
+    Disabled,
+    /// The draft is from another version of the form.
+    Version {
+        /// Version the draft was saved from.
+        draft: u32,
+        /// Version of the form now.
+        form: u32,
+    },
+    /// The draft is older than
+    /// [`FormAutosave::max_age`](crate::stela::FormAutosave::max_age).
+    Expired,
+}
+
+impl fmt::Display for DraftRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Disabled => write!(f, "form doesn't autosave"),
+            Self::Version { draft, form } => {
+                write!(f, "draft is from version {draft}, form is version {form}")
+            }
+            Self::Expired => write!(f, "draft expired"),
+        }
+    }
+}
+
+impl std::error::Error for DraftRejection {}
+
+impl FormDraft {
+    /// A draft of the values in `data`, entered into `form`, at `now` in
+    /// milliseconds since the Unix epoch. `None` if the form doesn't autosave.
+    pub fn capture(form: &SectionForm, data: &FormCallData, now: u64) -> Option<Self> {
+        let autosave = form.autosave.as_ref()?;
+        Some(Self {
+            version: autosave.version,
+            saved_at: now,
+            fields: form.preserved_fields(data),
+            step: data.step,
+        })
+    }
+
+    /// Whether the draft can be restored into `form` at `now`.
+    pub fn check(&self, form: &SectionForm, now: u64) -> Result<(), DraftRejection> {
+        let autosave = form.autosave.as_ref().ok_or(DraftRejection::Disabled)?;
+        if self.version != autosave.version {
+            return Err(DraftRejection::Version {
+                draft: self.version,
+                form: autosave.version,
+            });
+        }
+        if let Some(max_age) = autosave.max_age {
+            if now.saturating_sub(self.saved_at) > max_age.saturating_mul(1000) {
+                return Err(DraftRejection::Expired);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SectionForm {
+    /// Where to save drafts of this form, unique to its `form_name` and
+    /// `extra_data`. `None` if the form doesn't autosave.
+    ///
+    /// The key of a multi-step form uses its own `extra_data`, not the
+    /// [`WizardState`] that replaces it between steps, so it stays the same
+    /// from step to step.
+    pub fn draft_key(&self) -> Option<String> {
+        self.autosave.as_ref()?;
+        let extra_data = if self.steps.is_empty() {
+            self.extra_data.clone()
+        } else {
+            WizardState::decode(self.extra_data.as_deref()).extra_data
+        };
+        let id = serde_json::to_string(&(&self.form_name, &extra_data))
+            .expect("strings are always serializable");
+        Some(format!("{DRAFT_KEY_PREFIX}{id}"))
+    }
+
+    /// Use the values in `draft` as the initial values of the inputs, after
+    /// checking it with [`FormDraft::check`].
+    ///
+    /// Values that no longer fit their input, such as a removed option, are
+    /// skipped, as are typed tags of a multi-select.
+    pub fn restore_draft(&mut self, draft: &FormDraft, now: u64) -> Result<(), DraftRejection> {
+        draft.check(self, now)?;
+        let data = FormCallData {
+            form_name: self.form_name.clone(),
+            extra_data: self.extra_data.clone(),
+            fields: draft.fields.clone(),
+            step: None,
+            etag: None,
+        };
+        for input in &mut self.inputs {
+            restore(input, &data);
+        }
+        for step in &mut self.steps {
+            for input in &mut step.inputs {
+                restore(input, &data);
+            }
+        }
+        if let Some(step) = draft.step.filter(|step| *step < self.steps.len()) {
+            self.initial_step = Some(step);
+        }
+        Ok(())
+    }
+}
+
+/// Set the initial value of `input`, and the inputs inside it, from `data`.
+/// Inputs the draft has no value for keep theirs, such as a checkbox that is
+/// checked by default.
+fn restore(input: &mut FormInput, data: &FormCallData) {
+    let value = input
+        .name()
+        .filter(|name| data.fields.contains_key(*name))
+        .and_then(|_| data.value(input));
+    match input {
+        FormInput::Subsection(subsection) => {
+            for input in &mut Arc::make_mut(subsection).inputs {
+                restore(input, data);
+            }
+        }
+        FormInput::Conditional(conditional) => {
+            for input in &mut Arc::make_mut(conditional).inputs {
+                restore(input, data);
+            }
+        }
+        FormInput::Tabs(tabs) => {
+            for tab in &mut Arc::make_mut(tabs).tabs {
+                restore(&mut tab.input, data);
+            }
+        }
+        FormInput::Checkbox(checkbox) => {
+            if let Some(FormValue::Bool(checked)) = value {
+                Arc::make_mut(checkbox).default_checked = Some(checked);
+            }
+        }
+        FormInput::Radio(radio) => {
+            let Some(FormValue::Option(value)) = value else {
+                return;
+            };
+            if let Some(index) = radio.options.iter().position(|o| o.value == value) {
+                Arc::make_mut(radio).initial_index = Some(index);
+            }
+        }
+        FormInput::Select(select) => {
+            let Some(FormValue::Option(value)) = value else {
+                return;
+            };
+            if select.options.iter().any(|option| option.value == value) {
+                Arc::make_mut(select).initial_value = Some(value);
+            }
+        }
+        FormInput::MultiSelect(multi) => {
+            let Some(FormValue::List(values)) = value else {
+                return;
+            };
+            Arc::make_mut(multi).initial_values = values
+                .into_iter()
+                .filter_map(|value| match value {
+                    FormValue::Option(value) => Some(value),
+                    _ => None,
+                })
+                .collect();
+        }
+        FormInput::Image(image) => {
+            let Some(FormValue::Image(id)) = value else {
+                return;
+            };
+            if image
+                .initial_image
+                .as_ref()
+                .is_none_or(|image| image.id != id)
+            {
+                Arc::make_mut(image).initial_image = Some(Image {
+                    aspect: None,
+                    width: None,
+                    height: None,
+                    id,
+                    alt: None,
+                    focal_point: None,
+                    crop: None,
+                });
+            }
+        }
+        FormInput::Number(number) => {
+            let Some(FormValue::Text(text)) = value else {
+                return;
+            };
+            if let Ok(value) = text.trim().parse() {
+                Arc::make_mut(number).initial_value = Some(value);
+            }
+        }
+        FormInput::Text(text) => {
+            if let Some(FormValue::Text(value)) = value {
+                Arc::make_mut(text).initial_value = Some(value);
+            }
+        }
+        FormInput::Markdown(markdown) => {
+            if let Some(FormValue::Text(value)) = value {
+                Arc::make_mut(markdown).initial_value = Some(value);
+            }
+        }
+        FormInput::DateTime(date_time) => {
+            if let Some(FormValue::Text(value)) = value {
+                Arc::make_mut(date_time).initial_value = Some(value);
+            }
+        }
+        FormInput::Email(email) => {
+            if let Some(FormValue::Text(value)) = value {
+                Arc::make_mut(email).initial_value = Some(value);
+            }
+        }
+        FormInput::Url(url) => {
+            if let Some(FormValue::Text(value)) = value {
+                Arc::make_mut(url).initial_value = Some(value);
+            }
+        }
+        FormInput::CfTurnstile(_)
+        | FormInput::Password(_)
+        | FormInput::Motions(_)
+        | FormInput::Unknown => {}
+    }
+}
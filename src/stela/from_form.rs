@@ -0,0 +1,215 @@
+//! Typed structs from form submissions, and forms from typed structs.
+//!
+//! With the `derive` feature, `#[derive(FromFormCallData)]` on a struct
+//! implements both [`FormDefinition`] and [`FromFormCallData`]:
+//!
+//! ```ignore
+//! #[derive(FromFormCallData)]
+//! #[form(name = "signup", header = "Aliĝi", submit = "Aliĝi")]
+//! struct Signup {
+//!     #[form(title = "Uzantnomo", min = 3, max = 32, filter = "ALPHA_ASCII | DASH")]
+//!     username: String,
+//!     #[form(input = "radio", title = "Nivelo", options = ["komencanto", "flua"])]
+//!     level: String,
+//!     #[form(title = "Montri retpoŝtadreson")]
+//!     show_email: bool,
+//! }
+//! ```
+//!
+//! Struct attributes are `name` (required), `header`, `subheader`, and
+//! `submit`, the title of a submit button added after the inputs. Field
+//! attributes are:
+//!
+//! - `name` and `title`.
+//! - `input`: `text`, `markdown`, `checkbox`, `radio`, `select`,
+//!   `multiselect`, `number`, `date`, `time`, `datetime`, `email`, `url`,
+//!   `password`, `image`, or `turnstile`. `bool` fields default to
+//!   `checkbox`, numbers to `number`, and everything else to `text`.
+//! - `min` and `max`: lengths, the range of a `number`, or how many to pick
+//!   in a `multiselect`.
+//! - `step`, for `number`.
+//! - `filter`, for `text` and the typed tags of a `multiselect`.
+//! - `esperanto`, for `text`.
+//! - `options`, for `radio`, `select`, and `multiselect`.
+//! - `custom` and `style` (`checkboxes` or `chips`), for `multiselect`.
+//! - `show_if` and `require_if`: `"name"` when the checkbox `name` is
+//!   checked, or `"name = value"` when input `name` has `value`. The field
+//!   must be an `Option`.
+//! - `confirm`, `confirm_title`, and `autocomplete`, for `password`.
+//! - `sitekey`, for `turnstile`, and optionally `size`, `theme`,
+//!   `appearance`, `execution`, `retry`, `language`, and `action`, checked
+//!   at compile time.
+//!
+//! Inputs that can be required are required unless the field is an `Option`.
+//!
+//! On an enum of single-field variants, the derive implements
+//! [`FromFormCallData`] by dispatching on [`FormCallData::form_name`].
+
+use std::str::FromStr;
+
+use crate::stela::{FormCallData, FormError, FormErrorCode, FormResponse, FormValue, SectionForm};
+
+#[cfg(feature = "derive")]
+pub use anaso_site_api_models_derive::FromFormCallData;
+
+/// A type with a form to fill it out.
+pub trait FormDefinition {
+    /// [`SectionForm::form_name`] of the form.
+    const FORM_NAME: &'static str;
+
+    /// The form to show, which submissions are also validated against.
+    fn section_form() -> SectionForm;
+}
+
+/// A type that can be read from a form submission.
+pub trait FromFormCallData: Sized {
+    /// Read a submission.
+    ///
+    /// On failure, the response says what's wrong and keeps the submitted
+    /// values, ready to send back to the client.
+    fn from_form_call_data(data: &FormCallData) -> Result<Self, Box<FormResponse>>;
+}
+
+/// A type that can be read from the value submitted for one input.
+pub trait FromFormValue: Sized {
+    /// Read the value, read as the kind of value the input takes, or `None` if
+    /// nothing was submitted.
+    fn from_form_value(value: Option<FormValue>) -> Result<Self, FormError>;
+}
+
+fn required() -> FormError {
+    FormError::new(FormErrorCode::Required, "required")
+}
+
+fn invalid(message: &str) -> FormError {
+    FormError::new(FormErrorCode::Invalid, message)
+}
+
+impl FromFormValue for String {
+    fn from_form_value(value: Option<FormValue>) -> Result<Self, FormError> {
+        match value.ok_or_else(required)? {
+            FormValue::Text(text) | FormValue::Image(text) | FormValue::Option(text) => Ok(text),
+            FormValue::Bool(_) | FormValue::List(_) => Err(invalid("must be text")),
+        }
+    }
+}
+
+impl FromFormValue for bool {
+    fn from_form_value(value: Option<FormValue>) -> Result<Self, FormError> {
+        value
+            .ok_or_else(required)?
+            .as_bool()
+            .ok_or_else(|| invalid("must be checked or unchecked"))
+    }
+}
+
+macro_rules! impl_from_form_value_parse {
+    ($($ty:ty),*) => {
+        $(
+            impl FromFormValue for $ty {
+                fn from_form_value(value: Option<FormValue>) -> Result<Self, FormError> {
+                    let text = String::from_form_value(value)?;
+                    <$ty>::from_str(text.trim()).map_err(|_| invalid("must be a number"))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_form_value_parse!(i32, i64, u32, u64, usize, f32, f64);
+
+/// Nothing submitted, or empty text, is `None`.
+impl<T: FromFormValue> FromFormValue for Option<T> {
+    fn from_form_value(value: Option<FormValue>) -> Result<Self, FormError> {
+        match value {
+            None => Ok(None),
+            Some(FormValue::Text(text)) if text.is_empty() => Ok(None),
+            value => T::from_form_value(value).map(Some),
+        }
+    }
+}
+
+/// Nothing submitted is empty, and a single value is a list of one.
+impl<T: FromFormValue> FromFormValue for Vec<T> {
+    fn from_form_value(value: Option<FormValue>) -> Result<Self, FormError> {
+        match value {
+            None => Ok(Vec::new()),
+            Some(FormValue::List(values)) => values
+                .into_iter()
+                .map(|value| T::from_form_value(Some(value)))
+                .collect(),
+            value => T::from_form_value(value).map(|value| vec![value]),
+        }
+    }
+}
+
+/// Reads fields one at a time, collecting every error.
+///
+/// This is what the derive uses, and works the same by hand:
+///
+/// ```ignore
+/// let form = Signup::section_form();
+/// let mut reader = FieldReader::new(data, &form)?;
+/// match (reader.read("username"), reader.read("show_email")) {
+///     (Some(username), Some(show_email)) => Ok(Signup { username, show_email }),
+///     _ => Err(reader.finish()),
+/// }
+/// ```
+pub struct FieldReader<'a> {
+    data: &'a FormCallData,
+    form: &'a SectionForm,
+    response: FormResponse,
+}
+
+impl<'a> FieldReader<'a> {
+    /// Check that `data` is for `form` and passes [`SectionForm::validate`].
+    pub fn new(data: &'a FormCallData, form: &'a SectionForm) -> Result<Self, Box<FormResponse>> {
+        if data.form_name != form.form_name {
+            return Err(unknown_form(data));
+        }
+        let response = form.validate(data);
+        if response.has_errors() {
+            return Err(Box::new(response));
+        }
+        Ok(Self {
+            data,
+            form,
+            response,
+        })
+    }
+
+    /// Read the input named `name`, or record why it can't be read.
+    ///
+    /// Inputs hidden by a [`FormInputConditional`](crate::stela::FormInputConditional)
+    /// read as not submitted.
+    pub fn read<T: FromFormValue>(&mut self, name: &str) -> Option<T> {
+        let value = self
+            .form
+            .input(name)
+            .filter(|_| self.form.is_shown(name, self.data))
+            .and_then(|input| self.data.value(input));
+        match T::from_form_value(value) {
+            Ok(value) => Some(value),
+            Err(error) => {
+                let response = std::mem::take(&mut self.response);
+                self.response = response.with_field_error(name, error);
+                None
+            }
+        }
+    }
+
+    /// The errors recorded so far, keeping the submitted values.
+    pub fn finish(mut self) -> Box<FormResponse> {
+        self.response.fields = self.form.preserved_fields(self.data);
+        Box::new(self.response)
+    }
+}
+
+/// Response for a submission whose `form_name` isn't handled.
+pub fn unknown_form(data: &FormCallData) -> Box<FormResponse> {
+    let error = FormError::new(
+        FormErrorCode::Invalid,
+        format!("unknown form `{}`", data.form_name),
+    );
+    Box::new(FormResponse::default().with_form_error(error))
+}
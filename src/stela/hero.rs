@@ -1,27 +1,48 @@
 use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
 
-use crate::stela::{Image, VisualMotion};
+use crate::stela::{
+    lenient::{Lenient, LenientVec},
+    Image, VisualMotion,
+};
 
 /// Some grand information.
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Hero {
     /// Image to show on light theme.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub primary_image_light: Option<Image>,
     /// Image to show on dark theme.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub primary_image_dark: Option<Image>,
     /// Text to show when image isn't available or still loading.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub primary_image_fallback_text: Option<String>,
 
     /// Background to show on light theme.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub background_image_light: Option<Image>,
     /// Background to show on dark theme.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub background_image_dark: Option<Image>,
 
     /// Header text.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub title: Option<String>,
     /// Subheader text.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub description: Option<String>,
 
     /// Buttons user can click.
+    #[serde_as(as = "LenientVec")]
+    #[serde(default)]
     pub motions: Vec<VisualMotion>,
 }
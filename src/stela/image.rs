@@ -1,18 +1,199 @@
 use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::stela::lenient::Lenient;
 
 /// Image ID, and other useful info.
+#[serde_as]
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Image {
     /// Aspect ratio.
     ///
     /// width / height
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub aspect: Option<f32>,
     /// Original image width.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub width: Option<f32>,
     /// Original image height.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub height: Option<f32>,
     /// Cloudflare image storage id.
     ///
     /// This will _usually_ be a UUID, but it's not guaranteed to be.
     pub id: String,
+    /// Alternative text for screen readers.
+    ///
+    /// An empty string marks the image as decorative.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub alt: Option<String>,
+    /// The part of the image to keep in view when it's cropped to fit, such
+    /// as a face.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub focal_point: Option<FocalPoint>,
+    /// The part of the original image to show. The whole image if `None`.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub crop: Option<CropRect>,
+}
+
+/// A point on an image, as fractions of its width and height.
+///
+/// `(0, 0)` is the top left corner, `(1, 1)` the bottom right.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+pub struct FocalPoint {
+    /// From the left edge.
+    pub x: f32,
+    /// From the top edge.
+    pub y: f32,
+}
+
+/// A rectangle on an image, as fractions of its width and height.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+pub struct CropRect {
+    /// Left edge, from the left of the image.
+    pub x: f32,
+    /// Top edge, from the top of the image.
+    pub y: f32,
+    /// Width of the rectangle.
+    pub width: f32,
+    /// Height of the rectangle.
+    pub height: f32,
+}
+
+impl CropRect {
+    /// The whole image.
+    pub const FULL: Self = Self {
+        x: 0.0,
+        y: 0.0,
+        width: 1.0,
+        height: 1.0,
+    };
+
+    /// Whether the rectangle is non-empty and inside the image.
+    pub fn is_valid(&self) -> bool {
+        let fraction = 0.0..=1.0;
+        fraction.contains(&self.x)
+            && fraction.contains(&self.y)
+            && self.width > 0.0
+            && self.height > 0.0
+            && self.x + self.width <= 1.0 + f32::EPSILON
+            && self.y + self.height <= 1.0 + f32::EPSILON
+    }
+
+    /// Aspect ratio of the rectangle on an image of this aspect ratio.
+    ///
+    /// width / height
+    pub fn aspect(&self, image_aspect: f32) -> f32 {
+        image_aspect * self.width / self.height
+    }
+
+    /// The largest rectangle of `aspect` on an image of `image_aspect`,
+    /// centered on `focus` as far as the edges allow.
+    ///
+    /// The whole image if either aspect ratio isn't a positive number, such as
+    /// for an image without pixels.
+    pub fn around(focus: FocalPoint, aspect: f32, image_aspect: f32) -> Self {
+        let is_ratio = |aspect: f32| aspect.is_finite() && aspect > 0.0;
+        if !is_ratio(aspect) || !is_ratio(image_aspect) {
+            return Self::FULL;
+        }
+        let (width, height) = if aspect < image_aspect {
+            (aspect / image_aspect, 1.0)
+        } else {
+            (1.0, image_aspect / aspect)
+        };
+        Self {
+            x: (focus.x - width / 2.0).clamp(0.0, 1.0 - width),
+            y: (focus.y - height / 2.0).clamp(0.0, 1.0 - height),
+            width,
+            height,
+        }
+    }
+}
+
+impl FocalPoint {
+    /// The middle of the image.
+    pub const CENTER: Self = Self { x: 0.5, y: 0.5 };
+
+    /// CSS `object-position` value, such as `30% 40%`.
+    pub fn object_position(&self) -> String {
+        format!("{}% {}%", percent(self.x), percent(self.y))
+    }
+
+    /// Cloudflare Images `gravity` option, such as `0.3x0.4`.
+    pub fn cloudflare_gravity(&self) -> String {
+        format!("{}x{}", self.x.clamp(0.0, 1.0), self.y.clamp(0.0, 1.0))
+    }
+}
+
+fn percent(fraction: f32) -> f32 {
+    (fraction.clamp(0.0, 1.0) * 1000.0).round() / 10.0
+}
+
+impl Image {
+    /// The focal point within [`Self::crop`], which is what's shown.
+    ///
+    /// Without a focal point, the middle of the crop. `None` if neither is
+    /// set, so renderers can keep their default. A crop that isn't
+    /// [valid](CropRect::is_valid) is ignored.
+    pub fn focus(&self) -> Option<FocalPoint> {
+        match (self.focal_point, self.valid_crop()) {
+            (Some(point), Some(crop)) => Some(FocalPoint {
+                x: ((point.x - crop.x) / crop.width).clamp(0.0, 1.0),
+                y: ((point.y - crop.y) / crop.height).clamp(0.0, 1.0),
+            }),
+            (Some(point), None) => Some(point),
+            (None, Some(_)) => Some(FocalPoint::CENTER),
+            (None, None) => None,
+        }
+    }
+
+    /// CSS `object-position` for showing the cropped image with
+    /// `object-fit: cover`.
+    pub fn object_position(&self) -> Option<String> {
+        self.focus().map(|focus| focus.object_position())
+    }
+
+    /// Cloudflare Images `trim` option for [`Self::crop`], as
+    /// `top;right;bottom;left` in pixels. Needs the image's dimensions and a
+    /// valid crop.
+    pub fn cloudflare_trim(&self) -> Option<String> {
+        let crop = self.valid_crop()?;
+        let (width, height) = (self.width?, self.height?);
+        let top = crop.y * height;
+        let right = (1.0 - crop.x - crop.width) * width;
+        let bottom = (1.0 - crop.y - crop.height) * height;
+        let left = crop.x * width;
+        let [top, right, bottom, left] = [top, right, bottom, left].map(|px| px.round().max(0.0));
+        Some(format!("{top};{right};{bottom};{left}"))
+    }
+
+    fn valid_crop(&self) -> Option<CropRect> {
+        self.crop.filter(CropRect::is_valid)
+    }
+
+    /// Cloudflare Images options for showing this image cropped, with any
+    /// further `fit=cover` cropping centered on the focal point.
+    pub fn cloudflare_options(&self) -> Vec<String> {
+        let mut options = Vec::new();
+        // Without a trim the whole image is served, so the focal point is
+        // relative to all of it.
+        let focus = match self.cloudflare_trim() {
+            Some(trim) => {
+                options.push(format!("trim={trim}"));
+                self.focus()
+            }
+            None => self.focal_point,
+        };
+        if let Some(focus) = focus {
+            options.push(format!("gravity={}", focus.cloudflare_gravity()));
+        }
+        options
+    }
 }
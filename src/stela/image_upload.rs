@@ -0,0 +1,260 @@
+//! Uploading images to a [`FormInputImage`].
+//!
+//! Files go from the client straight to Cloudflare Images, not through the
+//! API:
+//!
+//! 1. The client checks the file against [`FormInputImage::constraints`] and
+//!    sends an [`ImageUploadTicketRequest`] describing it.
+//! 2. The API checks the request with [`SectionForm::check_upload`], asks
+//!    Cloudflare for a one-time upload URL, and answers with the
+//!    [`ImageUploadTicket`] made from the [`CloudflareDirectUpload`] result.
+//!    If the input has a [`FormInputImage::crop_aspect`] and the user didn't
+//!    crop, [`CropRect::around`] the focal point makes a default crop.
+//! 3. The client uploads the file to [`ImageUploadTicket::upload_url`] and
+//!    submits the form with [`ImageUploadTicket::id`] as the input's value.
+//! 4. The API looks up the stored [`Image`] and checks it with
+//!    [`ImageConstraints::check_image`], since the file that was uploaded
+//!    need not be the one described in the request.
+
+use serde::{Deserialize, Serialize};
+
+use crate::stela::{
+    CropRect, FocalPoint, FormError, FormErrorCode, FormInput, FormInputImage, Image,
+    ImageConstraints, SectionForm,
+};
+
+/// Ask the API for somewhere to upload an image.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct ImageUploadTicketRequest {
+    /// [`SectionForm::form_name`] of the form with the input.
+    pub form_name: String,
+    /// [`SectionForm::extra_data`] of the form with the input.
+    pub extra_data: Option<String>,
+    /// `name` of the [`FormInputImage`].
+    pub input_name: String,
+    /// MIME type of the file, such as `image/png`.
+    pub mime_type: String,
+    /// File size in bytes.
+    pub bytes: u64,
+    /// Width in pixels, if the client could read it.
+    pub width: Option<u32>,
+    /// Height in pixels, if the client could read it.
+    pub height: Option<u32>,
+    /// Where the user placed the focal point, on the original image.
+    #[serde(default)]
+    pub focal_point: Option<FocalPoint>,
+    /// How the user cropped the image, on the original image.
+    #[serde(default)]
+    pub crop: Option<CropRect>,
+}
+
+/// Where to upload an image.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct ImageUploadTicket {
+    /// Cloudflare image storage id the upload will get. Submit this as the
+    /// value of the input.
+    pub id: String,
+    /// One-time URL to `POST` the file to, as `multipart/form-data` with a
+    /// `file` field.
+    pub upload_url: String,
+    /// When the URL stops working, as an RFC 3339 timestamp.
+    pub expires: Option<String>,
+}
+
+/// Response envelope of the Cloudflare API.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct CloudflareResponse<T> {
+    /// Whether the call worked.
+    pub success: bool,
+    /// Why it didn't.
+    #[serde(default)]
+    pub errors: Vec<CloudflareMessage>,
+    /// What it returned.
+    pub result: Option<T>,
+}
+
+/// An error or message from the Cloudflare API.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct CloudflareMessage {
+    /// Cloudflare's error code.
+    pub code: i64,
+    /// Description in English.
+    pub message: String,
+}
+
+/// `result` of Cloudflare's `images/v2/direct_upload` endpoint.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct CloudflareDirectUpload {
+    /// Image id the upload will get.
+    pub id: String,
+    /// One-time URL to upload to.
+    #[serde(rename = "uploadURL")]
+    pub upload_url: String,
+}
+
+impl CloudflareDirectUpload {
+    /// The ticket to send to the client. `expires` is the `expiry` the upload
+    /// URL was requested with, if any.
+    pub fn into_ticket(self, expires: Option<String>) -> ImageUploadTicket {
+        ImageUploadTicket {
+            id: self.id,
+            upload_url: self.upload_url,
+            expires,
+        }
+    }
+}
+
+impl ImageConstraints {
+    /// Check a file described by an upload request, before it's uploaded.
+    pub fn check_upload(&self, request: &ImageUploadTicketRequest) -> Result<(), FormError> {
+        if !self.mime_types.is_empty()
+            && !self
+                .mime_types
+                .iter()
+                .any(|mime_type| mime_type.eq_ignore_ascii_case(&request.mime_type))
+        {
+            return Err(FormError::new(
+                FormErrorCode::InvalidFormat,
+                format!("`{}` is not an accepted image type", request.mime_type),
+            ));
+        }
+        if let Some(max) = self.max_bytes.filter(|max| request.bytes > *max) {
+            return Err(FormError::new(
+                FormErrorCode::TooLarge,
+                format!("image is {} bytes, at most {max} allowed", request.bytes),
+            ));
+        }
+        self.check_dimensions(
+            request.width.map(|width| width as f32),
+            request.height.map(|height| height as f32),
+            None,
+        )
+    }
+
+    /// Check an uploaded image. Limits on dimensions the image doesn't have
+    /// are skipped.
+    pub fn check_image(&self, image: &Image) -> Result<(), FormError> {
+        self.check_dimensions(image.width, image.height, image.aspect)
+    }
+
+    fn check_dimensions(
+        &self,
+        width: Option<f32>,
+        height: Option<f32>,
+        aspect: Option<f32>,
+    ) -> Result<(), FormError> {
+        for (what, value, min, max) in [
+            ("wide", width, self.min_width, self.max_width),
+            ("tall", height, self.min_height, self.max_height),
+        ] {
+            let Some(value) = value else { continue };
+            if let Some(min) = min.filter(|min| value < *min as f32) {
+                return Err(FormError::new(
+                    FormErrorCode::TooSmall,
+                    format!("image is {value} pixels {what}, at least {min} needed"),
+                ));
+            }
+            if let Some(max) = max.filter(|max| value > *max as f32) {
+                return Err(FormError::new(
+                    FormErrorCode::TooLarge,
+                    format!("image is {value} pixels {what}, at most {max} allowed"),
+                ));
+            }
+        }
+
+        let aspect = aspect.or(match (width, height) {
+            (Some(width), Some(height)) if height > 0.0 => Some(width / height),
+            _ => None,
+        });
+        if let Some(aspect) = aspect {
+            let too_narrow = self.aspect_min.is_some_and(|min| aspect < min);
+            let too_wide = self.aspect_max.is_some_and(|max| aspect > max);
+            if too_narrow || too_wide {
+                return Err(FormError::new(
+                    FormErrorCode::InvalidAspect,
+                    format!("aspect ratio {aspect} is not allowed"),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FormInputImage {
+    /// Check the crop and focal point of an upload request against
+    /// [`Self::crop_aspect`]. Images without pixels fail.
+    pub fn check_crop(&self, request: &ImageUploadTicketRequest) -> Result<(), FormError> {
+        if request.width == Some(0) || request.height == Some(0) {
+            return Err(FormError::new(
+                FormErrorCode::Invalid,
+                "image has no pixels",
+            ));
+        }
+
+        if let Some(crop) = request.crop.filter(|crop| !crop.is_valid()) {
+            return Err(FormError::new(
+                FormErrorCode::Invalid,
+                format!("crop {crop:?} is not inside the image"),
+            ));
+        }
+        let fraction = 0.0..=1.0;
+        if let Some(point) = request
+            .focal_point
+            .filter(|point| !fraction.contains(&point.x) || !fraction.contains(&point.y))
+        {
+            return Err(FormError::new(
+                FormErrorCode::Invalid,
+                format!("focal point {point:?} is not inside the image"),
+            ));
+        }
+        let (Some(aspect), Some(crop), Some(width), Some(height)) = (
+            self.crop_aspect,
+            request.crop,
+            request.width,
+            request.height,
+        ) else {
+            return Ok(());
+        };
+        let cropped = crop.aspect(width as f32 / height as f32);
+        if (cropped / aspect - 1.0).abs() > CROP_ASPECT_TOLERANCE {
+            return Err(FormError::new(
+                FormErrorCode::InvalidAspect,
+                format!("crop aspect ratio {cropped} is not {aspect}"),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// How far a crop may be from [`FormInputImage::crop_aspect`], relatively,
+/// to allow for rounding to whole pixels.
+pub const CROP_ASPECT_TOLERANCE: f32 = 0.01;
+
+impl SectionForm {
+    /// The image input named `name`, if there is one.
+    pub fn image_input(&self, name: &str) -> Option<&FormInputImage> {
+        match self.input(name)? {
+            FormInput::Image(image) => Some(image),
+            _ => None,
+        }
+    }
+
+    /// Check an upload request against this form before handing out a
+    /// ticket. The request must name an image input of this form.
+    pub fn check_upload(&self, request: &ImageUploadTicketRequest) -> Result<(), FormError> {
+        let input = self
+            .image_input(&request.input_name)
+            .filter(|_| request.form_name == self.form_name)
+            .ok_or_else(|| {
+                FormError::new(
+                    FormErrorCode::Invalid,
+                    format!(
+                        "`{}` has no image input `{}`",
+                        request.form_name, request.input_name
+                    ),
+                )
+            })?;
+        input.constraints.check_upload(request)?;
+        input.check_crop(request)
+    }
+}
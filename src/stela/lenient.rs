@@ -0,0 +1,400 @@
+//! `serde_with` adaptors that fall back instead of failing, and report what
+//! they skipped to [`crate::stela::diagnostics`].
+
+use std::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{
+        value::BorrowedStrDeserializer, DeserializeOwned, DeserializeSeed, EnumAccess, Error,
+        MapAccess, SeqAccess, Unexpected, VariantAccess, Visitor,
+    },
+    forward_to_deserialize_any, Deserialize, Deserializer, Serialize, Serializer,
+};
+use serde_json::Value;
+use serde_with::{DeserializeAs, SerializeAs};
+
+use crate::stela::diagnostics::{attempt, enter_index, enter_key, report, Fallback};
+
+/// Like `serde_with::DefaultOnError`, but reports the error.
+pub(crate) struct Lenient;
+
+/// Like `serde_with::VecSkipError`, but reports skipped elements and treats a
+/// non-array as empty.
+pub(crate) struct LenientVec;
+
+impl<'de, T> DeserializeAs<'de, T> for Lenient
+where
+    T: DeserializeOwned + Default,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(BUFFERED, LenientVisitor(PhantomData))
+    }
+}
+
+/// Newtype name [`Tracked`] answers with `visit_some` instead of
+/// `visit_newtype_struct`, so adaptors know their input is already buffered.
+///
+/// Any other deserializer may be a stream that a failed attempt would leave
+/// half read, so the adaptors buffer it into a [`Value`] first. Values reached
+/// through [`Tracked`] borrow from that buffer instead of copying their subtree
+/// again at every level.
+const BUFFERED: &str = "$anaso::lenient::Buffered";
+
+struct LenientVisitor<T>(PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for LenientVisitor<T>
+where
+    T: DeserializeOwned + Default,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any value")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        Ok(self.visit_some(Tracked(&value)).unwrap_or_default())
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(
+            attempt(|| T::deserialize(deserializer)).unwrap_or_else(|error| {
+                report(error, Fallback::Default);
+                T::default()
+            }),
+        )
+    }
+}
+
+impl<T: Serialize> SerializeAs<T> for Lenient {
+    fn serialize_as<S>(source: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        source.serialize(serializer)
+    }
+}
+
+impl<'de, T> DeserializeAs<'de, Vec<T>> for LenientVec
+where
+    T: DeserializeOwned,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<Vec<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(BUFFERED, LenientVecVisitor(PhantomData))
+    }
+}
+
+struct LenientVecVisitor<T>(PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for LenientVecVisitor<T>
+where
+    T: DeserializeOwned,
+{
+    type Value = Vec<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any value")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Vec<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        Ok(self.visit_some(Tracked(&value)).unwrap_or_default())
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Vec<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(deserializer
+            .deserialize_seq(SkipErrors(PhantomData))
+            .unwrap_or_else(|error| {
+                report(error, Fallback::Empty);
+                Vec::new()
+            }))
+    }
+}
+
+/// Collects the elements of a sequence, skipping the ones that fail.
+struct SkipErrors<T>(PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for SkipErrors<T>
+where
+    T: DeserializeOwned,
+{
+    type Value = Vec<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Vec<T>, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut elements = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(element) = seq.next_element_seed(SkipError(PhantomData))? {
+            elements.extend(element);
+        }
+        Ok(elements)
+    }
+}
+
+struct SkipError<T>(PhantomData<T>);
+
+impl<'de, T> DeserializeSeed<'de> for SkipError<T>
+where
+    T: DeserializeOwned,
+{
+    type Value = Option<T>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(attempt(|| T::deserialize(deserializer))
+            .map_err(|error| report(error, Fallback::Skipped))
+            .ok())
+    }
+}
+
+impl<T: Serialize> SerializeAs<Vec<T>> for LenientVec {
+    fn serialize_as<S>(source: &Vec<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        source.serialize(serializer)
+    }
+}
+
+/// Deserializes a [`Value`] like `serde_json` does, but keeps the diagnostics
+/// path up to date as it descends.
+pub(crate) struct Tracked<'a>(pub(crate) &'a Value);
+
+fn unexpected(value: &Value) -> Unexpected<'_> {
+    match value {
+        Value::Null => Unexpected::Unit,
+        Value::Bool(b) => Unexpected::Bool(*b),
+        Value::Number(n) => match (n.as_u64(), n.as_i64(), n.as_f64()) {
+            (Some(u), _, _) => Unexpected::Unsigned(u),
+            (_, Some(i), _) => Unexpected::Signed(i),
+            (_, _, f) => Unexpected::Float(f.unwrap_or_default()),
+        },
+        Value::String(s) => Unexpected::Str(s),
+        Value::Array(_) => Unexpected::Seq,
+        Value::Object(_) => Unexpected::Map,
+    }
+}
+
+impl<'de> Deserializer<'de> for Tracked<'de> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::Null => visitor.visit_unit(),
+            Value::Bool(b) => visitor.visit_bool(*b),
+            Value::Number(n) => match (n.as_u64(), n.as_i64(), n.as_f64()) {
+                (Some(u), _, _) => visitor.visit_u64(u),
+                (_, Some(i), _) => visitor.visit_i64(i),
+                (_, _, f) => visitor.visit_f64(f.unwrap_or_default()),
+            },
+            Value::String(s) => visitor.visit_borrowed_str(s),
+            Value::Array(elements) => {
+                let mut seq = TrackedSeq {
+                    iter: elements.iter().enumerate(),
+                };
+                let value = visitor.visit_seq(&mut seq)?;
+                match seq.iter.len() {
+                    0 => Ok(value),
+                    _ => Err(Error::invalid_length(
+                        elements.len(),
+                        &"fewer elements in array",
+                    )),
+                }
+            }
+            Value::Object(map) => visitor.visit_map(TrackedMap {
+                iter: map.iter(),
+                value: None,
+            }),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::String(variant) => {
+                visitor.visit_enum(BorrowedStrDeserializer::<Self::Error>::new(variant))
+            }
+            Value::Object(map) if map.len() == 1 => {
+                let (variant, value) = map.iter().next().unwrap();
+                visitor.visit_enum(TrackedEnum { variant, value })
+            }
+            other => Err(Error::invalid_type(
+                unexpected(other),
+                &"string or map with a single key",
+            )),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match name {
+            BUFFERED => visitor.visit_some(self),
+            _ => visitor.visit_newtype_struct(self),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier
+    }
+}
+
+struct TrackedSeq<'de> {
+    iter: std::iter::Enumerate<std::slice::Iter<'de, Value>>,
+}
+
+impl<'de> SeqAccess<'de> for TrackedSeq<'de> {
+    type Error = serde_json::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        let Some((index, value)) = self.iter.next() else {
+            return Ok(None);
+        };
+        let _guard = enter_index(index);
+        seed.deserialize(Tracked(value)).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct TrackedMap<'de> {
+    iter: serde_json::map::Iter<'de>,
+    value: Option<(&'de str, &'de Value)>,
+}
+
+impl<'de> MapAccess<'de> for TrackedMap<'de> {
+    type Error = serde_json::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some((key, value)) = self.iter.next() else {
+            return Ok(None);
+        };
+        self.value = Some((key, value));
+        seed.deserialize(BorrowedStrDeserializer::new(key))
+            .map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (key, value) = self
+            .value
+            .take()
+            .ok_or_else(|| Error::custom("value is missing"))?;
+        let _guard = enter_key(key);
+        seed.deserialize(Tracked(value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct TrackedEnum<'de> {
+    variant: &'de str,
+    value: &'de Value,
+}
+
+impl<'de> EnumAccess<'de> for TrackedEnum<'de> {
+    type Error = serde_json::Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Self::Error> {
+        let variant = seed.deserialize(BorrowedStrDeserializer::new(self.variant))?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for TrackedEnum<'de> {
+    type Error = serde_json::Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        match self.value {
+            Value::Null => Ok(()),
+            other => Err(Error::invalid_type(unexpected(other), &"unit variant")),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        let _guard = enter_key(self.variant);
+        seed.deserialize(Tracked(self.value))
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let _guard = enter_key(self.variant);
+        Tracked(self.value).deserialize_seq(visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let _guard = enter_key(self.variant);
+        Tracked(self.value).deserialize_map(visitor)
+    }
+}
@@ -0,0 +1,601 @@
+//! Structural checks for pages that are valid by type but broken in practice.
+//!
+//! Run [`Page::lint`] in server tests to catch mistakes before they reach a client.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::stela::{
+    turnstile::{is_valid_data, ACTION_MAX_LEN, CDATA_MAX_LEN},
+    FormInput, Hero, Motion, Page, PageLayout, Section, SectionForm, VisualMotion,
+};
+
+/// A problem found in a page.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Lint {
+    /// What kind of problem.
+    pub code: LintCode,
+    /// How bad it is.
+    pub severity: Severity,
+    /// Where it is, such as `sections[2].section.Form.inputs[0].Radio`.
+    pub path: String,
+    /// Human-readable explanation.
+    pub message: String,
+}
+
+/// How bad a lint is.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Probably intended, but worth a look.
+    Warning,
+    /// Clients will misbehave.
+    Error,
+}
+
+/// Stable identifier for each kind of lint.
+///
+/// Variants are never renumbered or reused; see [`LintCode::code`].
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub enum LintCode {
+    /// `FormInputRadio.initial_index` is past the end of `options`.
+    RadioInitialIndexOutOfRange,
+    /// `FormInputTabs.initial_index` is past the end of `tabs`.
+    TabsInitialIndexOutOfRange,
+    /// `length_min` is greater than `length_max`.
+    LengthMinExceedsMax,
+    /// Two inputs in one form share a `name`.
+    DuplicateInputName,
+    /// `Motion::Submit` or `Motion::PreviousStep` used somewhere other than
+    /// inside a form.
+    SubmitOutsideForm,
+    /// `PageLayout::Tabbed` with a section that has no title for its tab.
+    TabbedSectionWithoutTitle,
+    /// `MotionHref.uri` isn't a valid absolute or relative URI.
+    InvalidUri,
+    /// `Motion::ScrollTo` targets a section id that isn't on the page.
+    ScrollToUnknownSection,
+    /// Two sections on one page share an `id`.
+    DuplicateSectionId,
+    /// A number or date input's `min` exceeds its `max`, a bound isn't in
+    /// the input's format, or `step` isn't positive. Also image constraints
+    /// whose minimum exceeds their maximum, and aspect ratios that aren't
+    /// positive.
+    InvalidRange,
+    /// `FormInputSelect.initial_value`, or one of
+    /// `FormInputMultiSelect.initial_values`, isn't the value of any option.
+    SelectInitialValueNotAnOption,
+    /// A `FormCondition` looks at an input name that isn't in the form.
+    ConditionUnknownInput,
+    /// `SectionForm.initial_step` is past the end of `steps`.
+    InitialStepOutOfRange,
+    /// `FormInputCfTurnstile.action` or `cdata` is too long, or has characters
+    /// Turnstile rejects.
+    InvalidTurnstileData,
+    /// A button or tile has no text for screen readers.
+    MissingAccessibleName,
+    /// A content image has no alternative text.
+    MissingImageAlt,
+    /// A heading is more than one level deeper than the one before it.
+    HeadingLevelSkipped,
+    /// A form input has no visible label.
+    MissingInputLabel,
+}
+
+impl LintCode {
+    /// Short stable code, such as `STL0001`.
+    ///
+    /// Accessibility lints from [`crate::stela::a11y`] are numbered from `STL0101`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::RadioInitialIndexOutOfRange => "STL0001",
+            Self::TabsInitialIndexOutOfRange => "STL0002",
+            Self::LengthMinExceedsMax => "STL0003",
+            Self::DuplicateInputName => "STL0004",
+            Self::SubmitOutsideForm => "STL0005",
+            Self::TabbedSectionWithoutTitle => "STL0006",
+            Self::InvalidUri => "STL0007",
+            Self::ScrollToUnknownSection => "STL0008",
+            Self::DuplicateSectionId => "STL0009",
+            Self::InvalidRange => "STL0010",
+            Self::SelectInitialValueNotAnOption => "STL0011",
+            Self::ConditionUnknownInput => "STL0012",
+            Self::InitialStepOutOfRange => "STL0013",
+            Self::InvalidTurnstileData => "STL0014",
+            Self::MissingAccessibleName => "STL0101",
+            Self::MissingImageAlt => "STL0102",
+            Self::HeadingLevelSkipped => "STL0103",
+            Self::MissingInputLabel => "STL0104",
+        }
+    }
+
+    /// Default severity.
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::TabbedSectionWithoutTitle | Self::MissingImageAlt | Self::HeadingLevelSkipped => {
+                Severity::Warning
+            }
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl Page {
+    /// Check the page for structural problems.
+    ///
+    /// Run after [`Page::assign_section_ids`] if any `ScrollTo` motions rely on
+    /// generated ids.
+    pub fn lint(&self) -> Vec<Lint> {
+        let mut linter = Linter::default();
+        linter.page(self);
+        linter.lints
+    }
+}
+
+impl SectionForm {
+    /// Check a standalone form, such as one shown in a modal, for structural problems.
+    pub fn lint(&self) -> Vec<Lint> {
+        let mut linter = Linter::default();
+        linter.form(self);
+        linter.lints
+    }
+}
+
+/// Whether `uri` looks like something a browser can navigate to.
+///
+/// Accepts site-relative references (`/x`, `#x`, `?x`) and absolute URIs with a
+/// scheme. Rejects whitespace and control characters anywhere.
+pub fn is_valid_uri(uri: &str) -> bool {
+    if uri.is_empty() || uri.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return false;
+    }
+    if uri.starts_with(['/', '#', '?']) {
+        return true;
+    }
+    let Some((scheme, rest)) = uri.split_once(':') else {
+        return false;
+    };
+    let mut chars = scheme.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        && !rest.is_empty()
+}
+
+/// Walks a page, collecting lints with their paths.
+#[derive(Default)]
+pub(crate) struct Linter {
+    path: Vec<String>,
+    /// Ids of the page's sections, if linting a whole page.
+    section_ids: Option<HashSet<String>>,
+    /// Names of the inputs of the form being linted.
+    input_names: HashSet<String>,
+    pub(crate) lints: Vec<Lint>,
+}
+
+impl Linter {
+    pub(crate) fn push(&mut self, code: LintCode, message: String) {
+        let mut path = String::new();
+        for segment in &self.path {
+            if !path.is_empty() && !segment.starts_with('[') {
+                path.push('.');
+            }
+            path.push_str(segment);
+        }
+        self.lints.push(Lint {
+            code,
+            severity: code.severity(),
+            path,
+            message,
+        });
+    }
+
+    pub(crate) fn path_push(&mut self, segment: String) {
+        self.path.push(segment);
+    }
+
+    pub(crate) fn path_pop(&mut self) {
+        self.path.pop();
+    }
+
+    fn scoped(&mut self, segment: impl Into<String>, f: impl FnOnce(&mut Self)) {
+        self.path_push(segment.into());
+        f(self);
+        self.path_pop();
+    }
+
+    fn page(&mut self, page: &Page) {
+        let mut section_ids = HashSet::new();
+        for (i, visual) in page.sections.iter().enumerate() {
+            if let Some(id) = &visual.id {
+                if !section_ids.insert(id.clone()) {
+                    self.scoped(format!("sections[{i}].id"), |l| {
+                        l.push(
+                            LintCode::DuplicateSectionId,
+                            format!("section id `{id}` is used more than once on this page"),
+                        );
+                    });
+                }
+            }
+        }
+        self.section_ids = Some(section_ids);
+
+        if let Some(hero) = &page.hero {
+            self.scoped("hero", |l| l.hero(hero));
+        }
+        if let Some(sidebar) = &page.sidebar {
+            self.scoped("sidebar", |l| {
+                for (i, card) in sidebar.cards.iter().enumerate() {
+                    l.scoped(format!("cards[{i}]"), |l| {
+                        l.visual_motions("motions", &card.motions, false)
+                    });
+                }
+            });
+        }
+
+        let tabbed = page.layout == Some(PageLayout::Tabbed);
+        for (i, visual) in page.sections.iter().enumerate() {
+            self.scoped(format!("sections[{i}]"), |l| {
+                if tabbed && visual.title.as_deref().is_none_or(str::is_empty) {
+                    l.push(
+                        LintCode::TabbedSectionWithoutTitle,
+                        "tabbed page section has no title for its tab".to_string(),
+                    );
+                }
+                l.scoped("section", |l| l.section(&visual.section));
+            });
+        }
+    }
+
+    fn section(&mut self, section: &Section) {
+        match section {
+            Section::Form(form) => self.scoped("Form", |l| l.form(form)),
+            Section::Hero(hero) => self.scoped("Hero.hero", |l| l.hero(&hero.hero)),
+            Section::Post(post) => self.scoped("Post", |l| {
+                if let Some(motion) = &post.motion {
+                    l.scoped("motion", |l| l.motion(motion, false));
+                }
+                l.visual_motions("motions_tl", &post.motions_tl, false);
+                l.visual_motions("motions_tr", &post.motions_tr, false);
+                l.visual_motions("motions_br", &post.motions_br, false);
+                l.visual_motions("motions_bl", &post.motions_bl, false);
+            }),
+            Section::Sponsor(sponsor) => self.scoped("Sponsor", |l| {
+                for (i, motion) in sponsor.motions.iter().enumerate() {
+                    l.scoped(format!("motions[{i}]"), |l| l.motion(motion, false));
+                }
+            }),
+            Section::Tiles(tiles) => self.scoped("Tiles", |l| {
+                for (i, tile) in tiles.tiles.iter().enumerate() {
+                    if let Some(motion) = &tile.motion {
+                        l.scoped(format!("tiles[{i}].motion"), |l| l.motion(motion, false));
+                    }
+                }
+            }),
+            Section::Unknown => {}
+        }
+    }
+
+    fn hero(&mut self, hero: &Hero) {
+        self.visual_motions("motions", &hero.motions, false);
+    }
+
+    fn form(&mut self, form: &SectionForm) {
+        fn collect(inputs: &[FormInput], names: &mut HashSet<String>) {
+            for input in inputs {
+                match input {
+                    FormInput::Subsection(subsection) => collect(&subsection.inputs, names),
+                    FormInput::Conditional(conditional) => collect(&conditional.inputs, names),
+                    FormInput::Tabs(tabs) => {
+                        for tab in &tabs.tabs {
+                            collect(std::slice::from_ref(&tab.input), names);
+                        }
+                    }
+                    _ => names.extend(input.name().map(str::to_string)),
+                }
+            }
+        }
+        self.input_names.clear();
+        for input in form.all_inputs() {
+            collect(std::slice::from_ref(input), &mut self.input_names);
+        }
+
+        let mut names = HashSet::new();
+        for (i, input) in form.inputs.iter().enumerate() {
+            self.scoped(format!("inputs[{i}]"), |l| l.input(input, &mut names));
+        }
+        for (i, step) in form.steps.iter().enumerate() {
+            for (j, input) in step.inputs.iter().enumerate() {
+                self.scoped(format!("steps[{i}].inputs[{j}]"), |l| {
+                    l.input(input, &mut names)
+                });
+            }
+        }
+        if let Some(step) = form.initial_step.filter(|&step| step >= form.steps.len()) {
+            self.scoped("initial_step", |l| {
+                l.push(
+                    LintCode::InitialStepOutOfRange,
+                    format!(
+                        "initial_step {step} is out of range for {} steps",
+                        form.steps.len()
+                    ),
+                );
+            });
+        }
+    }
+
+    fn name(&mut self, names: &mut HashSet<String>, name: Option<&str>) {
+        if let Some(name) = name {
+            if !names.insert(name.to_string()) {
+                self.push(
+                    LintCode::DuplicateInputName,
+                    format!("input name `{name}` is used more than once in this form"),
+                );
+            }
+        }
+    }
+
+    fn lengths(&mut self, min: Option<i32>, max: Option<i32>) {
+        if let (Some(min), Some(max)) = (min, max) {
+            if min > max {
+                self.push(
+                    LintCode::LengthMinExceedsMax,
+                    format!("length_min {min} is greater than length_max {max}"),
+                );
+            }
+        }
+    }
+
+    fn input(&mut self, input: &FormInput, names: &mut HashSet<String>) {
+        match input {
+            FormInput::Checkbox(checkbox) => self.scoped("Checkbox", |l| {
+                l.name(names, Some(&checkbox.name));
+            }),
+            FormInput::CfTurnstile(turnstile) => self.scoped("CfTurnstile", |l| {
+                l.name(names, Some(turnstile.field_name()));
+                for (field, value, max) in [
+                    ("action", &turnstile.action, ACTION_MAX_LEN),
+                    ("cdata", &turnstile.cdata, CDATA_MAX_LEN),
+                ] {
+                    if let Some(value) = value.as_deref().filter(|v| !is_valid_data(v, max)) {
+                        l.push(
+                            LintCode::InvalidTurnstileData,
+                            format!(
+                                "{field} `{value}` must be 1 to {max} of `a-z`, `A-Z`, \
+                                 `0-9`, `-` and `_`"
+                            ),
+                        );
+                    }
+                }
+            }),
+            FormInput::Tabs(tabs) => self.scoped("Tabs", |l| {
+                if let Some(index) = tabs.initial_index {
+                    if index >= tabs.tabs.len() {
+                        l.push(
+                            LintCode::TabsInitialIndexOutOfRange,
+                            format!(
+                                "initial_index {index} is out of range for {} tabs",
+                                tabs.tabs.len()
+                            ),
+                        );
+                    }
+                }
+                for (i, tab) in tabs.tabs.iter().enumerate() {
+                    l.scoped(format!("tabs[{i}].input"), |l| l.input(&tab.input, names));
+                }
+            }),
+            FormInput::Image(image) => self.scoped("Image", |l| {
+                l.name(names, image.name.as_deref());
+                let constraints = &image.constraints;
+                for (field, min, max) in [
+                    ("width", constraints.min_width, constraints.max_width),
+                    ("height", constraints.min_height, constraints.max_height),
+                ] {
+                    if let (Some(min), Some(max)) = (min, max) {
+                        if min > max {
+                            l.push(
+                                LintCode::InvalidRange,
+                                format!("min_{field} {min} is greater than max_{field} {max}"),
+                            );
+                        }
+                    }
+                }
+                for (field, aspect) in [
+                    ("aspect_min", constraints.aspect_min),
+                    ("aspect_max", constraints.aspect_max),
+                ] {
+                    if let Some(aspect) = aspect.filter(|aspect| aspect.is_nan() || *aspect <= 0.0)
+                    {
+                        l.push(
+                            LintCode::InvalidRange,
+                            format!("{field} {aspect} is not positive"),
+                        );
+                    }
+                }
+                if let Some(aspect) = image
+                    .crop_aspect
+                    .filter(|aspect| aspect.is_nan() || *aspect <= 0.0)
+                {
+                    l.push(
+                        LintCode::InvalidRange,
+                        format!("crop_aspect {aspect} is not positive"),
+                    );
+                }
+                if let (Some(min), Some(max)) = (constraints.aspect_min, constraints.aspect_max) {
+                    if min > max {
+                        l.push(
+                            LintCode::InvalidRange,
+                            format!("aspect_min {min} is greater than aspect_max {max}"),
+                        );
+                    }
+                }
+            }),
+            FormInput::Markdown(markdown) => self.scoped("Markdown", |l| {
+                l.name(names, Some(&markdown.name));
+                l.lengths(markdown.length_min, markdown.length_max);
+            }),
+            FormInput::Motions(motions) => self.scoped("Motions", |l| {
+                l.visual_motions("motions", &motions.motions, true);
+            }),
+            FormInput::Radio(radio) => self.scoped("Radio", |l| {
+                l.name(names, Some(&radio.name));
+                if let Some(index) = radio.initial_index {
+                    if index >= radio.options.len() {
+                        l.push(
+                            LintCode::RadioInitialIndexOutOfRange,
+                            format!(
+                                "initial_index {index} is out of range for {} options",
+                                radio.options.len()
+                            ),
+                        );
+                    }
+                }
+            }),
+            FormInput::Subsection(subsection) => self.scoped("Subsection", |l| {
+                for (i, input) in subsection.inputs.iter().enumerate() {
+                    l.scoped(format!("inputs[{i}]"), |l| l.input(input, names));
+                }
+            }),
+            FormInput::Conditional(conditional) => self.scoped("Conditional", |l| {
+                for (field, condition) in [
+                    ("show_when", &conditional.show_when),
+                    ("require_when", &conditional.require_when),
+                ] {
+                    let Some(condition) = condition else { continue };
+                    l.scoped(field, |l| {
+                        for name in condition.names() {
+                            if !l.input_names.contains(name) {
+                                l.push(
+                                    LintCode::ConditionUnknownInput,
+                                    format!("condition looks at unknown input `{name}`"),
+                                );
+                            }
+                        }
+                    });
+                }
+                for (i, input) in conditional.inputs.iter().enumerate() {
+                    l.scoped(format!("inputs[{i}]"), |l| l.input(input, names));
+                }
+            }),
+            FormInput::Text(text) => self.scoped("Text", |l| {
+                l.name(names, text.name.as_deref());
+                l.lengths(text.length_min, text.length_max);
+            }),
+            FormInput::Select(select) => self.scoped("Select", |l| {
+                l.name(names, Some(&select.name));
+                if let Some(value) = &select.initial_value {
+                    if !select.options.iter().any(|option| &option.value == value) {
+                        l.push(
+                            LintCode::SelectInitialValueNotAnOption,
+                            format!("initial_value `{value}` is not one of the options"),
+                        );
+                    }
+                }
+            }),
+            FormInput::MultiSelect(multi) => self.scoped("MultiSelect", |l| {
+                l.name(names, Some(&multi.name));
+                for value in &multi.initial_values {
+                    if !multi.options.iter().any(|option| &option.value == value) {
+                        l.push(
+                            LintCode::SelectInitialValueNotAnOption,
+                            format!("initial value `{value}` is not one of the options"),
+                        );
+                    }
+                }
+                if let (Some(min), Some(max)) = (multi.min_selected, multi.max_selected) {
+                    if min > max {
+                        l.push(
+                            LintCode::InvalidRange,
+                            format!("min_selected {min} is greater than max_selected {max}"),
+                        );
+                    }
+                }
+            }),
+            FormInput::Number(number) => self.scoped("Number", |l| {
+                l.name(names, Some(&number.name));
+                if let (Some(min), Some(max)) = (number.min, number.max) {
+                    if min > max {
+                        l.push(
+                            LintCode::InvalidRange,
+                            format!("min {min} is greater than max {max}"),
+                        );
+                    }
+                }
+                if let Some(step) = number.step.filter(|step| step.is_nan() || *step <= 0.0) {
+                    l.push(
+                        LintCode::InvalidRange,
+                        format!("step {step} is not positive"),
+                    );
+                }
+            }),
+            FormInput::DateTime(date_time) => self.scoped("DateTime", |l| {
+                l.name(names, Some(&date_time.name));
+                let kind = date_time.kind;
+                for (field, value) in [("min", &date_time.min), ("max", &date_time.max)] {
+                    if let Some(value) = value.as_deref().filter(|v| !kind.is_valid(v)) {
+                        l.push(
+                            LintCode::InvalidRange,
+                            format!("{field} `{value}` is not a valid {kind:?} value"),
+                        );
+                    }
+                }
+                if let (Some(min), Some(max)) = (&date_time.min, &date_time.max) {
+                    if min > max {
+                        l.push(
+                            LintCode::InvalidRange,
+                            format!("min `{min}` is later than max `{max}`"),
+                        );
+                    }
+                }
+            }),
+            FormInput::Email(email) => self.scoped("Email", |l| {
+                l.name(names, Some(&email.name));
+            }),
+            FormInput::Url(url) => self.scoped("Url", |l| {
+                l.name(names, Some(&url.name));
+            }),
+            FormInput::Password(password) => self.scoped("Password", |l| {
+                l.name(names, Some(&password.name));
+                l.name(names, password.confirm_name.as_deref());
+                l.lengths(password.length_min, password.length_max);
+            }),
+            FormInput::Unknown => {}
+        }
+    }
+
+    fn visual_motions(&mut self, field: &str, motions: &[VisualMotion], in_form: bool) {
+        for (i, visual) in motions.iter().enumerate() {
+            self.scoped(format!("{field}[{i}].motion"), |l| {
+                l.motion(&visual.motion, in_form)
+            });
+        }
+    }
+
+    fn motion(&mut self, motion: &Motion, in_form: bool) {
+        match motion {
+            Motion::Href(href) if !is_valid_uri(&href.uri) => self.scoped("Href", |l| {
+                l.push(
+                    LintCode::InvalidUri,
+                    format!("`{}` is not a valid URI", href.uri),
+                );
+            }),
+            Motion::ScrollTo(scroll)
+                if self
+                    .section_ids
+                    .as_ref()
+                    .is_some_and(|ids| !ids.contains(&scroll.section_id)) =>
+            {
+                self.scoped("ScrollTo", |l| {
+                    l.push(
+                        LintCode::ScrollToUnknownSection,
+                        format!("no section on this page has id `{}`", scroll.section_id),
+                    );
+                });
+            }
+            Motion::Submit(_) | Motion::PreviousStep(_) if !in_form => self.push(
+                LintCode::SubmitOutsideForm,
+                "submit motion is not inside a form".to_string(),
+            ),
+            _ => {}
+        }
+    }
+}
@@ -15,6 +15,7 @@ pub use social_data::*;
 
 mod hero;
 mod image;
+mod lenient;
 mod modal;
 mod motion;
 mod navbar;
@@ -22,3 +23,20 @@ mod page;
 mod section;
 mod sidebar;
 mod social_data;
+
+pub mod a11y;
+pub mod concurrency;
+pub mod condition;
+pub mod diagnostics;
+pub mod draft;
+pub mod from_form;
+pub mod image_upload;
+pub mod lint;
+pub mod motion_controller;
+pub mod slug;
+pub mod structured_data;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod turnstile;
+pub mod validation;
+pub mod wizard;
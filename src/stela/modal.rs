@@ -1,10 +1,14 @@
 use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
 
-use crate::stela::Section;
+use crate::stela::{lenient::Lenient, Section};
 
 /// Pop-up section over page content.
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Modal {
     /// What to display.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub section: Section,
 }
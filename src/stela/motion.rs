@@ -1,27 +1,51 @@
 use std::sync::Arc;
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_with::serde_as;
 
-use crate::stela::Modal;
+use crate::stela::{
+    diagnostics::{report, Fallback},
+    lenient::Lenient,
+    Modal,
+};
 
 use super::Image;
 
 /// Display a motion as a button.
+#[serde_as]
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct VisualMotion {
     /// Primary button text.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub title: Option<String>,
+    /// Screen reader text, when `title` is missing or not descriptive enough.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub aria_label: Option<String>,
     /// Slug for the button icon.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub icon: Option<MotionIcon>,
     /// ID for associated image.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub image: Option<Image>,
     /// Whether the button should be in a toggled on state initially.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub initial_toggle: Option<bool>,
     /// How to display the motion.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub variant: MotionVariant,
     /// What color should it be.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub color: MotionColor,
     /// The motion to perform when clicked.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub motion: Motion,
 }
 
@@ -105,10 +129,16 @@ pub enum Motion {
     ApiCall(Arc<MotionApiCall>),
     /// Navigate to this link.
     Href(Arc<MotionHref>),
+    /// Scroll to a section on the current page.
+    ScrollTo(Arc<MotionScrollTo>),
     /// Show a share dialogue.
     Share(Arc<MotionShare>),
     /// Submit the form.
+    ///
+    /// On a step of a multi-step form, submit that step.
     Submit(Arc<MotionSubmit>),
+    /// Go back to the previous step of a multi-step form.
+    PreviousStep(Arc<MotionPreviousStep>),
     /// Unrecognized motion.
     #[default]
     #[serde(other)]
@@ -135,26 +165,142 @@ pub struct MotionApiCallResponse {
     pub redirect: Option<String>,
 }
 
+/// Data to pass to the `motion_interaction` endpoint.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MotionInteractionRequest {
+    /// [`MotionApiCall::data`] of the clicked motion.
+    pub data: String,
+    /// Whether the button was toggled on before it was clicked, if it's a toggle.
+    pub toggle: Option<bool>,
+    /// Path of the page the motion was on, such as `/p/1`.
+    pub page_path: Option<String>,
+    /// [`VisualSection::id`](crate::stela::VisualSection::id) of the section the
+    /// motion was in.
+    pub section_id: Option<String>,
+    /// What the client can do with the response.
+    #[serde(default)]
+    pub capabilities: ClientCapabilities,
+}
+
+bitflags::bitflags! {
+    /// Parts of [`MotionApiCallResponse`] a client knows how to handle.
+    ///
+    /// Names this version doesn't know are dropped, so newer clients can send
+    /// new capabilities.
+    #[derive(Clone, Copy, Debug, Default, Serialize, PartialEq, Eq)]
+    pub struct ClientCapabilities: u32 {
+        /// Applies `new_toggle` and `new_text`.
+        const UPDATE_BUTTON = 1;
+        /// Shows `modal`.
+        const MODAL = 1 << 1;
+        /// Follows `redirect`.
+        const REDIRECT = 1 << 2;
+        /// Shows a native share dialogue for [`Motion::Share`].
+        const SHARE = 1 << 3;
+    }
+}
+
+impl<'de> Deserialize<'de> for ClientCapabilities {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if !deserializer.is_human_readable() {
+            return u32::deserialize(deserializer).map(Self::from_bits_truncate);
+        }
+        let names = String::deserialize(deserializer)?;
+        let mut capabilities = Self::empty();
+        for name in names
+            .split('|')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            match Self::from_name(name) {
+                Some(capability) => capabilities |= capability,
+                None => report(format!("unknown capability `{name}`"), Fallback::Skipped),
+            }
+        }
+        Ok(capabilities)
+    }
+}
+
+/// The `motion_interaction` endpoint couldn't do what was asked.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MotionInteractionError {
+    /// What went wrong.
+    #[serde(default)]
+    pub code: MotionInteractionErrorCode,
+    /// Show this to the user.
+    pub message: Option<String>,
+    /// Whether sending the same request again might work.
+    #[serde(default)]
+    pub retryable: bool,
+}
+
+/// What went wrong with a `motion_interaction` call.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub enum MotionInteractionErrorCode {
+    /// The user needs to sign in.
+    Unauthenticated,
+    /// The user isn't allowed to do this.
+    Forbidden,
+    /// The thing acted on no longer exists.
+    NotFound,
+    /// Too many requests. Try again later.
+    RateLimited,
+    /// `data` wasn't understood.
+    InvalidData,
+    /// Something broke on the server.
+    Internal,
+    /// Unrecognized error.
+    #[default]
+    #[serde(other)]
+    Unknown,
+}
+
 /// Navigate to this link.
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 pub struct MotionHref {
     /// Where to go.
     pub uri: String,
     /// target="_blank"
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub new_tab: Option<bool>,
 }
 
+/// Scroll to a section on the current page.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MotionScrollTo {
+    /// [`VisualSection::id`](crate::stela::VisualSection::id) of the target.
+    pub section_id: String,
+}
+
 /// Show a share dialogue.
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 pub struct MotionShare {
     /// Title to be shared.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub title: Option<String>,
     /// Text to be shared.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub text: Option<String>,
     /// Url to be shared.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub url: Option<String>,
 }
 
 /// Submit the form.
 #[derive(Debug, Deserialize, Serialize)]
 pub struct MotionSubmit {}
+
+/// Go back to the previous step of a multi-step form.
+///
+/// Handled by the client; nothing is submitted.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MotionPreviousStep {}
@@ -0,0 +1,217 @@
+//! Client-side execution of motions, without I/O.
+//!
+//! A [`MotionController`] owns the state of one button. Clicking it gives an
+//! [`Activation`], which either lists [`MotionEffect`]s to apply right away or
+//! gives the [`MotionInteractionRequest`] to send to the `motion_interaction`
+//! endpoint. The client then
+//! passes the result to [`MotionController::complete`] or
+//! [`MotionController::fail`] and applies the effects returned, in order.
+//!
+//! Toggles are optimistic: the button flips as soon as it's clicked, and flips
+//! back if the call fails.
+
+use std::sync::Arc;
+
+use crate::stela::{
+    ClientCapabilities, Modal, Motion, MotionApiCallResponse, MotionInteractionRequest,
+    MotionShare, VisualMotion,
+};
+
+/// What a button currently shows.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ButtonState {
+    /// Primary button text.
+    pub title: Option<String>,
+    /// Toggled on or off, if the button is a toggle.
+    pub toggle: Option<bool>,
+    /// A `motion_interaction` call is in flight. Clicks are ignored.
+    pub pending: bool,
+}
+
+/// Something the client should do to the UI.
+#[derive(Clone, Debug)]
+pub enum MotionEffect {
+    /// Show the button toggled on or off.
+    SetToggle(bool),
+    /// Change the button text.
+    SetTitle(String),
+    /// Show a pop-up.
+    OpenModal(Arc<Modal>),
+    /// Navigate to a URI.
+    Navigate {
+        /// Where to go.
+        uri: String,
+        /// Open in a new tab.
+        new_tab: bool,
+    },
+    /// Scroll to the section with this id.
+    ScrollTo(String),
+    /// Show a share dialogue.
+    Share(Arc<MotionShare>),
+    /// Submit the enclosing form.
+    SubmitForm,
+    /// Go back a step in the enclosing multi-step form.
+    PreviousStep,
+}
+
+/// Result of clicking a button.
+#[derive(Clone, Debug)]
+pub enum Activation {
+    /// Apply the effects, then send `request` to the `motion_interaction`
+    /// endpoint.
+    ApiCall {
+        /// What to send.
+        request: MotionInteractionRequest,
+        /// Optimistic updates to apply while waiting.
+        effects: Vec<MotionEffect>,
+    },
+    /// Apply the effects. Nothing else to do.
+    Effects(Vec<MotionEffect>),
+    /// Nothing to do: the motion is unknown or a call is already in flight.
+    Ignored,
+}
+
+/// State machine for one button.
+#[derive(Clone, Debug)]
+pub struct MotionController {
+    motion: Motion,
+    state: ButtonState,
+    /// Toggle to restore if the in-flight call fails.
+    rollback_toggle: Option<bool>,
+    page_path: Option<String>,
+    section_id: Option<String>,
+    capabilities: ClientCapabilities,
+}
+
+impl MotionController {
+    /// Start from the button's initial state.
+    pub fn new(visual: &VisualMotion) -> Self {
+        Self {
+            motion: visual.motion.clone(),
+            state: ButtonState {
+                title: visual.title.clone(),
+                toggle: visual.initial_toggle,
+                pending: false,
+            },
+            rollback_toggle: None,
+            page_path: None,
+            section_id: None,
+            capabilities: ClientCapabilities::UPDATE_BUTTON
+                | ClientCapabilities::MODAL
+                | ClientCapabilities::REDIRECT,
+        }
+    }
+
+    /// Send `path` as the
+    /// [`page_path`](MotionInteractionRequest::page_path) of requests.
+    pub fn with_page_path(mut self, path: impl Into<String>) -> Self {
+        self.page_path = Some(path.into());
+        self
+    }
+
+    /// Send `id` as the
+    /// [`section_id`](MotionInteractionRequest::section_id) of requests.
+    pub fn with_section_id(mut self, id: impl Into<String>) -> Self {
+        self.section_id = Some(id.into());
+        self
+    }
+
+    /// Send `capabilities` with requests. By default, what
+    /// [`Self::complete`] handles: button updates, modals and redirects.
+    pub fn with_capabilities(mut self, capabilities: ClientCapabilities) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// What the button should show now.
+    pub fn state(&self) -> &ButtonState {
+        &self.state
+    }
+
+    /// The button was clicked.
+    pub fn activate(&mut self) -> Activation {
+        if self.state.pending {
+            return Activation::Ignored;
+        }
+        let effect = match &self.motion {
+            Motion::ApiCall(call) => {
+                let mut effects = Vec::new();
+                self.rollback_toggle = self.state.toggle;
+                if let Some(toggle) = &mut self.state.toggle {
+                    *toggle = !*toggle;
+                    effects.push(MotionEffect::SetToggle(*toggle));
+                }
+                self.state.pending = true;
+                let request = MotionInteractionRequest {
+                    data: call.data.clone(),
+                    toggle: self.rollback_toggle,
+                    page_path: self.page_path.clone(),
+                    section_id: self.section_id.clone(),
+                    capabilities: self.capabilities,
+                };
+                return Activation::ApiCall { request, effects };
+            }
+            Motion::Href(href) => MotionEffect::Navigate {
+                uri: href.uri.clone(),
+                new_tab: href.new_tab.unwrap_or(false),
+            },
+            Motion::ScrollTo(scroll) => MotionEffect::ScrollTo(scroll.section_id.clone()),
+            Motion::Share(share) => MotionEffect::Share(share.clone()),
+            Motion::Submit(_) => MotionEffect::SubmitForm,
+            Motion::PreviousStep(_) => MotionEffect::PreviousStep,
+            Motion::Unknown => return Activation::Ignored,
+        };
+        Activation::Effects(vec![effect])
+    }
+
+    /// The `motion_interaction` call succeeded.
+    ///
+    /// Effects are ordered toggle, title, modal, then redirect. A `new_toggle`
+    /// overrides the optimistic toggle; without one, the optimistic toggle
+    /// stands. Returns nothing if no call was in flight.
+    pub fn complete(&mut self, response: &MotionApiCallResponse) -> Vec<MotionEffect> {
+        if !self.state.pending {
+            return Vec::new();
+        }
+        self.state.pending = false;
+        self.rollback_toggle = None;
+
+        let mut effects = Vec::new();
+        if let Some(toggle) = response.new_toggle {
+            if self.state.toggle != Some(toggle) {
+                effects.push(MotionEffect::SetToggle(toggle));
+            }
+            self.state.toggle = Some(toggle);
+        }
+        if let Some(text) = &response.new_text {
+            self.state.title = Some(text.clone());
+            effects.push(MotionEffect::SetTitle(text.clone()));
+        }
+        if let Some(modal) = &response.modal {
+            effects.push(MotionEffect::OpenModal(modal.clone()));
+        }
+        if let Some(uri) = &response.redirect {
+            effects.push(MotionEffect::Navigate {
+                uri: uri.clone(),
+                new_tab: false,
+            });
+        }
+        effects
+    }
+
+    /// The `motion_interaction` call failed. Undo the optimistic toggle.
+    ///
+    /// Returns nothing if no call was in flight.
+    pub fn fail(&mut self) -> Vec<MotionEffect> {
+        if !self.state.pending {
+            return Vec::new();
+        }
+        self.state.pending = false;
+        let rollback = self.rollback_toggle.take();
+        if rollback == self.state.toggle {
+            return Vec::new();
+        }
+        self.state.toggle = rollback;
+        rollback.map(MotionEffect::SetToggle).into_iter().collect()
+    }
+}
@@ -1,22 +1,39 @@
 use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
 
-use crate::stela::{Image, Motion, VisualMotion};
+use crate::stela::{
+    lenient::{Lenient, LenientVec},
+    Image, Motion, VisualMotion,
+};
 
 /// Representation of data and buttons on the top/side navbars.
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Navbar {
     /// Motion for when left icon image is selected.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub left_side_motion: Motion,
     /// Primary image to show on left side of navbar.
     pub left_side_icon_image: Image,
     /// Dim text to show next to primary icon image.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub left_side_secondary_text: Option<String>,
     /// Motion for when search bar is clicked.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub search_motion: Option<Motion>,
     /// Text inside the search bar.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub search_text: Option<String>,
     /// Buttons on right side of navbar.
+    #[serde_as(as = "LenientVec")]
+    #[serde(default)]
     pub right_side_motions: Vec<VisualMotion>,
     /// Buttons in side nav.
+    #[serde_as(as = "LenientVec")]
+    #[serde(default)]
     pub side_motions: Vec<VisualMotion>,
 }
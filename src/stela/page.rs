@@ -1,42 +1,111 @@
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 use serde::{Deserialize, Serialize};
-use serde_with::{serde_as, DefaultOnError, VecSkipError};
+use serde_with::serde_as;
 
-use crate::stela::{Hero, Sidebar, SocialData, VisualSection};
+use crate::stela::{
+    diagnostics::{self, Diagnostic, Fallback},
+    lenient::{Lenient, LenientVec},
+    slug::Slugger,
+    Hero, Sidebar, SocialData, VisualSection,
+};
+
+/// Longest id [`Page::assign_section_ids`] generates from a title.
+const SECTION_ID_MAX_LEN: usize = 64;
 
 /// An entire page.
 #[serde_as]
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
 pub struct Page {
     /// The title of the page.
-    #[serde_as(as = "DefaultOnError")]
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub title: Option<String>,
     /// What language is the page itself in.
     ///
     /// Individual sections can still have their own languages.
     ///
     /// This is an `ISO-639` locale code, such as `en`, `eo`, `es`, `fr`.
-    #[serde_as(as = "DefaultOnError")]
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub lang: Option<String>,
     /// Info for the meta tags.
-    #[serde_as(as = "DefaultOnError")]
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub social: Option<Arc<SocialData>>,
     /// How to display things on screen.
-    #[serde_as(as = "DefaultOnError")]
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub layout: Option<PageLayout>,
     /// Display something fancy at the top of the page.
-    #[serde_as(as = "DefaultOnError")]
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub hero: Option<Arc<Hero>>,
     /// Mini sections on the side of the screen.
-    #[serde_as(as = "DefaultOnError")]
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub sidebar: Option<Arc<Sidebar>>,
     /// Individual sections of the page.
-    #[serde_as(as = "VecSkipError<_>")]
+    #[serde_as(as = "LenientVec")]
     #[serde(default)]
     pub sections: Vec<VisualSection>,
 }
 
+impl Page {
+    /// Find a section by its [`VisualSection::id`].
+    pub fn section_by_id(&self, id: &str) -> Option<&VisualSection> {
+        self.sections
+            .iter()
+            .find(|section| section.id.as_deref() == Some(id))
+    }
+
+    /// Give every section a unique id.
+    ///
+    /// Sections without an id get an x-system [`slug`](crate::stela::slug) of
+    /// their title, or `section-N` if they have no usable title. Duplicates
+    /// get a numeric suffix such as `-2`. Explicit ids are kept before any id
+    /// is generated, and the first occurrence of an explicit id keeps it, so
+    /// existing deep links stay stable.
+    pub fn assign_section_ids(&mut self) {
+        let slugger = Slugger {
+            max_len: Some(SECTION_ID_MAX_LEN),
+            ..Slugger::default()
+        };
+        let mut taken = HashSet::new();
+        for section in &mut self.sections {
+            if let Some(id) = section.id.as_deref().filter(|id| !id.is_empty()) {
+                section.id = Some(slugger.dedupe(id, &mut taken));
+            }
+        }
+        for (i, section) in self.sections.iter_mut().enumerate() {
+            if section.id.as_deref().is_some_and(|id| !id.is_empty()) {
+                continue;
+            }
+            let base = section
+                .title
+                .as_deref()
+                .and_then(|title| slugger.slug(title).ok())
+                .unwrap_or_else(|| format!("section-{}", i + 1));
+            section.id = Some(slugger.dedupe(&base, &mut taken));
+        }
+    }
+
+    /// Parse a page, reporting every malformed value that was defaulted or skipped.
+    ///
+    /// Never fails: if the JSON is invalid or isn't an object, an empty page is
+    /// returned with a single diagnostic at the root.
+    pub fn from_json_with_diagnostics(json: &str) -> (Page, Vec<Diagnostic>) {
+        diagnostics::from_json(json).unwrap_or_else(|error| {
+            let diagnostic = Diagnostic {
+                path: String::new(),
+                error: error.to_string(),
+                fallback: Fallback::Default,
+            };
+            (Page::default(), vec![diagnostic])
+        })
+    }
+}
+
 /// How to display things on screen.
 #[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
 pub enum PageLayout {
@@ -1,7 +1,9 @@
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
-use serde_with::{serde_as, DefaultOnError};
+use serde_with::serde_as;
+
+use crate::stela::lenient::Lenient;
 
 pub use section_form::*;
 pub use section_hero::*;
@@ -19,12 +21,23 @@ mod section_tiles;
 #[serde_as]
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct VisualSection {
+    /// Anchor for deep links and [`Motion::ScrollTo`](crate::stela::Motion::ScrollTo).
+    ///
+    /// Unique within a page. See [`Page::assign_section_ids`](crate::stela::Page::assign_section_ids).
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub id: Option<String>,
     /// Name to display for the section.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub title: Option<String>,
     /// Should the section be drawn with a border around it.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub bordered: Option<bool>,
     /// The actual section.
-    #[serde_as(as = "DefaultOnError")]
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub section: Section,
 }
 
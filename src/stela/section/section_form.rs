@@ -1,28 +1,118 @@
 use std::{collections::HashMap, sync::Arc};
 
 use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
 
-use crate::stela::{Image, Modal, VisualMotion};
+use crate::stela::{
+    lenient::{Lenient, LenientVec},
+    Image, Modal, VisualMotion,
+};
 
 /// Fill something out and submit.
-#[derive(Debug, Deserialize, Serialize)]
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SectionForm {
     /// Primary text at top.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub header: Option<String>,
     /// Secondary text under header.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub subheader: Option<String>,
     /// Which form is this.
     ///
     /// Arbitrary, unstructured data. Provided by API.
     pub form_name: String,
     /// Arbitrary, unstructured data. Provided by API.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub extra_data: Option<String>,
     /// Text to show in the `<noscript>` tag.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub noscript_text: Option<String>,
     /// Individual input fields.
+    ///
+    /// In a multi-step form, these are shown below the inputs of every step,
+    /// which makes them the place for back and next buttons.
+    #[serde_as(as = "LenientVec")]
+    #[serde(default)]
+    pub inputs: Vec<FormInput>,
+    /// Steps of a multi-step form, shown one at a time.
+    ///
+    /// Empty for a form with a single page.
+    #[serde_as(as = "LenientVec")]
+    #[serde(default)]
+    pub steps: Vec<FormStep>,
+    /// Index into `steps` to show first. The first step if unset.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub initial_step: Option<usize>,
+    /// How to show progress through `steps`.
+    #[serde(default)]
+    pub step_progress: StepProgress,
+    /// Save what's entered as a draft on the device, to restore if the page is
+    /// closed before submitting. Off if unset.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub autosave: Option<FormAutosave>,
+    /// Version of what the form edits, such as an HTTP `ETag`. Clients send it
+    /// back in [`FormCallData::etag`], so edits of an outdated version can be
+    /// caught, see [`crate::stela::concurrency`].
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub etag: Option<String>,
+}
+
+/// Settings for saving drafts of a form, see [`crate::stela::draft`].
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct FormAutosave {
+    /// Version of the form's inputs. Bump it when inputs change meaning, so
+    /// drafts of the old form aren't restored into the new one.
+    pub version: u32,
+    /// How long to keep drafts, in seconds. Until submitted if unset.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub max_age: Option<u64>,
+}
+
+/// One page of a multi-step form.
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FormStep {
+    /// Name of the step, for the progress indicator.
+    pub title: String,
+    /// Text under the title.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub subtitle: Option<String>,
+    /// Inputs on this step.
+    #[serde_as(as = "LenientVec")]
+    #[serde(default)]
     pub inputs: Vec<FormInput>,
 }
 
+/// How to show progress through a multi-step form.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub enum StepProgress {
+    /// "Step 2 of 4" with the step's title.
+    Numbered,
+    /// A dot per step.
+    Dots,
+    /// A bar that fills up.
+    Bar,
+    /// The titles of all steps, with the current one highlighted.
+    Titles,
+    /// No progress indicator.
+    Hidden,
+    /// Unknown style. Clients pick their own.
+    #[default]
+    #[serde(other)]
+    Unknown,
+}
+
 /// What kind of input it is with needed extra info.
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub enum FormInput {
@@ -44,13 +134,87 @@ pub enum FormInput {
     Subsection(Arc<FormInputSubsection>),
     /// Type text.
     Text(Arc<FormInputText>),
+    /// Pick one option from a dropdown.
+    Select(Arc<FormInputSelect>),
+    /// Type a number.
+    Number(Arc<FormInputNumber>),
+    /// Pick a date, a time, or both.
+    DateTime(Arc<FormInputDateTime>),
+    /// Type an email address.
+    Email(Arc<FormInputEmail>),
+    /// Type a web address.
+    Url(Arc<FormInputUrl>),
+    /// Type a password, optionally twice.
+    Password(Arc<FormInputPassword>),
+    /// Pick any number of options, or type tags.
+    MultiSelect(Arc<FormInputMultiSelect>),
+    /// Inputs shown or required depending on other inputs.
+    Conditional(Arc<FormInputConditional>),
     /// Unknown form input.
     #[default]
     #[serde(other)]
     Unknown,
 }
 
+impl SectionForm {
+    /// Find the input named `name`, including inside subsections and tabs.
+    pub fn input(&self, name: &str) -> Option<&FormInput> {
+        fn find<'a>(inputs: &'a [FormInput], name: &str) -> Option<&'a FormInput> {
+            inputs.iter().find_map(|input| match input {
+                FormInput::Subsection(subsection) => find(&subsection.inputs, name),
+                FormInput::Conditional(conditional) => find(&conditional.inputs, name),
+                FormInput::Tabs(tabs) => tabs
+                    .tabs
+                    .iter()
+                    .find_map(|tab| find(std::slice::from_ref(&tab.input), name)),
+                _ => (input.name() == Some(name)).then_some(input),
+            })
+        }
+        find(&self.inputs, name)
+            .or_else(|| self.steps.iter().find_map(|step| find(&step.inputs, name)))
+    }
+
+    /// `inputs`, then the inputs of every step.
+    pub fn all_inputs(&self) -> impl Iterator<Item = &FormInput> {
+        self.inputs
+            .iter()
+            .chain(self.steps.iter().flat_map(|step| &step.inputs))
+    }
+}
+
+impl FormInput {
+    /// What this input puts in form-data, if it submits a value.
+    ///
+    /// Turnstile defaults to `cf-turnstile-response`, like the widget does.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Self::Checkbox(checkbox) => Some(&checkbox.name),
+            Self::CfTurnstile(turnstile) => Some(turnstile.field_name()),
+            Self::Image(image) => image.name.as_deref(),
+            Self::Markdown(markdown) => Some(&markdown.name),
+            Self::Radio(radio) => Some(&radio.name),
+            Self::Text(text) => text.name.as_deref(),
+            Self::Select(select) => Some(&select.name),
+            Self::Number(number) => Some(&number.name),
+            Self::DateTime(date_time) => Some(&date_time.name),
+            Self::Email(email) => Some(&email.name),
+            Self::Url(url) => Some(&url.name),
+            Self::Password(password) => Some(&password.name),
+            Self::MultiSelect(multi) => Some(&multi.name),
+            Self::Motions(_)
+            | Self::Subsection(_)
+            | Self::Tabs(_)
+            | Self::Conditional(_)
+            | Self::Unknown => None,
+        }
+    }
+}
+
 /// Data to pass to the `form_submit()` server function.
+///
+/// An input with several values, such as a [`FormInputMultiSelect`], is
+/// submitted as a [`FormValue::List`]. Form-data bodies repeat the name
+/// instead; [`FormCallData::from_entries`] collects those into lists.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct FormCallData {
     /// Which form is this.
@@ -60,53 +224,486 @@ pub struct FormCallData {
     /// Arbitrary, unstructured data. Provided by API.
     pub extra_data: Option<String>,
     /// The values of the form fields as entered by the user.
-    pub fields: HashMap<String, String>,
+    pub fields: HashMap<String, FormValue>,
+    /// Index of the step submitted, for a multi-step form. Unset when
+    /// submitting the whole form.
+    #[serde(default)]
+    pub step: Option<usize>,
+    /// [`SectionForm::etag`] of the form when it was loaded.
+    #[serde(default)]
+    pub etag: Option<String>,
+}
+
+impl FormCallData {
+    /// Collect name-value pairs, such as a parsed form-data body, in order.
+    ///
+    /// A name that appears more than once becomes a [`FormValue::List`].
+    pub fn from_entries(
+        form_name: impl Into<String>,
+        extra_data: Option<String>,
+        entries: impl IntoIterator<Item = (String, String)>,
+    ) -> Self {
+        let mut data = Self {
+            form_name: form_name.into(),
+            extra_data,
+            fields: HashMap::new(),
+            step: None,
+            etag: None,
+        };
+        for (name, value) in entries {
+            data.append(name, value.into());
+        }
+        data
+    }
+
+    /// Add a value for `name`, turning the field into a list if it already
+    /// has one.
+    pub fn append(&mut self, name: impl Into<String>, value: FormValue) {
+        use std::collections::hash_map::Entry;
+
+        match self.fields.entry(name.into()) {
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+            }
+            Entry::Occupied(mut entry) => match entry.get_mut() {
+                FormValue::List(values) => values.push(value),
+                existing => {
+                    let first = std::mem::replace(existing, FormValue::List(Vec::new()));
+                    *existing = FormValue::List(vec![first, value]);
+                }
+            },
+        }
+    }
+
+    /// The value submitted for `input`, read as the kind of value it takes.
+    ///
+    /// Plain strings from older clients are converted: `"on"` or `"true"` to
+    /// a checked checkbox, and any string to a radio or select option or an
+    /// image id. Numbers and dates are text, as typed. A
+    /// checkbox that wasn't submitted is unchecked.
+    ///
+    /// A multi-select is always a [`FormValue::List`], empty if nothing was
+    /// submitted. Its items are [`FormValue::Option`]s for values of its
+    /// options, and [`FormValue::Text`] for typed tags.
+    ///
+    /// Returns `None` if nothing was submitted or the value doesn't fit the
+    /// input.
+    pub fn value(&self, input: &FormInput) -> Option<FormValue> {
+        let value = input.name().and_then(|name| self.fields.get(name));
+        match (input, value) {
+            (FormInput::Checkbox(_), None) => Some(FormValue::Bool(false)),
+            (FormInput::MultiSelect(multi), value) => {
+                let values = match value {
+                    None => &[][..],
+                    Some(FormValue::List(values)) => values,
+                    Some(value) => std::slice::from_ref(value),
+                };
+                let values = values
+                    .iter()
+                    .filter_map(|value| match value {
+                        FormValue::Text(value) | FormValue::Option(value) => Some(value),
+                        _ => None,
+                    })
+                    .map(|value| {
+                        if multi.options.iter().any(|option| &option.value == value) {
+                            FormValue::Option(value.clone())
+                        } else {
+                            FormValue::Text(value.clone())
+                        }
+                    })
+                    .collect();
+                Some(FormValue::List(values))
+            }
+            (_, None) => None,
+            (FormInput::Checkbox(_), Some(FormValue::Text(text))) => {
+                Some(FormValue::Bool(matches!(text.as_str(), "on" | "true")))
+            }
+            (FormInput::Checkbox(_), Some(FormValue::Bool(checked))) => {
+                Some(FormValue::Bool(*checked))
+            }
+            (
+                FormInput::Radio(_) | FormInput::Select(_),
+                Some(FormValue::Text(value) | FormValue::Option(value)),
+            ) => Some(FormValue::Option(value.clone())),
+            (FormInput::Image(_), Some(FormValue::Text(id) | FormValue::Image(id))) => {
+                Some(FormValue::Image(id.clone()))
+            }
+            (
+                FormInput::CfTurnstile(_)
+                | FormInput::Markdown(_)
+                | FormInput::Text(_)
+                | FormInput::Number(_)
+                | FormInput::DateTime(_)
+                | FormInput::Email(_)
+                | FormInput::Url(_)
+                | FormInput::Password(_),
+                Some(FormValue::Text(text)),
+            ) => Some(FormValue::Text(text.clone())),
+            _ => None,
+        }
+    }
+
+    /// The text submitted for `input`, if it takes text.
+    pub fn text(&self, input: &FormInput) -> Option<String> {
+        match self.value(input)? {
+            FormValue::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Whether the checkbox `input` was checked.
+    pub fn checked(&self, input: &FormInput) -> Option<bool> {
+        self.value(input)?.as_bool()
+    }
+}
+
+/// A value submitted for one input.
+///
+/// Text is a plain JSON string, as every value was before this type existed.
+/// Everything else is externally tagged, such as `{"Bool": true}`.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(from = "FormValueRepr", into = "FormValueRepr")]
+pub enum FormValue {
+    /// Typed text, markdown, or a Turnstile token.
+    Text(String),
+    /// Checkbox state.
+    Bool(bool),
+    /// Id of an uploaded image.
+    Image(String),
+    /// `value` of the selected [`RadioButton`] or [`SelectOption`].
+    Option(String),
+    /// Several values for one input.
+    List(Vec<FormValue>),
+}
+
+impl FormValue {
+    /// The text, if this is [`FormValue::Text`].
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            Self::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// The checkbox state, if this is [`FormValue::Bool`].
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(checked) => Some(*checked),
+            _ => None,
+        }
+    }
+
+    /// The image id, if this is [`FormValue::Image`].
+    pub fn as_image_id(&self) -> Option<&str> {
+        match self {
+            Self::Image(id) => Some(id),
+            _ => None,
+        }
+    }
+
+    /// The selected option, if this is [`FormValue::Option`].
+    pub fn as_option(&self) -> Option<&str> {
+        match self {
+            Self::Option(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The values, if this is [`FormValue::List`].
+    pub fn as_list(&self) -> Option<&[FormValue]> {
+        match self {
+            Self::List(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+impl From<String> for FormValue {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<&str> for FormValue {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+
+impl From<bool> for FormValue {
+    fn from(checked: bool) -> Self {
+        Self::Bool(checked)
+    }
+}
+
+/// Wire format of [`FormValue`]: bare strings are text.
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+enum FormValueRepr {
+    Text(String),
+    Tagged(TaggedFormValue),
+}
+
+#[derive(Deserialize, Serialize)]
+enum TaggedFormValue {
+    Bool(bool),
+    Image(String),
+    Option(String),
+    List(Vec<FormValue>),
+}
+
+impl From<FormValueRepr> for FormValue {
+    fn from(repr: FormValueRepr) -> Self {
+        match repr {
+            FormValueRepr::Text(text) => Self::Text(text),
+            FormValueRepr::Tagged(TaggedFormValue::Bool(checked)) => Self::Bool(checked),
+            FormValueRepr::Tagged(TaggedFormValue::Image(id)) => Self::Image(id),
+            FormValueRepr::Tagged(TaggedFormValue::Option(value)) => Self::Option(value),
+            FormValueRepr::Tagged(TaggedFormValue::List(values)) => Self::List(values),
+        }
+    }
+}
+
+impl From<FormValue> for FormValueRepr {
+    fn from(value: FormValue) -> Self {
+        match value {
+            FormValue::Text(text) => Self::Text(text),
+            FormValue::Bool(checked) => Self::Tagged(TaggedFormValue::Bool(checked)),
+            FormValue::Image(id) => Self::Tagged(TaggedFormValue::Image(id)),
+            FormValue::Option(value) => Self::Tagged(TaggedFormValue::Option(value)),
+            FormValue::List(values) => Self::Tagged(TaggedFormValue::List(values)),
+        }
+    }
 }
 
 /// Call the `form_submit` endpoint and do something with the response.
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct FormResponse {
     /// Show an error.
+    ///
+    /// Older clients only show this, so set it even when using
+    /// [`Self::field_errors`] or [`Self::form_errors`].
     pub error: Option<String>,
+    /// Errors for individual inputs, by input `name`.
+    #[serde(default)]
+    pub field_errors: HashMap<String, Vec<FormError>>,
+    /// Errors for the form as a whole.
+    #[serde(default)]
+    pub form_errors: Vec<FormError>,
+    /// Put these values back in the inputs, by input `name`, so the user
+    /// doesn't have to retype them.
+    #[serde(default)]
+    pub fields: HashMap<String, FormValue>,
     /// Show a pop-up.
     pub modal: Option<Arc<Modal>>,
     /// Send the user to the given URI.
     pub redirect: Option<String>,
     /// Hide the form and show this text.
     pub success: Option<String>,
+    /// Show this step of a multi-step form next.
+    #[serde(default)]
+    pub step: Option<usize>,
+    /// Replace the form's `extra_data`, to be sent back with the next
+    /// submission. Carries the progress of a multi-step form.
+    #[serde(default)]
+    pub extra_data: Option<String>,
+    /// The submission edited an outdated version. Show the current values.
+    #[serde(default)]
+    pub conflict: Option<FormConflict>,
+}
+
+/// What changed since an edit form was loaded.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct FormConflict {
+    /// The current [`SectionForm::etag`], to submit with the next attempt.
+    pub etag: Option<String>,
+    /// The current values, by input `name`.
+    #[serde(default)]
+    pub fields: HashMap<String, FormValue>,
+    /// Inputs changed both by the user and by someone else, in ways that
+    /// couldn't be merged.
+    #[serde(default)]
+    pub conflicting: Vec<String>,
+}
+
+impl FormResponse {
+    /// Add an error for the input named `name`.
+    ///
+    /// Also sets [`Self::error`] to `error.message` if it isn't set yet.
+    pub fn with_field_error(mut self, name: impl Into<String>, error: FormError) -> Self {
+        self.error.get_or_insert_with(|| error.message.clone());
+        self.field_errors
+            .entry(name.into())
+            .or_default()
+            .push(error);
+        self
+    }
+
+    /// Add an error for the form as a whole.
+    ///
+    /// Also sets [`Self::error`] to `error.message` if it isn't set yet.
+    pub fn with_form_error(mut self, error: FormError) -> Self {
+        self.error.get_or_insert_with(|| error.message.clone());
+        self.form_errors.push(error);
+        self
+    }
+
+    /// Keep the values the user entered into `form`, except passwords and
+    /// Turnstile tokens, see [`SectionForm::preserved_fields`].
+    pub fn preserving(mut self, form: &SectionForm, data: &FormCallData) -> Self {
+        self.fields = form.preserved_fields(data);
+        self
+    }
+
+    /// Show `step` of a multi-step form next.
+    pub fn with_step(mut self, step: usize) -> Self {
+        self.step = Some(step);
+        self
+    }
+
+    /// Replace the form's `extra_data`.
+    pub fn with_extra_data(mut self, extra_data: impl Into<String>) -> Self {
+        self.extra_data = Some(extra_data.into());
+        self
+    }
+
+    /// Reject a submission of an outdated version.
+    ///
+    /// Adds a [`FormErrorCode::Conflict`] form error, and a field error for each
+    /// of [`FormConflict::conflicting`].
+    pub fn with_conflict(mut self, conflict: FormConflict) -> Self {
+        for name in &conflict.conflicting {
+            let error = FormError::new(FormErrorCode::Conflict, "someone else changed this too");
+            self = self.with_field_error(name, error);
+        }
+        let error = FormError::new(
+            FormErrorCode::Conflict,
+            "this was changed by someone else since you opened it",
+        );
+        self.error = Some(error.message.clone());
+        self.form_errors.push(error);
+        self.conflict = Some(conflict);
+        self
+    }
+
+    /// Whether there is any error to show.
+    pub fn has_errors(&self) -> bool {
+        self.error.is_some() || !self.field_errors.is_empty() || !self.form_errors.is_empty()
+    }
+}
+
+/// Something wrong with a submitted form.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct FormError {
+    /// What kind of error, for client-side localization.
+    #[serde(default)]
+    pub code: FormErrorCode,
+    /// Show this if the client has no translation for `code`.
+    pub message: String,
+}
+
+impl FormError {
+    /// An error with a fallback message.
+    pub fn new(code: FormErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// What kind of error a [`FormError`] is.
+///
+/// Limits such as the minimum length come from the input itself.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub enum FormErrorCode {
+    /// No value was given.
+    Required,
+    /// Fewer characters than `length_min`.
+    TooShort,
+    /// More characters than `length_max`.
+    TooLong,
+    /// Characters outside the input's [`TextFilter`].
+    DisallowedCharacters,
+    /// Not one of the options.
+    InvalidChoice,
+    /// Not a number, date, email address, or URL, as the input expects.
+    InvalidFormat,
+    /// Less than, or earlier than, `min`.
+    TooSmall,
+    /// Greater than, or later than, `max`.
+    TooLarge,
+    /// Not a multiple of `step`.
+    InvalidStep,
+    /// Doesn't match the confirmation field.
+    Mismatch,
+    /// Fewer than `min_selected` picked.
+    TooFew,
+    /// More than `max_selected` picked.
+    TooMany,
+    /// Image aspect ratio outside `aspect_min` to `aspect_max`.
+    InvalidAspect,
+    /// Changed by someone else since the form was loaded.
+    Conflict,
+    /// The value isn't acceptable for some other reason.
+    Invalid,
+    /// Unrecognized error.
+    #[default]
+    #[serde(other)]
+    Unknown,
 }
 
 /// A smaller form with a title inside the full form.
-#[derive(Debug, Deserialize, Serialize)]
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct FormInputSubsection {
     /// Human-readable name.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub title: Option<String>,
     /// Individual input fields.
+    #[serde_as(as = "LenientVec")]
+    #[serde(default)]
     pub inputs: Vec<FormInput>,
 }
 
 /// This is a text field
-#[derive(Debug, Deserialize, Serialize)]
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct FormInputText {
     /// Human-readable name.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub title: Option<String>,
     /// What to put in form-data for the API.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub name: Option<String>,
     /// Value to start with.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub initial_value: Option<String>,
     /// Minimum character count.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub length_min: Option<i32>,
     /// Maximum character count.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub length_max: Option<i32>,
     /// Add x-system converter button.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub esperanto: bool,
     /// Filter out certain letters.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub filter: Option<TextFilter>,
 }
 
 bitflags::bitflags! {
     /// Allow-list of kinds of characters.
-    #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+    #[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
     pub struct TextFilter: u32 {
         /// `A-Z` ascii
         const ALPHA_ASCII = 1;
@@ -125,17 +722,102 @@ bitflags::bitflags! {
     }
 }
 
+impl TextFilter {
+    /// Whether `c` belongs to any of the allowed kinds.
+    pub fn allows(self, c: char) -> bool {
+        (self.contains(Self::ALPHA_ASCII) && c.is_ascii_alphabetic())
+            || (self.contains(Self::ALPHA_EO)
+                && (matches!(c, 'a'..='w' | 'y' | 'z' | 'A'..='W' | 'Y' | 'Z')
+                    || "ĉĝĥĵŝŭĈĜĤĴŜŬ".contains(c)))
+            || (self.contains(Self::NUMERIC) && c.is_ascii_digit())
+            || (self.contains(Self::DASH) && c == '-')
+            || (self.contains(Self::UNDERSCORE) && c == '_')
+            || (self.contains(Self::PERIOD) && c == '.')
+            || (self.contains(Self::SPACE) && c == ' ')
+    }
+
+    /// The first character of `text` that isn't allowed, if any.
+    pub fn first_disallowed(self, text: &str) -> Option<char> {
+        text.chars().find(|&c| !self.allows(c))
+    }
+}
+
 /// Upload an image.
-#[derive(Debug, Deserialize, Serialize)]
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct FormInputImage {
     /// Human-readable name.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub title: Option<String>,
     /// What to put in form-data for the API.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub name: Option<String>,
     /// Image to start with.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub initial_image: Option<Image>,
     /// How to show the image after upload.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub preview_style: ImagePreviewStyle,
+    /// What uploads are accepted.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub constraints: ImageConstraints,
+    /// Have the user crop the image to this aspect ratio before uploading.
+    ///
+    /// width / height
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub crop_aspect: Option<f32>,
+}
+
+/// Limits on images uploaded to a [`FormInputImage`], before any crop.
+///
+/// Clients check these before asking for an
+/// [`ImageUploadTicket`](crate::stela::image_upload::ImageUploadTicket), and
+/// the API checks them again, see [`crate::stela::image_upload`].
+#[serde_as]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct ImageConstraints {
+    /// Accepted MIME types, such as `image/png`. Any image type if empty.
+    #[serde_as(as = "LenientVec")]
+    #[serde(default)]
+    pub mime_types: Vec<String>,
+    /// Maximum file size in bytes.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub max_bytes: Option<u64>,
+    /// Minimum width in pixels.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub min_width: Option<u32>,
+    /// Minimum height in pixels.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub min_height: Option<u32>,
+    /// Maximum width in pixels.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub max_width: Option<u32>,
+    /// Maximum height in pixels.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub max_height: Option<u32>,
+    /// Minimum aspect ratio.
+    ///
+    /// width / height
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub aspect_min: Option<f32>,
+    /// Maximum aspect ratio.
+    ///
+    /// width / height
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub aspect_max: Option<f32>,
 }
 
 /// How to show an image after upload.
@@ -154,81 +836,341 @@ pub enum ImagePreviewStyle {
 }
 
 /// Write large body text as markdown.
-#[derive(Debug, Deserialize, Serialize)]
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct FormInputMarkdown {
     /// Human-readable name.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub title: Option<String>,
     /// What to put in form-data for the API.
     pub name: String,
     /// Text to start with.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub initial_value: Option<String>,
     /// Minimum character count.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub length_min: Option<i32>,
     /// Maximum character count.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub length_max: Option<i32>,
 }
 
 /// Select from multiple options.
 ///
 /// Can only select one.
-#[derive(Debug, Deserialize, Serialize)]
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct FormInputRadio {
     /// Human-readable name.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub title: Option<String>,
     /// What to put in form-data for the API.
     pub name: String,
     /// Index of initial value.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub initial_index: Option<usize>,
     /// Individual selectable options.
+    #[serde_as(as = "LenientVec")]
+    #[serde(default)]
     pub options: Vec<RadioButton>,
 }
 
 /// An individual radio button.
-#[derive(Debug, Deserialize, Serialize)]
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct RadioButton {
     /// What to put in form-data for the API.
     pub value: String,
     /// Human-readable text for option.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub title: Option<String>,
 }
 
 /// Toggle on or off.
-#[derive(Debug, Deserialize, Serialize)]
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct FormInputCheckbox {
     /// Human-readable name.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub title: Option<String>,
     /// What to put in form-data for the API.
     pub name: String,
     /// Should it start checked.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub default_checked: Option<bool>,
 }
 
 /// Cloudflare Turnstile
-#[derive(Debug, Deserialize, Serialize)]
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct FormInputCfTurnstile {
     /// Attribute `class`
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub class: Option<String>,
     /// Attribute `data-sitekey`
     pub sitekey: String,
     /// Attribute `data-response-field-name`
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub response_field_name: Option<String>,
     /// Attribute `data-size`
-    pub size: Option<String>,
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub size: Option<TurnstileSize>,
     /// Attribute `data-language`
-    pub language: Option<String>,
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub language: Option<TurnstileLanguage>,
+    /// Attribute `data-theme`
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub theme: Option<TurnstileTheme>,
+    /// Attribute `data-appearance`
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub appearance: Option<TurnstileAppearance>,
+    /// Attribute `data-execution`
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub execution: Option<TurnstileExecution>,
+    /// Attribute `data-retry`
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub retry: Option<TurnstileRetry>,
+    /// Attribute `data-action`
+    ///
+    /// Returned by siteverify, to tell widgets apart. At most 32 of `a-z`,
+    /// `A-Z`, `0-9`, `-` and `_`.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub action: Option<String>,
+    /// Attribute `data-cdata`
+    ///
+    /// Returned by siteverify, such as a session id. At most 255 of the same
+    /// characters as `action`.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub cdata: Option<String>,
+}
+
+/// Size of the Turnstile widget.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TurnstileSize {
+    /// 300 × 65 pixels.
+    Normal,
+    /// 150 × 140 pixels.
+    Compact,
+    /// Full width of the form, at least 300 pixels.
+    Flexible,
+    /// Unknown size.
+    #[default]
+    #[serde(other)]
+    Unknown,
+}
+
+/// Color scheme of the Turnstile widget.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TurnstileTheme {
+    /// Light.
+    Light,
+    /// Dark.
+    Dark,
+    /// Follow the visitor's preference.
+    Auto,
+    /// Unknown theme.
+    #[default]
+    #[serde(other)]
+    Unknown,
+}
+
+/// When the Turnstile widget is visible.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TurnstileAppearance {
+    /// Always.
+    Always,
+    /// Once the challenge runs.
+    Execute,
+    /// Only if the visitor has to interact with it.
+    InteractionOnly,
+    /// Unknown appearance.
+    #[default]
+    #[serde(other)]
+    Unknown,
+}
+
+/// When the Turnstile challenge runs.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TurnstileExecution {
+    /// As soon as the widget renders.
+    Render,
+    /// When the client calls `turnstile.execute`, such as on submit.
+    Execute,
+    /// Unknown execution mode.
+    #[default]
+    #[serde(other)]
+    Unknown,
 }
 
+/// Whether the Turnstile widget retries a failed challenge by itself.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TurnstileRetry {
+    /// Retry automatically.
+    Auto,
+    /// Leave retrying to the client.
+    Never,
+    /// Unknown retry mode.
+    #[default]
+    #[serde(other)]
+    Unknown,
+}
+
+/// Language of the Turnstile widget: `auto`, or an ISO 639 language code with
+/// an optional region, such as `eo` or `pt-BR`.
+///
+/// Parse one with [`str::parse`]. Codes are normalized to a lowercase language
+/// and an uppercase region.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(try_from = "String", into = "String")]
+pub struct TurnstileLanguage(String);
+
+/// A string that isn't a [`TurnstileLanguage`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidLanguageTag(pub String);
+
+impl TurnstileLanguage {
+    /// Use the visitor's language.
+    pub fn auto() -> Self {
+        Self("auto".to_string())
+    }
+
+    /// The value of `data-language`.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether `tag` parses as a language.
+    ///
+    /// `const` so `#[derive(FromFormCallData)]` can check `language` at
+    /// compile time.
+    pub const fn is_valid(tag: &str) -> bool {
+        /// Whether `bytes[start..end]` are `min` to `max` ASCII letters.
+        const fn letters(bytes: &[u8], start: usize, end: usize, min: usize, max: usize) -> bool {
+            if end - start < min || end - start > max {
+                return false;
+            }
+            let mut i = start;
+            while i < end {
+                if !bytes[i].is_ascii_alphabetic() {
+                    return false;
+                }
+                i += 1;
+            }
+            true
+        }
+        let bytes = tag.as_bytes();
+        if let b"auto" = bytes {
+            return true;
+        }
+        let mut dash = 0;
+        while dash < bytes.len() && bytes[dash] != b'-' {
+            dash += 1;
+        }
+        if dash == bytes.len() {
+            return letters(bytes, 0, dash, 2, 3);
+        }
+        letters(bytes, 0, dash, 2, 3) && letters(bytes, dash + 1, bytes.len(), 2, 2)
+    }
+
+    /// A language from a `tag` that [`Self::is_valid`], for code generated by
+    /// `#[derive(FromFormCallData)]`.
+    #[doc(hidden)]
+    pub fn from_valid(tag: &str) -> Self {
+        if tag == "auto" {
+            return Self::auto();
+        }
+        let (language, region) = match tag.split_once('-') {
+            Some((language, region)) => (language, Some(region)),
+            None => (tag, None),
+        };
+        let mut normalized = language.to_ascii_lowercase();
+        if let Some(region) = region {
+            normalized.push('-');
+            normalized.push_str(&region.to_ascii_uppercase());
+        }
+        Self(normalized)
+    }
+}
+
+impl std::str::FromStr for TurnstileLanguage {
+    type Err = InvalidLanguageTag;
+
+    fn from_str(tag: &str) -> Result<Self, Self::Err> {
+        if !Self::is_valid(tag) {
+            return Err(InvalidLanguageTag(tag.to_string()));
+        }
+        Ok(Self::from_valid(tag))
+    }
+}
+
+impl TryFrom<String> for TurnstileLanguage {
+    type Error = InvalidLanguageTag;
+
+    fn try_from(tag: String) -> Result<Self, Self::Error> {
+        tag.parse()
+    }
+}
+
+impl From<TurnstileLanguage> for String {
+    fn from(language: TurnstileLanguage) -> Self {
+        language.0
+    }
+}
+
+impl std::fmt::Display for TurnstileLanguage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::fmt::Display for InvalidLanguageTag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}` is not a Turnstile language", self.0)
+    }
+}
+
+impl std::error::Error for InvalidLanguageTag {}
+
 /// Tabs of multiple optional inputs
-#[derive(Debug, Deserialize, Serialize)]
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct FormInputTabs {
     /// Labeled tabs
+    #[serde_as(as = "LenientVec")]
+    #[serde(default)]
     pub tabs: Vec<FormInputTab>,
     /// Start with the tab of this index shown.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub initial_index: Option<usize>,
 }
 
 /// Labeled form tab
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct FormInputTab {
     /// Tab label
     pub title: String,
@@ -237,10 +1179,377 @@ pub struct FormInputTab {
 }
 
 /// A list of motions.
-#[derive(Debug, Deserialize, Serialize)]
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct FormInputMotions {
     /// Show vertically instead of horizontally.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub vertical_list: Option<bool>,
     /// The list of motions to show.
+    #[serde_as(as = "LenientVec")]
+    #[serde(default)]
     pub motions: Vec<VisualMotion>,
 }
+
+/// Pick one option from a dropdown.
+///
+/// Use instead of [`FormInputRadio`] for long lists.
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FormInputSelect {
+    /// Human-readable name.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub title: Option<String>,
+    /// What to put in form-data for the API.
+    pub name: String,
+    /// `value` of the option to start with.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub initial_value: Option<String>,
+    /// Shown before anything is picked.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub placeholder: Option<String>,
+    /// Individual selectable options.
+    #[serde_as(as = "LenientVec")]
+    #[serde(default)]
+    pub options: Vec<SelectOption>,
+    /// Something must be picked.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// An individual dropdown option.
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SelectOption {
+    /// What to put in form-data for the API.
+    pub value: String,
+    /// Human-readable text for option.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub title: Option<String>,
+}
+
+/// Type a number.
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FormInputNumber {
+    /// Human-readable name.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub title: Option<String>,
+    /// What to put in form-data for the API.
+    pub name: String,
+    /// Value to start with.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub initial_value: Option<f64>,
+    /// Smallest allowed value.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub min: Option<f64>,
+    /// Largest allowed value.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub max: Option<f64>,
+    /// Allowed values are `min` plus multiples of this, or multiples of this
+    /// if there's no `min`.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub step: Option<f64>,
+    /// A value must be given.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// Pick a date, a time, or both.
+///
+/// Values are ISO 8601, as HTML date inputs use: `2024-07-26`, `19:30`, or
+/// `2024-07-26T19:30`.
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FormInputDateTime {
+    /// Human-readable name.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub title: Option<String>,
+    /// What to put in form-data for the API.
+    pub name: String,
+    /// Which parts to pick.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub kind: DateTimeKind,
+    /// Value to start with.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub initial_value: Option<String>,
+    /// Earliest allowed value.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub min: Option<String>,
+    /// Latest allowed value.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub max: Option<String>,
+    /// A value must be given.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// Which parts of a date and time to pick.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub enum DateTimeKind {
+    /// `YYYY-MM-DD`
+    Date,
+    /// `HH:MM`
+    Time,
+    /// `YYYY-MM-DDTHH:MM`
+    DateTime,
+    /// Unknown kind. Treated as [`DateTimeKind::DateTime`].
+    #[default]
+    #[serde(other)]
+    Unknown,
+}
+
+impl DateTimeKind {
+    /// Whether `value` is in this kind's format.
+    ///
+    /// Values in the same format compare correctly as strings.
+    pub fn is_valid(self, value: &str) -> bool {
+        fn digits(value: &str, len: usize, max: u32) -> bool {
+            value.len() == len
+                && value.bytes().all(|b| b.is_ascii_digit())
+                && value.parse::<u32>().is_ok_and(|n| n <= max)
+        }
+        fn date(value: &str) -> bool {
+            let mut parts = value.split('-');
+            let (Some(year), Some(month), Some(day), None) =
+                (parts.next(), parts.next(), parts.next(), parts.next())
+            else {
+                return false;
+            };
+            digits(year, 4, 9999)
+                && digits(month, 2, 12)
+                && digits(day, 2, 31)
+                && month != "00"
+                && day != "00"
+        }
+        fn time(value: &str) -> bool {
+            let mut parts = value.split(':');
+            let (Some(hour), Some(minute), None) = (parts.next(), parts.next(), parts.next())
+            else {
+                return false;
+            };
+            digits(hour, 2, 23) && digits(minute, 2, 59)
+        }
+        match self {
+            Self::Date => date(value),
+            Self::Time => time(value),
+            Self::DateTime | Self::Unknown => value
+                .split_once('T')
+                .is_some_and(|(d, t)| date(d) && time(t)),
+        }
+    }
+}
+
+/// Type an email address.
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FormInputEmail {
+    /// Human-readable name.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub title: Option<String>,
+    /// What to put in form-data for the API.
+    pub name: String,
+    /// Value to start with.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub initial_value: Option<String>,
+    /// A value must be given.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// Type a web address.
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FormInputUrl {
+    /// Human-readable name.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub title: Option<String>,
+    /// What to put in form-data for the API.
+    pub name: String,
+    /// Value to start with.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub initial_value: Option<String>,
+    /// A value must be given.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// Type a password.
+///
+/// Passwords are never sent back in [`FormResponse::fields`].
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FormInputPassword {
+    /// Human-readable name.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub title: Option<String>,
+    /// What to put in form-data for the API.
+    pub name: String,
+    /// Minimum character count.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub length_min: Option<i32>,
+    /// Maximum character count.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub length_max: Option<i32>,
+    /// If set, ask for the password twice and put the second in form-data
+    /// under this name.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub confirm_name: Option<String>,
+    /// Human-readable name of the confirmation field.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub confirm_title: Option<String>,
+    /// Value of the `autocomplete` attribute, such as `new-password`.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub autocomplete: Option<String>,
+}
+
+/// Pick any number of options, or type tags.
+///
+/// Submitted as a [`FormValue::List`].
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FormInputMultiSelect {
+    /// Human-readable name.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub title: Option<String>,
+    /// What to put in form-data for the API.
+    pub name: String,
+    /// How to show the options.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub style: MultiSelectStyle,
+    /// Individual selectable options.
+    #[serde_as(as = "LenientVec")]
+    #[serde(default)]
+    pub options: Vec<SelectOption>,
+    /// `value`s of the options to start with picked.
+    #[serde_as(as = "LenientVec")]
+    #[serde(default)]
+    pub initial_values: Vec<String>,
+    /// Fewest options to pick.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub min_selected: Option<i32>,
+    /// Most options to pick.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub max_selected: Option<i32>,
+    /// Let the user type tags that aren't among the options.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub allow_custom: bool,
+    /// Filter out certain letters in typed tags.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub custom_filter: Option<TextFilter>,
+    /// Maximum character count of a typed tag.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub custom_length_max: Option<i32>,
+}
+
+/// How to show a [`FormInputMultiSelect`].
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub enum MultiSelectStyle {
+    /// A checkbox per option.
+    Checkboxes,
+    /// Removable chips, with a field to add more.
+    Chips,
+    /// Unknown style. Treated as [`MultiSelectStyle::Checkboxes`].
+    #[default]
+    #[serde(other)]
+    Unknown,
+}
+
+/// Inputs shown or required depending on what's entered in other inputs.
+///
+/// Evaluate with [`FormInputConditional::is_shown`] and
+/// [`FormInputConditional::is_required`]. Hidden inputs are skipped by
+/// validation, and read as not submitted.
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FormInputConditional {
+    /// Only show the inputs when this holds. Always shown if unset.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub show_when: Option<FormCondition>,
+    /// The inputs must be filled in when this holds.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub require_when: Option<FormCondition>,
+    /// Inputs this applies to.
+    #[serde_as(as = "LenientVec")]
+    #[serde(default)]
+    pub inputs: Vec<FormInput>,
+}
+
+/// Test on the values entered in a form.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub enum FormCondition {
+    /// The input has this value, or this option selected.
+    Equals(Arc<FormConditionValue>),
+    /// The multi-select has this option, or the text has this substring.
+    Contains(Arc<FormConditionValue>),
+    /// The checkbox is checked.
+    Checked(Arc<FormConditionInput>),
+    /// The input has a non-empty value.
+    Filled(Arc<FormConditionInput>),
+    /// Every condition holds.
+    All(Vec<FormCondition>),
+    /// At least one condition holds.
+    Any(Vec<FormCondition>),
+    /// The condition doesn't hold.
+    Not(Arc<FormCondition>),
+    /// Unknown condition. Never holds, but inputs it would hide are shown, so
+    /// newer forms show everything.
+    #[default]
+    #[serde(other)]
+    Unknown,
+}
+
+/// Which input a condition looks at.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FormConditionInput {
+    /// `name` of the input.
+    pub name: String,
+}
+
+/// Which input a condition looks at, and the value to compare.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FormConditionValue {
+    /// `name` of the input.
+    pub name: String,
+    /// Value, option `value`, or substring.
+    pub value: String,
+}
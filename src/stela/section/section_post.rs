@@ -1,28 +1,53 @@
 use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
 
-use crate::stela::{Image, Motion, VisualMotion};
+use crate::stela::{
+    lenient::{Lenient, LenientVec},
+    Image, Motion, VisualMotion,
+};
 
 /// User-generated content.
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 pub struct SectionPost {
     /// Header text.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub title: Option<String>,
     /// Main post image.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub image: Option<Image>,
     /// Blurred background image.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub background: Option<Image>,
     /// Show a pin icon.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub is_pinned: Option<bool>,
     /// Post content in HTML form.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub body_html: Option<String>,
     /// What to do when post is clicked.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub motion: Option<Motion>,
     /// Top-left motions.
+    #[serde_as(as = "LenientVec")]
+    #[serde(default)]
     pub motions_tl: Vec<VisualMotion>,
     /// Top-right motions.
+    #[serde_as(as = "LenientVec")]
+    #[serde(default)]
     pub motions_tr: Vec<VisualMotion>,
     /// Bottom-right motions.
+    #[serde_as(as = "LenientVec")]
+    #[serde(default)]
     pub motions_br: Vec<VisualMotion>,
     /// Bottom-left motions.
+    #[serde_as(as = "LenientVec")]
+    #[serde(default)]
     pub motions_bl: Vec<VisualMotion>,
 }
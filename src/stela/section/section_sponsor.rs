@@ -1,8 +1,10 @@
 use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
 
-use crate::stela::Motion;
+use crate::stela::{lenient::LenientVec, Motion};
 
 /// Ads. Gotta make money.
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 pub struct SectionSponsor {
     /// The word "Sponsor".
@@ -12,5 +14,7 @@ pub struct SectionSponsor {
     /// Primary text.
     pub text: String,
     /// Call-to-action
+    #[serde_as(as = "LenientVec")]
+    #[serde(default)]
     pub motions: Vec<Motion>,
 }
@@ -1,30 +1,50 @@
 use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
 
-use crate::stela::{Image, Motion};
+use crate::stela::{
+    lenient::{Lenient, LenientVec},
+    Image, Motion,
+};
 
 /// List of clickable tiles.
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 pub struct SectionTiles {
     /// The list of tiles.
+    #[serde_as(as = "LenientVec")]
+    #[serde(default)]
     pub tiles: Vec<Tile>,
     /// How to layout the tiles.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub layout: TilesLayout,
 }
 
 /// Individual clickable tile.
 ///
 /// See [`SectionTiles`].
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Tile {
     /// Primary text.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub header: Option<String>,
     /// Secondary text.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub subheader: Option<String>,
     /// What to do when clicked.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub motion: Option<Motion>,
     /// Both thumbnail and background.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub image: Option<Image>,
     /// Show text instead of thumbnail.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub body_text: Option<String>,
 }
 
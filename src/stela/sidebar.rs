@@ -1,15 +1,20 @@
 use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
 
-use crate::stela::VisualMotion;
+use crate::stela::{lenient::LenientVec, VisualMotion};
 
 /// Mini sections on the side of the screen.
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Sidebar {
     /// The sections to show.
+    #[serde_as(as = "LenientVec")]
+    #[serde(default)]
     pub cards: Vec<SidebarCard>,
 }
 
 /// An individual sidebar section.
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 pub struct SidebarCard {
     /// Headr text at the top.
@@ -17,5 +22,7 @@ pub struct SidebarCard {
     /// Main text.
     pub body: String,
     /// Buttons underneath body.
+    #[serde_as(as = "LenientVec")]
+    #[serde(default)]
     pub motions: Vec<VisualMotion>,
 }
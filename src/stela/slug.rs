@@ -0,0 +1,245 @@
+//! URL-safe slugs for section anchors, URLs, and filtered text inputs.
+//!
+//! Esperanto letters are transliterated with the x-system (`ĉ` → `cx`), the
+//! h-system (`ĉ` → `ch`, `ŭ` → `u`), or plain ASCII folding (`ĉ` → `c`). Other
+//! Latin diacritics are always folded to ASCII, and anything else becomes a
+//! separator.
+
+use std::{collections::HashSet, fmt};
+
+use crate::stela::TextFilter;
+
+/// How to write letters that aren't ASCII.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Transliteration {
+    /// `ĉ` → `cx`, `ŭ` → `ux`. Lossless for Esperanto.
+    #[default]
+    XSystem,
+    /// `ĉ` → `ch`, `ŭ` → `u`, as in the Fundamento.
+    HSystem,
+    /// Drop every diacritic: `ĉ` → `c`.
+    Ascii,
+}
+
+/// Why a slug couldn't be made.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SlugError {
+    /// The text has no letters or digits to build a slug from.
+    Empty,
+    /// The slug contains a character the [`TextFilter`] doesn't allow.
+    Disallowed {
+        /// The slug that was made.
+        slug: String,
+        /// First character the filter rejected.
+        character: char,
+    },
+}
+
+impl fmt::Display for SlugError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "text has no letters or digits"),
+            Self::Disallowed { slug, character } => {
+                write!(
+                    f,
+                    "slug `{slug}` contains disallowed character `{character}`"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for SlugError {}
+
+/// Settings for making slugs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Slugger {
+    /// How to write Esperanto letters.
+    pub transliteration: Transliteration,
+    /// Put between words. Never doubled, leading, or trailing.
+    pub separator: char,
+    /// Maximum length in bytes, including any collision suffix.
+    pub max_len: Option<usize>,
+    /// Reject slugs with characters outside this filter.
+    pub filter: Option<TextFilter>,
+}
+
+impl Default for Slugger {
+    fn default() -> Self {
+        Self {
+            transliteration: Transliteration::default(),
+            separator: '-',
+            max_len: None,
+            filter: None,
+        }
+    }
+}
+
+impl Slugger {
+    /// Make a lowercase slug from `text`.
+    pub fn slug(&self, text: &str) -> Result<String, SlugError> {
+        let mut slug = String::with_capacity(text.len());
+        let mut pending_separator = false;
+        for c in text.chars().flat_map(char::to_lowercase) {
+            if matches!(c, '\'' | '’') {
+                continue;
+            }
+            let mut buf = [0; 4];
+            let letters = match self.transliterate(c) {
+                Some(letters) => letters,
+                None if c.is_ascii_alphanumeric() => &*c.encode_utf8(&mut buf),
+                None => {
+                    pending_separator = !slug.is_empty();
+                    continue;
+                }
+            };
+            if pending_separator {
+                slug.push(self.separator);
+                pending_separator = false;
+            }
+            slug.push_str(letters);
+        }
+        if slug.is_empty() {
+            return Err(SlugError::Empty);
+        }
+        if let Some(max_len) = self.max_len {
+            self.truncate(&mut slug, max_len);
+        }
+        self.check(slug)
+    }
+
+    /// Make a slug from `text` that isn't in `taken`, then add it to `taken`.
+    pub fn unique_slug(
+        &self,
+        text: &str,
+        taken: &mut HashSet<String>,
+    ) -> Result<String, SlugError> {
+        let slug = self.dedupe(&self.slug(text)?, taken);
+        self.check(slug).inspect_err(|error| {
+            if let SlugError::Disallowed { slug, .. } = error {
+                taken.remove(slug);
+            }
+        })
+    }
+
+    /// Add a numeric suffix to `slug`, such as `-2`, until it isn't in `taken`,
+    /// then add it to `taken`.
+    ///
+    /// The first occurrence keeps `slug` as is. Later ones are shortened to fit
+    /// [`Self::max_len`] with their suffix.
+    pub fn dedupe(&self, slug: &str, taken: &mut HashSet<String>) -> String {
+        let mut candidate = slug.to_string();
+        let mut n = 2;
+        while !taken.insert(candidate.clone()) {
+            let suffix = format!("{}{n}", self.separator);
+            let mut base = slug.to_string();
+            if let Some(max_len) = self.max_len {
+                self.truncate(&mut base, max_len.saturating_sub(suffix.len()));
+            }
+            // A suffix alone doesn't start with the separator.
+            candidate = if base.is_empty() {
+                n.to_string()
+            } else {
+                base + &suffix
+            };
+            n += 1;
+        }
+        candidate
+    }
+
+    fn check(&self, slug: String) -> Result<String, SlugError> {
+        match self
+            .filter
+            .and_then(|filter| filter.first_disallowed(&slug))
+        {
+            Some(character) => Err(SlugError::Disallowed { slug, character }),
+            None => Ok(slug),
+        }
+    }
+
+    /// Cut to at most `max_len` bytes on a char boundary, without splitting
+    /// a transliterated letter such as `cx` or leaving a trailing separator.
+    fn truncate(&self, slug: &mut String, max_len: usize) {
+        if slug.len() > max_len {
+            let mut end = max_len;
+            while !slug.is_char_boundary(end) {
+                end -= 1;
+            }
+            let (firsts, second) = match self.transliteration {
+                Transliteration::XSystem => ("cghjsu", 'x'),
+                Transliteration::HSystem => ("cghjs", 'h'),
+                Transliteration::Ascii => ("", ' '),
+            };
+            if slug[end..].starts_with(second) && slug[..end].ends_with(|c| firsts.contains(c)) {
+                end -= 1;
+            }
+            slug.truncate(end);
+        }
+        while slug.ends_with(self.separator) {
+            slug.pop();
+        }
+    }
+
+    fn transliterate(&self, c: char) -> Option<&'static str> {
+        let esperanto = match self.transliteration {
+            Transliteration::XSystem => esperanto_x(c),
+            Transliteration::HSystem => esperanto_h(c),
+            Transliteration::Ascii => None,
+        };
+        esperanto.or_else(|| fold(c))
+    }
+}
+
+fn esperanto_x(c: char) -> Option<&'static str> {
+    Some(match c {
+        'ĉ' => "cx",
+        'ĝ' => "gx",
+        'ĥ' => "hx",
+        'ĵ' => "jx",
+        'ŝ' => "sx",
+        'ŭ' => "ux",
+        _ => return None,
+    })
+}
+
+fn esperanto_h(c: char) -> Option<&'static str> {
+    Some(match c {
+        'ĉ' => "ch",
+        'ĝ' => "gh",
+        'ĥ' => "hh",
+        'ĵ' => "jh",
+        'ŝ' => "sh",
+        'ŭ' => "u",
+        _ => return None,
+    })
+}
+
+/// ASCII for lowercase Latin letters with diacritics.
+fn fold(c: char) -> Option<&'static str> {
+    Some(match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'ď' | 'đ' | 'ð' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'ĥ' | 'ħ' => "h",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'ĵ' => "j",
+        'ķ' => "k",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'œ' => "oe",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'ś' | 'ŝ' | 'ş' | 'š' => "s",
+        'ß' => "ss",
+        'ţ' | 'ť' | 'ŧ' => "t",
+        'þ' => "th",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'ŵ' => "w",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => return None,
+    })
+}
@@ -1,16 +1,146 @@
 use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::stela::{lenient::Lenient, Image};
 
 /// Website meta tags.
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 pub struct SocialData {
     /// `name`, `og:title`, `twitter:title`
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub title: Option<String>,
     /// `description`, `og:description`, `twitter:description`
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub description: Option<String>,
     /// `image`, `og:image`, `twitter:image`
-    pub image: Option<String>,
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub image: Option<SocialImage>,
+    /// `og:image:alt`, `twitter:image:alt`
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub image_alt: Option<String>,
     /// `og:url`
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
     pub url: Option<String>,
+    /// `<link rel="canonical">`
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub canonical: Option<String>,
+    /// `og:type`
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub og_type: Option<OpenGraphType>,
+    /// `og:site_name`
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub site_name: Option<String>,
+    /// `article:published_time`
+    ///
+    /// ISO 8601 date-time.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub published_time: Option<String>,
+    /// `article:modified_time`
+    ///
+    /// ISO 8601 date-time.
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub modified_time: Option<String>,
+    /// `article:author`, `author`
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub author: Option<String>,
     /// `twitter:card`
-    pub twitter_card: Option<String>,
+    #[serde_as(as = "Lenient")]
+    #[serde(default)]
+    pub twitter_card: Option<TwitterCard>,
+}
+
+/// Image of a page, for [`SocialData::image`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum SocialImage {
+    /// An image in Cloudflare Images.
+    Image(Image),
+    /// URL of an image, as older API versions sent. Use it as is, rather than
+    /// as an [`Image::id`].
+    Url(String),
+}
+
+impl From<Image> for SocialImage {
+    fn from(image: Image) -> Self {
+        Self::Image(image)
+    }
+}
+
+/// Kind of Twitter/X card to show.
+///
+/// Serialized the same way as the `twitter:card` meta tag value.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TwitterCard {
+    /// `summary`
+    Summary,
+    /// `summary_large_image`
+    SummaryLargeImage,
+    /// `player`
+    Player,
+    /// `app`
+    App,
+    /// Fallback.
+    #[default]
+    #[serde(other)]
+    Unknown,
+}
+
+impl TwitterCard {
+    /// Value for the `twitter:card` meta tag.
+    ///
+    /// `None` for [`TwitterCard::Unknown`].
+    pub fn as_str(&self) -> Option<&'static str> {
+        match self {
+            Self::Summary => Some("summary"),
+            Self::SummaryLargeImage => Some("summary_large_image"),
+            Self::Player => Some("player"),
+            Self::App => Some("app"),
+            Self::Unknown => None,
+        }
+    }
+}
+
+/// Kind of Open Graph object the page represents.
+///
+/// Serialized the same way as the `og:type` meta tag value.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OpenGraphType {
+    /// `website`
+    Website,
+    /// `article`
+    Article,
+    /// `profile`
+    Profile,
+    /// Fallback.
+    #[default]
+    #[serde(other)]
+    Unknown,
+}
+
+impl OpenGraphType {
+    /// Value for the `og:type` meta tag.
+    ///
+    /// `None` for [`OpenGraphType::Unknown`].
+    pub fn as_str(&self) -> Option<&'static str> {
+        match self {
+            Self::Website => Some("website"),
+            Self::Article => Some("article"),
+            Self::Profile => Some("profile"),
+            Self::Unknown => None,
+        }
+    }
 }
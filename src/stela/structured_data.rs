@@ -0,0 +1,405 @@
+//! schema.org JSON-LD derived from Stela pages.
+//!
+//! Each [`JsonLdDocument`] becomes the body of a
+//! `<script type="application/ld+json">` tag. Post text is plain text, so it
+//! can contain `</script>`; embed documents only with
+//! [`JsonLdDocument::to_script_json`], which escapes it.
+
+use serde::{Deserialize, Serialize};
+
+use crate::stela::{Image, Motion, Navbar, Page, Section, SectionPost, SocialData};
+
+/// Value of `@context` for every document.
+pub const SCHEMA_ORG_CONTEXT: &str = "https://schema.org";
+
+/// Site-wide info needed to build absolute URLs.
+pub struct StructuredDataConfig<'a> {
+    /// Origin of the site, without a trailing slash, such as `https://ana.so`.
+    pub site_url: &'a str,
+    /// Human-readable site name.
+    pub site_name: &'a str,
+    /// Absolute URL of the site logo.
+    pub logo_url: Option<&'a str>,
+    /// Turns an [`Image`] into an absolute URL.
+    pub image_url: &'a dyn Fn(&Image) -> String,
+}
+
+impl StructuredDataConfig<'_> {
+    /// Resolve a site-relative URI against [`Self::site_url`].
+    pub fn absolute_url(&self, uri: &str) -> String {
+        if uri.starts_with('/') {
+            format!("{}{}", self.site_url.trim_end_matches('/'), uri)
+        } else {
+            uri.to_string()
+        }
+    }
+}
+
+/// A top-level JSON-LD document.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct JsonLdDocument {
+    /// Always [`SCHEMA_ORG_CONTEXT`].
+    #[serde(rename = "@context")]
+    pub context: String,
+    /// The described thing.
+    #[serde(flatten)]
+    pub node: JsonLd,
+}
+
+impl JsonLdDocument {
+    /// The document as JSON to put in a `<script type="application/ld+json">`
+    /// tag as is, with `<`, `>` and `&` escaped so text can't close the tag.
+    ///
+    /// This is the only safe way to embed a document in HTML.
+    pub fn to_script_json(&self) -> String {
+        let json = serde_json::to_string(self).expect("JSON-LD is always serializable");
+        let mut escaped = String::with_capacity(json.len());
+        for c in json.chars() {
+            match c {
+                '<' => escaped.push_str("\\u003c"),
+                '>' => escaped.push_str("\\u003e"),
+                '&' => escaped.push_str("\\u0026"),
+                c => escaped.push(c),
+            }
+        }
+        escaped
+    }
+}
+
+impl From<JsonLd> for JsonLdDocument {
+    fn from(node: JsonLd) -> Self {
+        Self {
+            context: SCHEMA_ORG_CONTEXT.to_string(),
+            node,
+        }
+    }
+}
+
+/// A schema.org node, tagged by `@type`.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(tag = "@type")]
+pub enum JsonLd {
+    /// A user post.
+    DiscussionForumPosting(DiscussionForumPosting),
+    /// Path from the site root to the page.
+    BreadcrumbList(BreadcrumbList),
+    /// The organization running the site.
+    Organization(Organization),
+    /// The site itself.
+    WebSite(WebSite),
+}
+
+/// <https://schema.org/DiscussionForumPosting>
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscussionForumPosting {
+    /// Post title.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headline: Option<String>,
+    /// Post body as plain text.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Absolute image URL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    /// Absolute URL of the post.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Author name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<Person>,
+    /// ISO 8601 date-time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_published: Option<String>,
+    /// ISO 8601 date-time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_modified: Option<String>,
+}
+
+/// <https://schema.org/Person>
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Person {
+    /// Always `Person`.
+    #[serde(rename = "@type")]
+    pub schema_type: String,
+    /// Display name.
+    pub name: String,
+}
+
+impl Person {
+    /// A person with the given name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            schema_type: "Person".to_string(),
+            name: name.into(),
+        }
+    }
+}
+
+/// <https://schema.org/BreadcrumbList>
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BreadcrumbList {
+    /// Crumbs from the site root to the page.
+    pub item_list_element: Vec<ListItem>,
+}
+
+/// <https://schema.org/ListItem>
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct ListItem {
+    /// Always `ListItem`.
+    #[serde(rename = "@type")]
+    pub schema_type: String,
+    /// 1-based position in the list.
+    pub position: usize,
+    /// Human-readable name.
+    pub name: String,
+    /// Absolute URL.
+    pub item: String,
+}
+
+/// <https://schema.org/Organization>
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Organization {
+    /// Organization name.
+    pub name: String,
+    /// Absolute URL of the home page.
+    pub url: String,
+    /// Absolute URL of the logo.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logo: Option<String>,
+}
+
+/// <https://schema.org/WebSite>
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WebSite {
+    /// Site name.
+    pub name: String,
+    /// Absolute URL of the home page.
+    pub url: String,
+    /// Sitelinks search box.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub potential_action: Option<SearchAction>,
+}
+
+/// <https://schema.org/SearchAction>
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct SearchAction {
+    /// Always `SearchAction`.
+    #[serde(rename = "@type")]
+    pub schema_type: String,
+    /// URL template with a `{search_term_string}` placeholder.
+    pub target: String,
+    /// Always `required name=search_term_string`.
+    #[serde(rename = "query-input")]
+    pub query_input: String,
+}
+
+impl SearchAction {
+    /// Search by appending `q={search_term_string}` to `base`.
+    pub fn new(base: &str) -> Self {
+        let sep = if base.contains('?') { '&' } else { '?' };
+        Self {
+            schema_type: "SearchAction".to_string(),
+            target: format!("{base}{sep}q={{search_term_string}}"),
+            query_input: "required name=search_term_string".to_string(),
+        }
+    }
+}
+
+/// All JSON-LD documents for a page.
+///
+/// Always emits `Organization` and `WebSite`. Emits `BreadcrumbList` when the
+/// page has a URL, and one `DiscussionForumPosting` per post section.
+pub fn page_structured_data(
+    page: &Page,
+    navbar: Option<&Navbar>,
+    config: &StructuredDataConfig,
+) -> Vec<JsonLdDocument> {
+    let mut docs = vec![
+        JsonLd::Organization(organization(config)).into(),
+        JsonLd::WebSite(website(navbar, config)).into(),
+    ];
+
+    if let Some(crumbs) = breadcrumbs(page, config) {
+        docs.push(JsonLd::BreadcrumbList(crumbs).into());
+    }
+
+    let posts = page
+        .sections
+        .iter()
+        .filter_map(|visual| match &visual.section {
+            Section::Post(post) => Some((visual.title.as_deref(), post.as_ref())),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    // Page-level authorship only describes the post when it is alone on the page.
+    let social = page.social.as_deref().filter(|_| posts.len() == 1);
+    for (title, post) in posts {
+        let mut posting = discussion_forum_posting(post, social, config);
+        if posting.headline.is_none() {
+            posting.headline = title.map(str::to_string);
+        }
+        docs.push(JsonLd::DiscussionForumPosting(posting).into());
+    }
+
+    docs
+}
+
+/// `Organization` for the site.
+pub fn organization(config: &StructuredDataConfig) -> Organization {
+    Organization {
+        name: config.site_name.to_string(),
+        url: config.absolute_url("/"),
+        logo: config.logo_url.map(str::to_string),
+    }
+}
+
+/// `WebSite` for the site, with a search action if the navbar links to search.
+pub fn website(navbar: Option<&Navbar>, config: &StructuredDataConfig) -> WebSite {
+    let potential_action = navbar
+        .and_then(|navbar| navbar.search_motion.as_ref())
+        .and_then(|motion| match motion {
+            Motion::Href(href) => Some(SearchAction::new(&config.absolute_url(&href.uri))),
+            _ => None,
+        });
+    WebSite {
+        name: config.site_name.to_string(),
+        url: config.absolute_url("/"),
+        potential_action,
+    }
+}
+
+/// `BreadcrumbList` built from the path of the page's canonical URL or `og:url`.
+///
+/// The last crumb is named after the page title; the others after their path
+/// segment.
+pub fn breadcrumbs(page: &Page, config: &StructuredDataConfig) -> Option<BreadcrumbList> {
+    let social = page.social.as_deref()?;
+    let url = social.canonical.as_deref().or(social.url.as_deref())?;
+    let path = match url.find("://") {
+        Some(scheme_end) => {
+            let rest = &url[scheme_end + 3..];
+            rest.find('/').map_or("", |i| &rest[i..])
+        }
+        None => url,
+    };
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+
+    let mut item_list_element = vec![ListItem {
+        schema_type: "ListItem".to_string(),
+        position: 1,
+        name: config.site_name.to_string(),
+        item: config.absolute_url("/"),
+    }];
+    let segments = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>();
+    let mut current = String::new();
+    for (i, segment) in segments.iter().enumerate() {
+        current.push('/');
+        current.push_str(segment);
+        let is_last = i + 1 == segments.len();
+        let name = match (is_last, page.title.as_deref().or(social.title.as_deref())) {
+            (true, Some(title)) => title.to_string(),
+            _ => segment.to_string(),
+        };
+        item_list_element.push(ListItem {
+            schema_type: "ListItem".to_string(),
+            position: i + 2,
+            name,
+            item: config.absolute_url(&current),
+        });
+    }
+
+    Some(BreadcrumbList { item_list_element })
+}
+
+/// `DiscussionForumPosting` for a post.
+///
+/// `social` supplies authorship and dates; pass it only when it describes this post.
+pub fn discussion_forum_posting(
+    post: &SectionPost,
+    social: Option<&SocialData>,
+    config: &StructuredDataConfig,
+) -> DiscussionForumPosting {
+    let url = match &post.motion {
+        Some(Motion::Href(href)) => Some(config.absolute_url(&href.uri)),
+        _ => social.and_then(|s| s.canonical.as_ref().or(s.url.as_ref()).cloned()),
+    };
+    DiscussionForumPosting {
+        headline: post.title.clone(),
+        text: post.body_html.as_deref().map(strip_html),
+        image: post.image.as_ref().map(config.image_url),
+        url,
+        author: social.and_then(|s| s.author.clone()).map(Person::new),
+        date_published: social.and_then(|s| s.published_time.clone()),
+        date_modified: social.and_then(|s| s.modified_time.clone()),
+    }
+}
+
+/// Drop tags, decode character references and collapse whitespace.
+fn strip_html(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => {
+                in_tag = true;
+                text.push(' ');
+            }
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    decode_entities(&text)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Decode numeric character references and the named ones HTML escapers
+/// emit. Anything else is kept as written.
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest
+            .get(1..)
+            .and_then(|after| after.find(';').filter(|end| *end <= 10))
+            .map(|end| &rest[1..end + 1]);
+        let c = entity.and_then(|entity| match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some('\u{a0}'),
+            _ => {
+                let number = entity.strip_prefix('#')?;
+                let code = match number.strip_prefix(['x', 'X']) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                    None => number.parse().ok()?,
+                };
+                char::from_u32(code)
+            }
+        });
+        match (entity, c) {
+            (Some(entity), Some(c)) => {
+                decoded.push(c);
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
@@ -0,0 +1,579 @@
+//! Curated sample pages for exercising renderers.
+
+use std::sync::Arc;
+
+use crate::stela::*;
+
+fn image(id: &str, width: f32, height: f32, alt: &str) -> Image {
+    Image {
+        aspect: Some(width / height),
+        width: Some(width),
+        height: Some(height),
+        id: id.to_string(),
+        alt: Some(alt.to_string()),
+        focal_point: None,
+        crop: None,
+    }
+}
+
+fn href(uri: &str) -> Motion {
+    Motion::Href(Arc::new(MotionHref {
+        uri: uri.to_string(),
+        new_tab: None,
+    }))
+}
+
+fn api_call(data: &str) -> Motion {
+    Motion::ApiCall(Arc::new(MotionApiCall {
+        data: data.to_string(),
+    }))
+}
+
+fn button(title: &str, icon: MotionIcon, motion: Motion) -> VisualMotion {
+    VisualMotion {
+        title: Some(title.to_string()),
+        aria_label: None,
+        icon: Some(icon),
+        image: None,
+        initial_toggle: None,
+        variant: MotionVariant::LinkHoverButton,
+        color: MotionColor::Text,
+        motion,
+    }
+}
+
+fn toggle(title: &str, icon: MotionIcon, data: &str, on: bool) -> VisualMotion {
+    VisualMotion {
+        initial_toggle: Some(on),
+        ..button(title, icon, api_call(data))
+    }
+}
+
+fn post(id: u32, title: &str, body: &str, pinned: bool) -> VisualSection {
+    VisualSection {
+        id: Some(format!("post-{id}")),
+        title: None,
+        bordered: Some(true),
+        section: Section::Post(Arc::new(SectionPost {
+            title: Some(title.to_string()),
+            image: id.is_multiple_of(2).then(|| {
+                image(
+                    &format!("post-image-{id}"),
+                    1200.0,
+                    800.0,
+                    &format!("Ilustraĵo: {title}"),
+                )
+            }),
+            background: None,
+            is_pinned: Some(pinned),
+            body_html: Some(format!("<p>{body}</p>")),
+            motion: Some(href(&format!("/p/{id}"))),
+            motions_tl: vec![],
+            motions_tr: vec![button(
+                "Raporti",
+                MotionIcon::Flag,
+                api_call(&format!("report:{id}")),
+            )],
+            motions_br: vec![button(
+                "Kunhavigi",
+                MotionIcon::ShareFat,
+                Motion::Share(Arc::new(MotionShare {
+                    title: Some(title.to_string()),
+                    text: None,
+                    url: Some(format!("https://ana.so/p/{id}")),
+                })),
+            )],
+            motions_bl: vec![
+                toggle(
+                    "12",
+                    MotionIcon::Heart,
+                    &format!("like:{id}"),
+                    id.is_multiple_of(3),
+                ),
+                button(
+                    "3",
+                    MotionIcon::ChatCircle,
+                    href(&format!("/p/{id}#komentoj")),
+                ),
+            ],
+        })),
+    }
+}
+
+/// The site navbar.
+pub fn navbar() -> Navbar {
+    Navbar {
+        left_side_motion: href("/"),
+        left_side_icon_image: image("anaso-logo", 512.0, 512.0, "Anaso"),
+        left_side_secondary_text: Some("Anaso".to_string()),
+        search_motion: Some(href("/serci")),
+        search_text: Some("Serĉi…".to_string()),
+        right_side_motions: vec![
+            button("Nova afiŝo", MotionIcon::Plus, href("/nova")),
+            button("Sciigoj", MotionIcon::Bell, href("/sciigoj")),
+        ],
+        side_motions: vec![
+            button("Hejmo", MotionIcon::House, href("/")),
+            button("Komunumoj", MotionIcon::GlobeHemisphereWest, href("/k")),
+        ],
+    }
+}
+
+/// A community feed: a pinned post, regular posts, a sponsor and a sidebar.
+pub fn feed_page() -> Page {
+    Page {
+        title: Some("Esperanto".to_string()),
+        lang: Some("eo".to_string()),
+        social: Some(Arc::new(SocialData {
+            title: Some("Esperanto — Anaso".to_string()),
+            description: Some("Komunumo por esperantistoj.".to_string()),
+            image: Some(image("community-banner", 1600.0, 400.0, "Verda stelo").into()),
+            image_alt: Some("Verda stelo".to_string()),
+            url: Some("https://ana.so/k/esperanto".to_string()),
+            canonical: Some("https://ana.so/k/esperanto".to_string()),
+            og_type: Some(OpenGraphType::Website),
+            site_name: Some("Anaso".to_string()),
+            published_time: None,
+            modified_time: None,
+            author: None,
+            twitter_card: Some(TwitterCard::SummaryLargeImage),
+        })),
+        layout: Some(PageLayout::List),
+        hero: Some(Arc::new(Hero {
+            primary_image_light: Some(image("community-icon", 256.0, 256.0, "Esperanto")),
+            primary_image_dark: None,
+            primary_image_fallback_text: Some("E".to_string()),
+            background_image_light: Some(Image {
+                focal_point: Some(FocalPoint { x: 0.7, y: 0.4 }),
+                ..image("community-banner", 1600.0, 400.0, "")
+            }),
+            background_image_dark: None,
+            title: Some("Esperanto".to_string()),
+            description: Some("Komunumo por esperantistoj.".to_string()),
+            motions: vec![toggle("Aliĝi", MotionIcon::Plus, "join:esperanto", false)],
+        })),
+        sidebar: Some(Arc::new(Sidebar {
+            cards: vec![SidebarCard {
+                title: "Pri".to_string(),
+                body: "Ĉi tie ni parolas Esperante.".to_string(),
+                motions: vec![button(
+                    "Reguloj",
+                    MotionIcon::Shield,
+                    href("/k/esperanto/reguloj"),
+                )],
+            }],
+        })),
+        sections: vec![
+            post(1, "Bonvenon!", "Legu la regulojn antaŭ afiŝi.", true),
+            post(2, "Ĉu iu iros al UK?", "Mi serĉas ĉambrokunulon.", false),
+            VisualSection {
+                id: None,
+                title: None,
+                bordered: Some(false),
+                section: Section::Sponsor(Arc::new(SectionSponsor {
+                    sponsor_text: "Sponsorita".to_string(),
+                    name: "Lernu".to_string(),
+                    text: "Lernu Esperanton senpage.".to_string(),
+                    motions: vec![Motion::Href(Arc::new(MotionHref {
+                        uri: "https://lernu.net".to_string(),
+                        new_tab: Some(true),
+                    }))],
+                })),
+            },
+            post(3, "Nova vortaro", "Jen ligilo al nova vortaro.", false),
+        ],
+    }
+}
+
+/// A user profile split into tabs: posts, communities, and settings.
+pub fn profile_page() -> Page {
+    Page {
+        title: Some("@zamenhof".to_string()),
+        lang: Some("eo".to_string()),
+        social: Some(Arc::new(SocialData {
+            title: Some("@zamenhof".to_string()),
+            description: None,
+            image: Some(image("avatar-zamenhof", 400.0, 400.0, "Profilbildo de @zamenhof").into()),
+            image_alt: Some("Profilbildo de @zamenhof".to_string()),
+            url: Some("https://ana.so/u/zamenhof".to_string()),
+            canonical: None,
+            og_type: Some(OpenGraphType::Profile),
+            site_name: Some("Anaso".to_string()),
+            published_time: None,
+            modified_time: None,
+            author: Some("zamenhof".to_string()),
+            twitter_card: Some(TwitterCard::Summary),
+        })),
+        layout: Some(PageLayout::Tabbed),
+        hero: Some(Arc::new(Hero {
+            primary_image_light: Some(image(
+                "avatar-zamenhof",
+                400.0,
+                400.0,
+                "Profilbildo de @zamenhof",
+            )),
+            primary_image_dark: None,
+            primary_image_fallback_text: Some("Z".to_string()),
+            background_image_light: None,
+            background_image_dark: None,
+            title: Some("@zamenhof".to_string()),
+            description: Some("Iniciatinto".to_string()),
+            motions: vec![toggle(
+                "Sekvi",
+                MotionIcon::UserCircle,
+                "follow:zamenhof",
+                true,
+            )],
+        })),
+        sidebar: None,
+        sections: vec![
+            VisualSection {
+                id: None,
+                title: Some("Afiŝoj".to_string()),
+                bordered: None,
+                section: Section::Tiles(Arc::new(SectionTiles {
+                    tiles: (1..=3)
+                        .map(|id| Tile {
+                            header: Some(format!("Afiŝo {id}")),
+                            subheader: Some("antaŭ 2 tagoj".to_string()),
+                            motion: Some(href(&format!("/p/{id}"))),
+                            image: (id != 2)
+                                .then(|| image(&format!("post-image-{id}"), 1200.0, 800.0, "")),
+                            body_text: (id == 2).then(|| "Nur teksto.".to_string()),
+                        })
+                        .collect(),
+                    layout: TilesLayout::VerticalList,
+                })),
+            },
+            VisualSection {
+                id: None,
+                title: Some("Komunumoj".to_string()),
+                bordered: None,
+                section: Section::Tiles(Arc::new(SectionTiles {
+                    tiles: vec![Tile {
+                        header: Some("Esperanto".to_string()),
+                        subheader: None,
+                        motion: Some(href("/k/esperanto")),
+                        image: Some(image("community-icon", 256.0, 256.0, "")),
+                        body_text: None,
+                    }],
+                    layout: TilesLayout::Grid,
+                })),
+            },
+            VisualSection {
+                id: None,
+                title: Some("Agordoj".to_string()),
+                bordered: Some(true),
+                section: Section::Form(Arc::new(nested_form())),
+            },
+        ],
+    }
+}
+
+/// A settings form with tabs, a subsection, a radio group and Turnstile.
+pub fn nested_form() -> SectionForm {
+    let username_filter =
+        TextFilter::ALPHA_ASCII | TextFilter::NUMERIC | TextFilter::DASH | TextFilter::UNDERSCORE;
+    SectionForm {
+        header: Some("Agordoj".to_string()),
+        subheader: Some("Ŝanĝu vian profilon.".to_string()),
+        form_name: "profile_settings".to_string(),
+        extra_data: Some("user:zamenhof".to_string()),
+        noscript_text: Some("Ĉi tiu formularo bezonas JavaScript.".to_string()),
+        inputs: vec![
+            FormInput::Text(Arc::new(FormInputText {
+                title: Some("Uzantnomo".to_string()),
+                name: Some("username".to_string()),
+                initial_value: Some("zamenhof".to_string()),
+                length_min: Some(3),
+                length_max: Some(32),
+                esperanto: false,
+                filter: Some(username_filter),
+            })),
+            FormInput::Tabs(Arc::new(FormInputTabs {
+                tabs: vec![
+                    FormInputTab {
+                        title: "Alŝuti".to_string(),
+                        input: FormInput::Image(Arc::new(FormInputImage {
+                            title: Some("Profilbildo".to_string()),
+                            name: Some("avatar".to_string()),
+                            initial_image: Some(image(
+                                "avatar-zamenhof",
+                                400.0,
+                                400.0,
+                                "Profilbildo de @zamenhof",
+                            )),
+                            preview_style: ImagePreviewStyle::ThumbnailCircle,
+                            constraints: ImageConstraints {
+                                mime_types: vec![
+                                    "image/png".to_string(),
+                                    "image/jpeg".to_string(),
+                                    "image/webp".to_string(),
+                                ],
+                                max_bytes: Some(5_000_000),
+                                min_width: Some(128),
+                                min_height: Some(128),
+                                max_width: None,
+                                max_height: None,
+                                aspect_min: Some(1.0),
+                                aspect_max: Some(1.0),
+                            },
+                            crop_aspect: None,
+                        })),
+                    },
+                    FormInputTab {
+                        title: "Ligilo".to_string(),
+                        input: FormInput::Text(Arc::new(FormInputText {
+                            title: Some("Bildligilo".to_string()),
+                            name: Some("avatar_url".to_string()),
+                            initial_value: None,
+                            length_min: None,
+                            length_max: Some(2048),
+                            esperanto: false,
+                            filter: None,
+                        })),
+                    },
+                ],
+                initial_index: Some(0),
+            })),
+            FormInput::Subsection(Arc::new(FormInputSubsection {
+                title: Some("Pri vi".to_string()),
+                inputs: vec![
+                    FormInput::Markdown(Arc::new(FormInputMarkdown {
+                        title: Some("Biografio".to_string()),
+                        name: "bio".to_string(),
+                        initial_value: Some("Mi kreis lingvon.".to_string()),
+                        length_min: None,
+                        length_max: Some(4000),
+                    })),
+                    FormInput::Radio(Arc::new(FormInputRadio {
+                        title: Some("Nivelo".to_string()),
+                        name: "level".to_string(),
+                        initial_index: Some(2),
+                        options: ["komencanto", "progresanto", "flua"]
+                            .into_iter()
+                            .map(|value| RadioButton {
+                                value: value.to_string(),
+                                title: Some(value.to_string()),
+                            })
+                            .collect(),
+                    })),
+                    FormInput::Checkbox(Arc::new(FormInputCheckbox {
+                        title: Some("Montri retpoŝtadreson".to_string()),
+                        name: "show_email".to_string(),
+                        default_checked: Some(false),
+                    })),
+                ],
+            })),
+            FormInput::CfTurnstile(Arc::new(FormInputCfTurnstile {
+                class: None,
+                sitekey: "1x00000000000000000000AA".to_string(),
+                response_field_name: Some("cf_turnstile".to_string()),
+                size: Some(TurnstileSize::Flexible),
+                language: Some("eo".parse().unwrap()),
+                theme: Some(TurnstileTheme::Auto),
+                appearance: Some(TurnstileAppearance::InteractionOnly),
+                execution: None,
+                retry: None,
+                action: Some("profile_settings".to_string()),
+                cdata: None,
+            })),
+            FormInput::Motions(Arc::new(FormInputMotions {
+                vertical_list: Some(false),
+                motions: vec![VisualMotion {
+                    variant: MotionVariant::Button,
+                    color: MotionColor::Primary,
+                    ..button(
+                        "Konservi",
+                        MotionIcon::Unknown,
+                        Motion::Submit(Arc::new(MotionSubmit {})),
+                    )
+                }],
+            })),
+        ],
+        steps: Vec::new(),
+        initial_step: None,
+        step_progress: StepProgress::Unknown,
+        autosave: None,
+        etag: Some("\"user-zamenhof-v7\"".to_string()),
+    }
+}
+
+/// A sign-up form using the select, multi-select, number, date, email, URL
+/// and password inputs.
+pub fn signup_form() -> SectionForm {
+    SectionForm {
+        header: Some("Aliĝi".to_string()),
+        subheader: None,
+        form_name: "signup".to_string(),
+        extra_data: None,
+        noscript_text: None,
+        inputs: vec![
+            FormInput::Email(Arc::new(FormInputEmail {
+                title: Some("Retpoŝtadreso".to_string()),
+                name: "email".to_string(),
+                initial_value: None,
+                required: true,
+            })),
+            FormInput::Password(Arc::new(FormInputPassword {
+                title: Some("Pasvorto".to_string()),
+                name: "password".to_string(),
+                length_min: Some(8),
+                length_max: Some(128),
+                confirm_name: Some("password_confirm".to_string()),
+                confirm_title: Some("Ripetu la pasvorton".to_string()),
+                autocomplete: Some("new-password".to_string()),
+            })),
+            FormInput::Select(Arc::new(FormInputSelect {
+                title: Some("Lando".to_string()),
+                name: "country".to_string(),
+                initial_value: Some("eo".to_string()),
+                placeholder: Some("Elektu landon".to_string()),
+                options: [("eo", "Esperantujo"), ("fr", "Francujo"), ("br", "Brazilo")]
+                    .into_iter()
+                    .map(|(value, title)| SelectOption {
+                        value: value.to_string(),
+                        title: Some(title.to_string()),
+                    })
+                    .collect(),
+                required: true,
+            })),
+            FormInput::Number(Arc::new(FormInputNumber {
+                title: Some("Jaroj da lernado".to_string()),
+                name: "years".to_string(),
+                initial_value: None,
+                min: Some(0.0),
+                max: Some(100.0),
+                step: Some(0.5),
+                required: false,
+            })),
+            FormInput::DateTime(Arc::new(FormInputDateTime {
+                title: Some("Naskiĝdato".to_string()),
+                name: "birthday".to_string(),
+                kind: DateTimeKind::Date,
+                initial_value: None,
+                min: Some("1900-01-01".to_string()),
+                max: Some("2020-12-31".to_string()),
+                required: false,
+            })),
+            FormInput::Url(Arc::new(FormInputUrl {
+                title: Some("Retejo".to_string()),
+                name: "website".to_string(),
+                initial_value: None,
+                required: false,
+            })),
+            FormInput::MultiSelect(Arc::new(FormInputMultiSelect {
+                title: Some("Interesoj".to_string()),
+                name: "interests".to_string(),
+                style: MultiSelectStyle::Chips,
+                options: [("literaturo", "Literaturo"), ("muziko", "Muziko")]
+                    .into_iter()
+                    .map(|(value, title)| SelectOption {
+                        value: value.to_string(),
+                        title: Some(title.to_string()),
+                    })
+                    .collect(),
+                initial_values: vec!["literaturo".to_string()],
+                min_selected: None,
+                max_selected: Some(3),
+                allow_custom: true,
+                custom_filter: Some(TextFilter::ALPHA_EO | TextFilter::DASH),
+                custom_length_max: Some(24),
+            })),
+            FormInput::Motions(Arc::new(FormInputMotions {
+                vertical_list: None,
+                motions: vec![VisualMotion {
+                    variant: MotionVariant::Button,
+                    color: MotionColor::Primary,
+                    ..button(
+                        "Aliĝi",
+                        MotionIcon::Unknown,
+                        Motion::Submit(Arc::new(MotionSubmit {})),
+                    )
+                }],
+            })),
+        ],
+        steps: Vec::new(),
+        initial_step: None,
+        step_progress: StepProgress::Unknown,
+        autosave: None,
+        etag: None,
+    }
+}
+
+/// A community creation form in three steps, with back and next buttons
+/// shown on every step, saved as a draft for a week.
+pub fn community_wizard() -> SectionForm {
+    let text = |title: &str, name: &str, min: i32, max: i32| {
+        FormInput::Text(Arc::new(FormInputText {
+            title: Some(title.to_string()),
+            name: Some(name.to_string()),
+            initial_value: None,
+            length_min: Some(min),
+            length_max: Some(max),
+            esperanto: false,
+            filter: None,
+        }))
+    };
+    let step_button = |title: &str, color: MotionColor, motion: Motion| VisualMotion {
+        variant: MotionVariant::Button,
+        color,
+        ..button(title, MotionIcon::Unknown, motion)
+    };
+    SectionForm {
+        header: Some("Krei komunumon".to_string()),
+        subheader: None,
+        form_name: "create_community".to_string(),
+        extra_data: None,
+        noscript_text: None,
+        inputs: vec![FormInput::Motions(Arc::new(FormInputMotions {
+            vertical_list: Some(false),
+            motions: vec![
+                step_button(
+                    "Reen",
+                    MotionColor::Text,
+                    Motion::PreviousStep(Arc::new(MotionPreviousStep {})),
+                ),
+                step_button(
+                    "Pluen",
+                    MotionColor::Primary,
+                    Motion::Submit(Arc::new(MotionSubmit {})),
+                ),
+            ],
+        }))],
+        steps: vec![
+            FormStep {
+                title: "Nomo".to_string(),
+                subtitle: Some("Kiel oni nomu vian komunumon?".to_string()),
+                inputs: vec![text("Nomo", "name", 3, 48), text("Adreso", "slug", 3, 32)],
+            },
+            FormStep {
+                title: "Priskribo".to_string(),
+                subtitle: None,
+                inputs: vec![FormInput::Markdown(Arc::new(FormInputMarkdown {
+                    title: Some("Priskribo".to_string()),
+                    name: "description".to_string(),
+                    initial_value: None,
+                    length_min: Some(1),
+                    length_max: Some(2000),
+                }))],
+            },
+            FormStep {
+                title: "Reguloj".to_string(),
+                subtitle: None,
+                inputs: vec![FormInput::Checkbox(Arc::new(FormInputCheckbox {
+                    title: Some("Publika".to_string()),
+                    name: "public".to_string(),
+                    default_checked: Some(true),
+                }))],
+            },
+        ],
+        initial_step: Some(0),
+        step_progress: StepProgress::Titles,
+        autosave: Some(FormAutosave {
+            version: 1,
+            max_age: Some(7 * 24 * 60 * 60),
+        }),
+        etag: None,
+    }
+}
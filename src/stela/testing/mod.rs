@@ -0,0 +1,6 @@
+//! Sample data and random generators for testing clients and servers.
+//!
+//! Only available with the `testing` feature.
+
+pub mod fixtures;
+pub mod strategies;
@@ -0,0 +1,1463 @@
+//! `proptest` strategies for every Stela type.
+//!
+//! Every type also implements [`proptest::arbitrary::Arbitrary`], so
+//! `any::<Page>()` works. Enum strategies include the `Unknown` variants.
+
+use std::{collections::HashMap, sync::Arc};
+
+use proptest::{collection::vec, option::of, prelude::*};
+
+use crate::stela::{
+    image_upload::{CloudflareDirectUpload, ImageUploadTicket, ImageUploadTicketRequest},
+    *,
+};
+
+/// Short human-readable text, including Esperanto letters.
+pub fn text() -> impl Strategy<Value = String> {
+    "[a-zA-Z0-9 ĉĝĥĵŝŭĈĜĤĴŜŬ.,!?-]{0,24}"
+}
+
+/// Identifier-like text for form field names and similar.
+pub fn name() -> impl Strategy<Value = String> {
+    "[a-z][a-z0-9_]{0,12}"
+}
+
+/// Site-relative or absolute URI.
+pub fn uri() -> impl Strategy<Value = String> {
+    prop_oneof![
+        "/[a-z0-9/_-]{0,24}",
+        "https://[a-z]{1,12}\\.[a-z]{2,3}/[a-z0-9/_-]{0,16}",
+    ]
+}
+
+fn opt_text() -> impl Strategy<Value = Option<String>> {
+    of(text())
+}
+
+fn small_vec<T: std::fmt::Debug>(
+    element: impl Strategy<Value = T>,
+) -> impl Strategy<Value = Vec<T>> {
+    vec(element, 0..4)
+}
+
+fn dimension() -> impl Strategy<Value = Option<f32>> {
+    of(1.0f32..8192.0)
+}
+
+/// [`Image`]
+pub fn image() -> impl Strategy<Value = Image> {
+    (
+        of(0.1f32..10.0),
+        dimension(),
+        dimension(),
+        "[0-9a-f-]{8,36}",
+        opt_text(),
+        of(focal_point()),
+        of(crop_rect()),
+    )
+        .prop_map(
+            |(aspect, width, height, id, alt, focal_point, crop)| Image {
+                aspect,
+                width,
+                height,
+                id,
+                alt,
+                focal_point,
+                crop,
+            },
+        )
+}
+
+/// [`FocalPoint`]
+pub fn focal_point() -> impl Strategy<Value = FocalPoint> {
+    (0.0f32..=1.0, 0.0f32..=1.0).prop_map(|(x, y)| FocalPoint { x, y })
+}
+
+/// [`CropRect`], always inside the image.
+pub fn crop_rect() -> impl Strategy<Value = CropRect> {
+    (0.0f32..0.5, 0.0f32..0.5, 0.1f32..=0.5, 0.1f32..=0.5).prop_map(|(x, y, width, height)| {
+        CropRect {
+            x,
+            y,
+            width,
+            height,
+        }
+    })
+}
+
+/// [`MotionIcon`]
+pub fn motion_icon() -> impl Strategy<Value = MotionIcon> {
+    prop_oneof![
+        Just(MotionIcon::Bell),
+        Just(MotionIcon::ChatCircle),
+        Just(MotionIcon::GlobeHemisphereWest),
+        Just(MotionIcon::Heart),
+        Just(MotionIcon::House),
+        Just(MotionIcon::MagnifyingGlass),
+        Just(MotionIcon::Plus),
+        Just(MotionIcon::PushPin),
+        Just(MotionIcon::ShareFat),
+        Just(MotionIcon::Shield),
+        Just(MotionIcon::SignIn),
+        Just(MotionIcon::SignOut),
+        Just(MotionIcon::Toggle),
+        Just(MotionIcon::Flag),
+        Just(MotionIcon::UserCircle),
+        Just(MotionIcon::Unknown),
+    ]
+}
+
+/// [`MotionVariant`]
+pub fn motion_variant() -> impl Strategy<Value = MotionVariant> {
+    prop_oneof![
+        Just(MotionVariant::Button),
+        Just(MotionVariant::ButtonBorder),
+        Just(MotionVariant::Link),
+        Just(MotionVariant::LinkHoverButton),
+        Just(MotionVariant::LinkHoverButtonBorder),
+        Just(MotionVariant::Unknown),
+    ]
+}
+
+/// [`MotionColor`]
+pub fn motion_color() -> impl Strategy<Value = MotionColor> {
+    prop_oneof![
+        Just(MotionColor::Primary),
+        Just(MotionColor::Secondary),
+        Just(MotionColor::Text),
+        Just(MotionColor::Unknown),
+    ]
+}
+
+/// [`MotionApiCall`]
+pub fn motion_api_call() -> impl Strategy<Value = MotionApiCall> {
+    text().prop_map(|data| MotionApiCall { data })
+}
+
+/// [`MotionHref`]
+pub fn motion_href() -> impl Strategy<Value = MotionHref> {
+    (uri(), of(any::<bool>())).prop_map(|(uri, new_tab)| MotionHref { uri, new_tab })
+}
+
+/// [`MotionScrollTo`]
+pub fn motion_scroll_to() -> impl Strategy<Value = MotionScrollTo> {
+    name().prop_map(|section_id| MotionScrollTo { section_id })
+}
+
+/// [`MotionShare`]
+pub fn motion_share() -> impl Strategy<Value = MotionShare> {
+    (opt_text(), opt_text(), of(uri())).prop_map(|(title, text, url)| MotionShare {
+        title,
+        text,
+        url,
+    })
+}
+
+/// [`MotionSubmit`]
+pub fn motion_submit() -> impl Strategy<Value = MotionSubmit> {
+    proptest::strategy::LazyJust::new(|| MotionSubmit {})
+}
+
+/// [`MotionPreviousStep`]
+pub fn motion_previous_step() -> impl Strategy<Value = MotionPreviousStep> {
+    proptest::strategy::LazyJust::new(|| MotionPreviousStep {})
+}
+
+/// [`Motion`]
+pub fn motion() -> impl Strategy<Value = Motion> {
+    prop_oneof![
+        motion_api_call().prop_map(|m| Motion::ApiCall(Arc::new(m))),
+        motion_href().prop_map(|m| Motion::Href(Arc::new(m))),
+        motion_scroll_to().prop_map(|m| Motion::ScrollTo(Arc::new(m))),
+        motion_share().prop_map(|m| Motion::Share(Arc::new(m))),
+        motion_submit().prop_map(|m| Motion::Submit(Arc::new(m))),
+        motion_previous_step().prop_map(|m| Motion::PreviousStep(Arc::new(m))),
+        Just(Motion::Unknown),
+    ]
+}
+
+/// [`MotionApiCallResponse`]
+pub fn motion_api_call_response() -> impl Strategy<Value = MotionApiCallResponse> {
+    (of(any::<bool>()), opt_text(), of(modal()), of(uri())).prop_map(
+        |(new_toggle, new_text, modal, redirect)| MotionApiCallResponse {
+            new_toggle,
+            new_text,
+            modal: modal.map(Arc::new),
+            redirect,
+        },
+    )
+}
+
+/// [`MotionInteractionRequest`]
+pub fn motion_interaction_request() -> impl Strategy<Value = MotionInteractionRequest> {
+    (
+        text(),
+        of(any::<bool>()),
+        of(uri()),
+        of(name()),
+        client_capabilities(),
+    )
+        .prop_map(|(data, toggle, page_path, section_id, capabilities)| {
+            MotionInteractionRequest {
+                data,
+                toggle,
+                page_path,
+                section_id,
+                capabilities,
+            }
+        })
+}
+
+/// [`ClientCapabilities`]
+pub fn client_capabilities() -> impl Strategy<Value = ClientCapabilities> {
+    any::<u32>().prop_map(ClientCapabilities::from_bits_truncate)
+}
+
+/// [`MotionInteractionError`]
+pub fn motion_interaction_error() -> impl Strategy<Value = MotionInteractionError> {
+    (motion_interaction_error_code(), opt_text(), any::<bool>()).prop_map(
+        |(code, message, retryable)| MotionInteractionError {
+            code,
+            message,
+            retryable,
+        },
+    )
+}
+
+/// [`MotionInteractionErrorCode`]
+pub fn motion_interaction_error_code() -> impl Strategy<Value = MotionInteractionErrorCode> {
+    prop_oneof![
+        Just(MotionInteractionErrorCode::Unauthenticated),
+        Just(MotionInteractionErrorCode::Forbidden),
+        Just(MotionInteractionErrorCode::NotFound),
+        Just(MotionInteractionErrorCode::RateLimited),
+        Just(MotionInteractionErrorCode::InvalidData),
+        Just(MotionInteractionErrorCode::Internal),
+        Just(MotionInteractionErrorCode::Unknown),
+    ]
+}
+
+/// [`VisualMotion`]
+pub fn visual_motion() -> BoxedStrategy<VisualMotion> {
+    (
+        opt_text(),
+        opt_text(),
+        of(motion_icon()),
+        of(image()),
+        of(any::<bool>()),
+        motion_variant(),
+        motion_color(),
+        motion(),
+    )
+        .prop_map(
+            |(title, aria_label, icon, image, initial_toggle, variant, color, motion)| {
+                VisualMotion {
+                    title,
+                    aria_label,
+                    icon,
+                    image,
+                    initial_toggle,
+                    variant,
+                    color,
+                    motion,
+                }
+            },
+        )
+        .boxed()
+}
+
+/// [`Hero`]
+pub fn hero() -> BoxedStrategy<Hero> {
+    (
+        of(image()),
+        of(image()),
+        opt_text(),
+        of(image()),
+        of(image()),
+        opt_text(),
+        opt_text(),
+        small_vec(visual_motion()),
+    )
+        .prop_map(
+            |(
+                primary_image_light,
+                primary_image_dark,
+                primary_image_fallback_text,
+                background_image_light,
+                background_image_dark,
+                title,
+                description,
+                motions,
+            )| Hero {
+                primary_image_light,
+                primary_image_dark,
+                primary_image_fallback_text,
+                background_image_light,
+                background_image_dark,
+                title,
+                description,
+                motions,
+            },
+        )
+        .boxed()
+}
+
+/// [`Navbar`]
+pub fn navbar() -> impl Strategy<Value = Navbar> {
+    (
+        motion(),
+        image(),
+        opt_text(),
+        of(motion()),
+        opt_text(),
+        small_vec(visual_motion()),
+        small_vec(visual_motion()),
+    )
+        .prop_map(
+            |(
+                left_side_motion,
+                left_side_icon_image,
+                left_side_secondary_text,
+                search_motion,
+                search_text,
+                right_side_motions,
+                side_motions,
+            )| Navbar {
+                left_side_motion,
+                left_side_icon_image,
+                left_side_secondary_text,
+                search_motion,
+                search_text,
+                right_side_motions,
+                side_motions,
+            },
+        )
+}
+
+/// [`SidebarCard`]
+pub fn sidebar_card() -> impl Strategy<Value = SidebarCard> {
+    (text(), text(), small_vec(visual_motion())).prop_map(|(title, body, motions)| SidebarCard {
+        title,
+        body,
+        motions,
+    })
+}
+
+/// [`Sidebar`]
+pub fn sidebar() -> impl Strategy<Value = Sidebar> {
+    small_vec(sidebar_card()).prop_map(|cards| Sidebar { cards })
+}
+
+/// [`TwitterCard`]
+pub fn twitter_card() -> impl Strategy<Value = TwitterCard> {
+    prop_oneof![
+        Just(TwitterCard::Summary),
+        Just(TwitterCard::SummaryLargeImage),
+        Just(TwitterCard::Player),
+        Just(TwitterCard::App),
+        Just(TwitterCard::Unknown),
+    ]
+}
+
+/// [`OpenGraphType`]
+pub fn open_graph_type() -> impl Strategy<Value = OpenGraphType> {
+    prop_oneof![
+        Just(OpenGraphType::Website),
+        Just(OpenGraphType::Article),
+        Just(OpenGraphType::Profile),
+        Just(OpenGraphType::Unknown),
+    ]
+}
+
+/// [`SocialImage`]
+pub fn social_image() -> impl Strategy<Value = SocialImage> {
+    prop_oneof![
+        image().prop_map(SocialImage::Image),
+        uri().prop_map(SocialImage::Url),
+    ]
+}
+
+/// [`SocialData`]
+pub fn social_data() -> BoxedStrategy<SocialData> {
+    (
+        (opt_text(), opt_text(), of(social_image()), opt_text()),
+        (of(uri()), of(uri()), of(open_graph_type()), opt_text()),
+        (opt_text(), opt_text(), opt_text(), of(twitter_card())),
+    )
+        .prop_map(
+            |(
+                (title, description, image, image_alt),
+                (url, canonical, og_type, site_name),
+                (published_time, modified_time, author, twitter_card),
+            )| SocialData {
+                title,
+                description,
+                image,
+                image_alt,
+                url,
+                canonical,
+                og_type,
+                site_name,
+                published_time,
+                modified_time,
+                author,
+                twitter_card,
+            },
+        )
+        .boxed()
+}
+
+/// [`TextFilter`]
+pub fn text_filter() -> impl Strategy<Value = TextFilter> {
+    any::<u32>().prop_map(TextFilter::from_bits_truncate)
+}
+
+/// [`ImagePreviewStyle`]
+pub fn image_preview_style() -> impl Strategy<Value = ImagePreviewStyle> {
+    prop_oneof![
+        Just(ImagePreviewStyle::LargeRectangle),
+        Just(ImagePreviewStyle::ThumbnailRect),
+        Just(ImagePreviewStyle::ThumbnailCircle),
+        Just(ImagePreviewStyle::Unknown),
+    ]
+}
+
+fn length_range() -> impl Strategy<Value = (Option<i32>, Option<i32>)> {
+    (of(0i32..64), of(64i32..4096))
+}
+
+/// [`FormInputText`]
+pub fn form_input_text() -> impl Strategy<Value = FormInputText> {
+    (
+        opt_text(),
+        of(name()),
+        opt_text(),
+        length_range(),
+        any::<bool>(),
+        of(text_filter()),
+    )
+        .prop_map(
+            |(title, name, initial_value, (length_min, length_max), esperanto, filter)| {
+                FormInputText {
+                    title,
+                    name,
+                    initial_value,
+                    length_min,
+                    length_max,
+                    esperanto,
+                    filter,
+                }
+            },
+        )
+}
+
+/// [`SelectOption`]
+pub fn select_option() -> impl Strategy<Value = SelectOption> {
+    (name(), opt_text()).prop_map(|(value, title)| SelectOption { value, title })
+}
+
+/// [`FormInputSelect`]
+pub fn form_input_select() -> impl Strategy<Value = FormInputSelect> {
+    (
+        opt_text(),
+        name(),
+        of(name()),
+        opt_text(),
+        small_vec(select_option()),
+        any::<bool>(),
+    )
+        .prop_map(
+            |(title, name, initial_value, placeholder, options, required)| FormInputSelect {
+                title,
+                name,
+                initial_value,
+                placeholder,
+                options,
+                required,
+            },
+        )
+}
+
+/// [`MultiSelectStyle`]
+pub fn multi_select_style() -> impl Strategy<Value = MultiSelectStyle> {
+    prop_oneof![
+        Just(MultiSelectStyle::Checkboxes),
+        Just(MultiSelectStyle::Chips),
+        Just(MultiSelectStyle::Unknown),
+    ]
+}
+
+/// [`FormInputMultiSelect`]
+pub fn form_input_multi_select() -> impl Strategy<Value = FormInputMultiSelect> {
+    (
+        opt_text(),
+        name(),
+        multi_select_style(),
+        small_vec(select_option()),
+        small_vec(name()),
+        (of(0i32..4), of(4i32..16)),
+        any::<bool>(),
+        of(text_filter()),
+        of(1i32..64),
+    )
+        .prop_map(
+            |(
+                title,
+                name,
+                style,
+                options,
+                initial_values,
+                (min_selected, max_selected),
+                allow_custom,
+                custom_filter,
+                custom_length_max,
+            )| FormInputMultiSelect {
+                title,
+                name,
+                style,
+                options,
+                initial_values,
+                min_selected,
+                max_selected,
+                allow_custom,
+                custom_filter,
+                custom_length_max,
+            },
+        )
+}
+
+/// Numbers that survive a JSON round trip exactly.
+fn number() -> impl Strategy<Value = f64> {
+    (-4000i32..4000).prop_map(|n| f64::from(n) / 4.0)
+}
+
+/// [`FormInputNumber`]
+pub fn form_input_number() -> impl Strategy<Value = FormInputNumber> {
+    (
+        opt_text(),
+        name(),
+        of(number()),
+        of(number()),
+        of(number()),
+        of(number()),
+        any::<bool>(),
+    )
+        .prop_map(
+            |(title, name, initial_value, min, max, step, required)| FormInputNumber {
+                title,
+                name,
+                initial_value,
+                min,
+                max,
+                step,
+                required,
+            },
+        )
+}
+
+/// [`DateTimeKind`]
+pub fn date_time_kind() -> impl Strategy<Value = DateTimeKind> {
+    prop_oneof![
+        Just(DateTimeKind::Date),
+        Just(DateTimeKind::Time),
+        Just(DateTimeKind::DateTime),
+        Just(DateTimeKind::Unknown),
+    ]
+}
+
+/// [`FormInputDateTime`]
+pub fn form_input_date_time() -> impl Strategy<Value = FormInputDateTime> {
+    (
+        opt_text(),
+        name(),
+        date_time_kind(),
+        opt_text(),
+        opt_text(),
+        opt_text(),
+        any::<bool>(),
+    )
+        .prop_map(
+            |(title, name, kind, initial_value, min, max, required)| FormInputDateTime {
+                title,
+                name,
+                kind,
+                initial_value,
+                min,
+                max,
+                required,
+            },
+        )
+}
+
+/// [`FormInputEmail`]
+pub fn form_input_email() -> impl Strategy<Value = FormInputEmail> {
+    (opt_text(), name(), opt_text(), any::<bool>()).prop_map(
+        |(title, name, initial_value, required)| FormInputEmail {
+            title,
+            name,
+            initial_value,
+            required,
+        },
+    )
+}
+
+/// [`FormInputUrl`]
+pub fn form_input_url() -> impl Strategy<Value = FormInputUrl> {
+    (opt_text(), name(), of(uri()), any::<bool>()).prop_map(
+        |(title, name, initial_value, required)| FormInputUrl {
+            title,
+            name,
+            initial_value,
+            required,
+        },
+    )
+}
+
+/// [`FormInputPassword`]
+pub fn form_input_password() -> impl Strategy<Value = FormInputPassword> {
+    (
+        opt_text(),
+        name(),
+        length_range(),
+        of(name()),
+        opt_text(),
+        opt_text(),
+    )
+        .prop_map(
+            |(title, name, (length_min, length_max), confirm_name, confirm_title, autocomplete)| {
+                FormInputPassword {
+                    title,
+                    name,
+                    length_min,
+                    length_max,
+                    confirm_name,
+                    confirm_title,
+                    autocomplete,
+                }
+            },
+        )
+}
+
+/// [`FormInputImage`]
+pub fn form_input_image() -> impl Strategy<Value = FormInputImage> {
+    (
+        opt_text(),
+        of(name()),
+        of(image()),
+        image_preview_style(),
+        image_constraints(),
+        of(0.1f32..10.0),
+    )
+        .prop_map(
+            |(title, name, initial_image, preview_style, constraints, crop_aspect)| {
+                FormInputImage {
+                    title,
+                    name,
+                    initial_image,
+                    preview_style,
+                    constraints,
+                    crop_aspect,
+                }
+            },
+        )
+}
+
+fn mime_type() -> impl Strategy<Value = String> {
+    prop_oneof![
+        Just("image/png".to_string()),
+        Just("image/jpeg".to_string()),
+        Just("image/webp".to_string()),
+        "image/[a-z+-]{1,12}",
+    ]
+}
+
+fn pixels() -> impl Strategy<Value = Option<u32>> {
+    of(1u32..8192)
+}
+
+/// [`ImageConstraints`]
+pub fn image_constraints() -> impl Strategy<Value = ImageConstraints> {
+    (
+        small_vec(mime_type()),
+        of(1u64..50_000_000),
+        (pixels(), pixels(), pixels(), pixels()),
+        of(0.1f32..10.0),
+        of(0.1f32..10.0),
+    )
+        .prop_map(
+            |(
+                mime_types,
+                max_bytes,
+                (min_width, min_height, max_width, max_height),
+                aspect_min,
+                aspect_max,
+            )| {
+                ImageConstraints {
+                    mime_types,
+                    max_bytes,
+                    min_width,
+                    min_height,
+                    max_width,
+                    max_height,
+                    aspect_min,
+                    aspect_max,
+                }
+            },
+        )
+}
+
+/// [`ImageUploadTicketRequest`]
+pub fn image_upload_ticket_request() -> impl Strategy<Value = ImageUploadTicketRequest> {
+    (
+        name(),
+        opt_text(),
+        name(),
+        mime_type(),
+        1u64..50_000_000,
+        pixels(),
+        pixels(),
+        of(focal_point()),
+        of(crop_rect()),
+    )
+        .prop_map(
+            |(
+                form_name,
+                extra_data,
+                input_name,
+                mime_type,
+                bytes,
+                width,
+                height,
+                focal_point,
+                crop,
+            )| {
+                ImageUploadTicketRequest {
+                    form_name,
+                    extra_data,
+                    input_name,
+                    mime_type,
+                    bytes,
+                    width,
+                    height,
+                    focal_point,
+                    crop,
+                }
+            },
+        )
+}
+
+/// [`ImageUploadTicket`]
+pub fn image_upload_ticket() -> impl Strategy<Value = ImageUploadTicket> {
+    (
+        "[0-9a-f-]{8,36}",
+        uri(),
+        of(Just("2026-10-19T12:30:00Z".to_string())),
+    )
+        .prop_map(|(id, upload_url, expires)| ImageUploadTicket {
+            id,
+            upload_url,
+            expires,
+        })
+}
+
+/// [`CloudflareDirectUpload`]
+pub fn cloudflare_direct_upload() -> impl Strategy<Value = CloudflareDirectUpload> {
+    ("[0-9a-f-]{8,36}", uri())
+        .prop_map(|(id, upload_url)| CloudflareDirectUpload { id, upload_url })
+}
+
+/// [`FormInputMarkdown`]
+pub fn form_input_markdown() -> impl Strategy<Value = FormInputMarkdown> {
+    (opt_text(), name(), opt_text(), length_range()).prop_map(
+        |(title, name, initial_value, (length_min, length_max))| FormInputMarkdown {
+            title,
+            name,
+            initial_value,
+            length_min,
+            length_max,
+        },
+    )
+}
+
+/// [`RadioButton`]
+pub fn radio_button() -> impl Strategy<Value = RadioButton> {
+    (name(), opt_text()).prop_map(|(value, title)| RadioButton { value, title })
+}
+
+/// [`FormInputRadio`]
+pub fn form_input_radio() -> impl Strategy<Value = FormInputRadio> {
+    (opt_text(), name(), of(0usize..4), small_vec(radio_button())).prop_map(
+        |(title, name, initial_index, options)| FormInputRadio {
+            title,
+            name,
+            initial_index,
+            options,
+        },
+    )
+}
+
+/// [`FormInputCheckbox`]
+pub fn form_input_checkbox() -> impl Strategy<Value = FormInputCheckbox> {
+    (opt_text(), name(), of(any::<bool>())).prop_map(|(title, name, default_checked)| {
+        FormInputCheckbox {
+            title,
+            name,
+            default_checked,
+        }
+    })
+}
+
+/// [`FormInputCfTurnstile`]
+pub fn form_input_cf_turnstile() -> impl Strategy<Value = FormInputCfTurnstile> {
+    (
+        (
+            of(name()),
+            "[0-9a-zA-Z_]{8,24}",
+            of(name()),
+            of(turnstile_size()),
+            of(turnstile_language()),
+        ),
+        (
+            of(turnstile_theme()),
+            of(turnstile_appearance()),
+            of(turnstile_execution()),
+            of(turnstile_retry()),
+            of("[a-zA-Z0-9_-]{1,32}"),
+            of("[a-zA-Z0-9_-]{1,64}"),
+        ),
+    )
+        .prop_map(
+            |(
+                (class, sitekey, response_field_name, size, language),
+                (theme, appearance, execution, retry, action, cdata),
+            )| FormInputCfTurnstile {
+                class,
+                sitekey,
+                response_field_name,
+                size,
+                language,
+                theme,
+                appearance,
+                execution,
+                retry,
+                action,
+                cdata,
+            },
+        )
+}
+
+/// [`TurnstileSize`]
+pub fn turnstile_size() -> impl Strategy<Value = TurnstileSize> {
+    prop_oneof![
+        Just(TurnstileSize::Normal),
+        Just(TurnstileSize::Compact),
+        Just(TurnstileSize::Flexible),
+        Just(TurnstileSize::Unknown),
+    ]
+}
+
+/// [`TurnstileTheme`]
+pub fn turnstile_theme() -> impl Strategy<Value = TurnstileTheme> {
+    prop_oneof![
+        Just(TurnstileTheme::Light),
+        Just(TurnstileTheme::Dark),
+        Just(TurnstileTheme::Auto),
+        Just(TurnstileTheme::Unknown),
+    ]
+}
+
+/// [`TurnstileAppearance`]
+pub fn turnstile_appearance() -> impl Strategy<Value = TurnstileAppearance> {
+    prop_oneof![
+        Just(TurnstileAppearance::Always),
+        Just(TurnstileAppearance::Execute),
+        Just(TurnstileAppearance::InteractionOnly),
+        Just(TurnstileAppearance::Unknown),
+    ]
+}
+
+/// [`TurnstileExecution`]
+pub fn turnstile_execution() -> impl Strategy<Value = TurnstileExecution> {
+    prop_oneof![
+        Just(TurnstileExecution::Render),
+        Just(TurnstileExecution::Execute),
+        Just(TurnstileExecution::Unknown),
+    ]
+}
+
+/// [`TurnstileRetry`]
+pub fn turnstile_retry() -> impl Strategy<Value = TurnstileRetry> {
+    prop_oneof![
+        Just(TurnstileRetry::Auto),
+        Just(TurnstileRetry::Never),
+        Just(TurnstileRetry::Unknown),
+    ]
+}
+
+/// [`TurnstileLanguage`]
+pub fn turnstile_language() -> impl Strategy<Value = TurnstileLanguage> {
+    prop_oneof![
+        Just(TurnstileLanguage::auto()),
+        "[a-z]{2,3}(-[A-Z]{2})?".prop_map(|tag| tag.parse().unwrap()),
+    ]
+}
+
+/// [`FormInputMotions`]
+pub fn form_input_motions() -> impl Strategy<Value = FormInputMotions> {
+    (of(any::<bool>()), small_vec(visual_motion())).prop_map(|(vertical_list, motions)| {
+        FormInputMotions {
+            vertical_list,
+            motions,
+        }
+    })
+}
+
+/// Non-recursive [`FormInput`] variants.
+fn form_input_leaf() -> impl Strategy<Value = FormInput> {
+    prop_oneof![
+        form_input_checkbox().prop_map(|i| FormInput::Checkbox(Arc::new(i))),
+        form_input_cf_turnstile().prop_map(|i| FormInput::CfTurnstile(Arc::new(i))),
+        form_input_image().prop_map(|i| FormInput::Image(Arc::new(i))),
+        form_input_markdown().prop_map(|i| FormInput::Markdown(Arc::new(i))),
+        form_input_motions().prop_map(|i| FormInput::Motions(Arc::new(i))),
+        form_input_radio().prop_map(|i| FormInput::Radio(Arc::new(i))),
+        form_input_text().prop_map(|i| FormInput::Text(Arc::new(i))),
+        form_input_select().prop_map(|i| FormInput::Select(Arc::new(i))),
+        form_input_number().prop_map(|i| FormInput::Number(Arc::new(i))),
+        form_input_date_time().prop_map(|i| FormInput::DateTime(Arc::new(i))),
+        form_input_email().prop_map(|i| FormInput::Email(Arc::new(i))),
+        form_input_url().prop_map(|i| FormInput::Url(Arc::new(i))),
+        form_input_password().prop_map(|i| FormInput::Password(Arc::new(i))),
+        form_input_multi_select().prop_map(|i| FormInput::MultiSelect(Arc::new(i))),
+        Just(FormInput::Unknown),
+    ]
+}
+
+/// [`FormInput`], nesting tabs and subsections up to three levels deep.
+pub fn form_input() -> BoxedStrategy<FormInput> {
+    form_input_leaf()
+        .prop_recursive(3, 24, 4, |inner| {
+            prop_oneof![
+                (opt_text(), vec(inner.clone(), 0..4)).prop_map(|(title, inputs)| {
+                    FormInput::Subsection(Arc::new(FormInputSubsection { title, inputs }))
+                }),
+                (
+                    of(form_condition()),
+                    of(form_condition()),
+                    vec(inner.clone(), 0..4)
+                )
+                    .prop_map(|(show_when, require_when, inputs)| {
+                        FormInput::Conditional(Arc::new(FormInputConditional {
+                            show_when,
+                            require_when,
+                            inputs,
+                        }))
+                    }),
+                (vec((text(), inner), 1..4), of(0usize..4)).prop_map(|(tabs, initial_index)| {
+                    FormInput::Tabs(Arc::new(FormInputTabs {
+                        tabs: tabs
+                            .into_iter()
+                            .map(|(title, input)| FormInputTab { title, input })
+                            .collect(),
+                        initial_index,
+                    }))
+                }),
+            ]
+        })
+        .boxed()
+}
+
+/// [`FormInputConditional`]
+pub fn form_input_conditional() -> impl Strategy<Value = FormInputConditional> {
+    (
+        of(form_condition()),
+        of(form_condition()),
+        small_vec(form_input()),
+    )
+        .prop_map(|(show_when, require_when, inputs)| FormInputConditional {
+            show_when,
+            require_when,
+            inputs,
+        })
+}
+
+/// [`FormConditionInput`]
+pub fn form_condition_input() -> impl Strategy<Value = FormConditionInput> {
+    name().prop_map(|name| FormConditionInput { name })
+}
+
+/// [`FormConditionValue`]
+pub fn form_condition_value() -> impl Strategy<Value = FormConditionValue> {
+    (name(), text()).prop_map(|(name, value)| FormConditionValue { name, value })
+}
+
+/// [`FormCondition`]
+pub fn form_condition() -> BoxedStrategy<FormCondition> {
+    prop_oneof![
+        form_condition_value().prop_map(|c| FormCondition::Equals(Arc::new(c))),
+        form_condition_value().prop_map(|c| FormCondition::Contains(Arc::new(c))),
+        form_condition_input().prop_map(|c| FormCondition::Checked(Arc::new(c))),
+        form_condition_input().prop_map(|c| FormCondition::Filled(Arc::new(c))),
+        Just(FormCondition::Unknown),
+    ]
+    .prop_recursive(2, 8, 3, |inner| {
+        prop_oneof![
+            vec(inner.clone(), 0..3).prop_map(FormCondition::All),
+            vec(inner.clone(), 0..3).prop_map(FormCondition::Any),
+            inner.prop_map(|c| FormCondition::Not(Arc::new(c))),
+        ]
+    })
+    .boxed()
+}
+
+/// [`FormInputSubsection`]
+pub fn form_input_subsection() -> impl Strategy<Value = FormInputSubsection> {
+    (opt_text(), small_vec(form_input()))
+        .prop_map(|(title, inputs)| FormInputSubsection { title, inputs })
+}
+
+/// [`FormInputTab`]
+pub fn form_input_tab() -> impl Strategy<Value = FormInputTab> {
+    (text(), form_input()).prop_map(|(title, input)| FormInputTab { title, input })
+}
+
+/// [`FormInputTabs`]
+pub fn form_input_tabs() -> impl Strategy<Value = FormInputTabs> {
+    (vec(form_input_tab(), 1..4), of(0usize..4)).prop_map(|(tabs, initial_index)| FormInputTabs {
+        tabs,
+        initial_index,
+    })
+}
+
+/// [`StepProgress`]
+pub fn step_progress() -> impl Strategy<Value = StepProgress> {
+    prop_oneof![
+        Just(StepProgress::Numbered),
+        Just(StepProgress::Dots),
+        Just(StepProgress::Bar),
+        Just(StepProgress::Titles),
+        Just(StepProgress::Hidden),
+        Just(StepProgress::Unknown),
+    ]
+}
+
+/// [`FormStep`]
+pub fn form_step() -> impl Strategy<Value = FormStep> {
+    (text(), opt_text(), small_vec(form_input())).prop_map(|(title, subtitle, inputs)| FormStep {
+        title,
+        subtitle,
+        inputs,
+    })
+}
+
+/// [`SectionForm`]
+pub fn section_form() -> BoxedStrategy<SectionForm> {
+    (
+        (opt_text(), opt_text(), name(), opt_text(), opt_text()),
+        small_vec(form_input()),
+        vec(form_step(), 0..3),
+        of(0usize..4),
+        step_progress(),
+        of(form_autosave()),
+        of(etag()),
+    )
+        .prop_map(
+            |(
+                (header, subheader, form_name, extra_data, noscript_text),
+                inputs,
+                steps,
+                initial_step,
+                step_progress,
+                autosave,
+                etag,
+            )| SectionForm {
+                header,
+                subheader,
+                form_name,
+                extra_data,
+                noscript_text,
+                inputs,
+                steps,
+                initial_step,
+                step_progress,
+                autosave,
+                etag,
+            },
+        )
+        .boxed()
+}
+
+fn etag() -> impl Strategy<Value = String> {
+    "\"[0-9a-f]{8,16}\""
+}
+
+/// [`FormAutosave`]
+pub fn form_autosave() -> impl Strategy<Value = FormAutosave> {
+    (any::<u32>(), of(60u64..31_536_000))
+        .prop_map(|(version, max_age)| FormAutosave { version, max_age })
+}
+
+/// [`FormCallData`]
+pub fn form_call_data() -> impl Strategy<Value = FormCallData> {
+    (
+        name(),
+        opt_text(),
+        proptest::collection::hash_map(name(), form_value(), 0..4),
+        of(0usize..4),
+        of(etag()),
+    )
+        .prop_map(|(form_name, extra_data, fields, step, etag)| FormCallData {
+            form_name,
+            extra_data,
+            fields: fields.into_iter().collect::<HashMap<_, _>>(),
+            step,
+            etag,
+        })
+}
+
+/// [`FormValue`], with lists nested up to two levels deep.
+pub fn form_value() -> BoxedStrategy<FormValue> {
+    prop_oneof![
+        text().prop_map(FormValue::Text),
+        any::<bool>().prop_map(FormValue::Bool),
+        name().prop_map(FormValue::Image),
+        name().prop_map(FormValue::Option),
+    ]
+    .prop_recursive(2, 8, 3, |inner| vec(inner, 0..3).prop_map(FormValue::List))
+    .boxed()
+}
+
+/// [`FormResponse`]
+pub fn form_response() -> impl Strategy<Value = FormResponse> {
+    (
+        opt_text(),
+        proptest::collection::hash_map(name(), vec(form_error(), 1..3), 0..3),
+        vec(form_error(), 0..2),
+        proptest::collection::hash_map(name(), form_value(), 0..3),
+        of(modal()),
+        of(uri()),
+        opt_text(),
+        of(0usize..4),
+        opt_text(),
+        of(form_conflict()),
+    )
+        .prop_map(
+            |(
+                error,
+                field_errors,
+                form_errors,
+                fields,
+                modal,
+                redirect,
+                success,
+                step,
+                extra_data,
+                conflict,
+            )| FormResponse {
+                error,
+                field_errors: field_errors.into_iter().collect::<HashMap<_, _>>(),
+                form_errors,
+                fields: fields.into_iter().collect::<HashMap<_, _>>(),
+                modal: modal.map(Arc::new),
+                redirect,
+                success,
+                step,
+                extra_data,
+                conflict,
+            },
+        )
+}
+
+/// [`FormConflict`]
+pub fn form_conflict() -> impl Strategy<Value = FormConflict> {
+    (
+        of(etag()),
+        proptest::collection::hash_map(name(), form_value(), 0..3),
+        small_vec(name()),
+    )
+        .prop_map(|(etag, fields, conflicting)| FormConflict {
+            etag,
+            fields: fields.into_iter().collect::<HashMap<_, _>>(),
+            conflicting,
+        })
+}
+
+/// [`FormError`]
+pub fn form_error() -> impl Strategy<Value = FormError> {
+    (form_error_code(), text()).prop_map(|(code, message)| FormError { code, message })
+}
+
+/// [`FormErrorCode`]
+pub fn form_error_code() -> impl Strategy<Value = FormErrorCode> {
+    prop_oneof![
+        Just(FormErrorCode::Required),
+        Just(FormErrorCode::TooShort),
+        Just(FormErrorCode::TooLong),
+        Just(FormErrorCode::DisallowedCharacters),
+        Just(FormErrorCode::InvalidChoice),
+        Just(FormErrorCode::InvalidFormat),
+        Just(FormErrorCode::TooSmall),
+        Just(FormErrorCode::TooLarge),
+        Just(FormErrorCode::InvalidStep),
+        Just(FormErrorCode::Mismatch),
+        Just(FormErrorCode::TooFew),
+        Just(FormErrorCode::TooMany),
+        Just(FormErrorCode::InvalidAspect),
+        Just(FormErrorCode::Conflict),
+        Just(FormErrorCode::Invalid),
+        Just(FormErrorCode::Unknown),
+    ]
+}
+
+/// [`SectionHero`]
+pub fn section_hero() -> impl Strategy<Value = SectionHero> {
+    hero().prop_map(|hero| SectionHero {
+        hero: Arc::new(hero),
+    })
+}
+
+/// [`SectionPost`]
+pub fn section_post() -> BoxedStrategy<SectionPost> {
+    (
+        (opt_text(), of(image()), of(image()), of(any::<bool>())),
+        (opt_text(), of(motion())),
+        (
+            small_vec(visual_motion()),
+            small_vec(visual_motion()),
+            small_vec(visual_motion()),
+            small_vec(visual_motion()),
+        ),
+    )
+        .prop_map(
+            |(
+                (title, image, background, is_pinned),
+                (body_html, motion),
+                (motions_tl, motions_tr, motions_br, motions_bl),
+            )| SectionPost {
+                title,
+                image,
+                background,
+                is_pinned,
+                body_html,
+                motion,
+                motions_tl,
+                motions_tr,
+                motions_br,
+                motions_bl,
+            },
+        )
+        .boxed()
+}
+
+/// [`SectionSponsor`]
+pub fn section_sponsor() -> impl Strategy<Value = SectionSponsor> {
+    (text(), text(), text(), small_vec(motion())).prop_map(|(sponsor_text, name, text, motions)| {
+        SectionSponsor {
+            sponsor_text,
+            name,
+            text,
+            motions,
+        }
+    })
+}
+
+/// [`TilesLayout`]
+pub fn tiles_layout() -> impl Strategy<Value = TilesLayout> {
+    prop_oneof![
+        Just(TilesLayout::HorizontalList),
+        Just(TilesLayout::VerticalList),
+        Just(TilesLayout::Grid),
+        Just(TilesLayout::Unknown),
+    ]
+}
+
+/// [`Tile`]
+pub fn tile() -> impl Strategy<Value = Tile> {
+    (
+        opt_text(),
+        opt_text(),
+        of(motion()),
+        of(image()),
+        opt_text(),
+    )
+        .prop_map(|(header, subheader, motion, image, body_text)| Tile {
+            header,
+            subheader,
+            motion,
+            image,
+            body_text,
+        })
+}
+
+/// [`SectionTiles`]
+pub fn section_tiles() -> impl Strategy<Value = SectionTiles> {
+    (small_vec(tile()), tiles_layout()).prop_map(|(tiles, layout)| SectionTiles { tiles, layout })
+}
+
+/// [`Section`]
+pub fn section() -> BoxedStrategy<Section> {
+    prop_oneof![
+        section_form().prop_map(|s| Section::Form(Arc::new(s))),
+        section_hero().prop_map(|s| Section::Hero(Arc::new(s))),
+        section_post().prop_map(|s| Section::Post(Arc::new(s))),
+        section_sponsor().prop_map(|s| Section::Sponsor(Arc::new(s))),
+        section_tiles().prop_map(|s| Section::Tiles(Arc::new(s))),
+        Just(Section::Unknown),
+    ]
+    .boxed()
+}
+
+/// [`VisualSection`]
+pub fn visual_section() -> impl Strategy<Value = VisualSection> {
+    (of(name()), opt_text(), of(any::<bool>()), section()).prop_map(
+        |(id, title, bordered, section)| VisualSection {
+            id,
+            title,
+            bordered,
+            section,
+        },
+    )
+}
+
+/// [`Modal`]
+pub fn modal() -> BoxedStrategy<Modal> {
+    section().prop_map(|section| Modal { section }).boxed()
+}
+
+/// [`PageLayout`]
+pub fn page_layout() -> impl Strategy<Value = PageLayout> {
+    prop_oneof![
+        Just(PageLayout::List),
+        Just(PageLayout::Tabbed),
+        Just(PageLayout::Unknown),
+    ]
+}
+
+/// [`Page`]
+pub fn page() -> BoxedStrategy<Page> {
+    (
+        opt_text(),
+        of("[a-z]{2}"),
+        of(social_data()),
+        of(page_layout()),
+        of(hero()),
+        of(sidebar()),
+        small_vec(visual_section()),
+    )
+        .prop_map(
+            |(title, lang, social, layout, hero, sidebar, sections)| Page {
+                title,
+                lang,
+                social: social.map(Arc::new),
+                layout,
+                hero: hero.map(Arc::new),
+                sidebar: sidebar.map(Arc::new),
+                sections,
+            },
+        )
+        .boxed()
+}
+
+macro_rules! impl_arbitrary {
+    ($($ty:ty => $strategy:ident,)*) => {
+        $(
+            impl Arbitrary for $ty {
+                type Parameters = ();
+                type Strategy = BoxedStrategy<Self>;
+
+                fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                    $strategy().boxed()
+                }
+            }
+        )*
+    };
+}
+
+impl_arbitrary! {
+    ClientCapabilities => client_capabilities,
+    CloudflareDirectUpload => cloudflare_direct_upload,
+    CropRect => crop_rect,
+    DateTimeKind => date_time_kind,
+    FocalPoint => focal_point,
+    FormAutosave => form_autosave,
+    FormCallData => form_call_data,
+    FormCondition => form_condition,
+    FormConditionInput => form_condition_input,
+    FormConditionValue => form_condition_value,
+    FormConflict => form_conflict,
+    FormError => form_error,
+    FormErrorCode => form_error_code,
+    FormInput => form_input,
+    FormInputCfTurnstile => form_input_cf_turnstile,
+    FormInputCheckbox => form_input_checkbox,
+    FormInputConditional => form_input_conditional,
+    FormInputDateTime => form_input_date_time,
+    FormInputEmail => form_input_email,
+    FormInputImage => form_input_image,
+    FormInputMarkdown => form_input_markdown,
+    FormInputMotions => form_input_motions,
+    FormInputMultiSelect => form_input_multi_select,
+    FormInputNumber => form_input_number,
+    FormInputPassword => form_input_password,
+    FormInputRadio => form_input_radio,
+    FormInputSelect => form_input_select,
+    FormInputSubsection => form_input_subsection,
+    FormInputTab => form_input_tab,
+    FormInputTabs => form_input_tabs,
+    FormInputText => form_input_text,
+    FormInputUrl => form_input_url,
+    FormResponse => form_response,
+    FormStep => form_step,
+    FormValue => form_value,
+    Hero => hero,
+    Image => image,
+    ImageConstraints => image_constraints,
+    ImagePreviewStyle => image_preview_style,
+    ImageUploadTicket => image_upload_ticket,
+    ImageUploadTicketRequest => image_upload_ticket_request,
+    Modal => modal,
+    Motion => motion,
+    MotionApiCall => motion_api_call,
+    MotionApiCallResponse => motion_api_call_response,
+    MotionColor => motion_color,
+    MotionHref => motion_href,
+    MotionIcon => motion_icon,
+    MotionInteractionError => motion_interaction_error,
+    MotionInteractionErrorCode => motion_interaction_error_code,
+    MotionInteractionRequest => motion_interaction_request,
+    MotionPreviousStep => motion_previous_step,
+    MotionScrollTo => motion_scroll_to,
+    MotionShare => motion_share,
+    MotionSubmit => motion_submit,
+    MotionVariant => motion_variant,
+    MultiSelectStyle => multi_select_style,
+    Navbar => navbar,
+    OpenGraphType => open_graph_type,
+    Page => page,
+    PageLayout => page_layout,
+    RadioButton => radio_button,
+    Section => section,
+    SectionForm => section_form,
+    SectionHero => section_hero,
+    SectionPost => section_post,
+    SectionSponsor => section_sponsor,
+    SectionTiles => section_tiles,
+    SelectOption => select_option,
+    Sidebar => sidebar,
+    SidebarCard => sidebar_card,
+        SocialData => social_data,
+    SocialImage => social_image,
+    StepProgress => step_progress,
+    TextFilter => text_filter,
+    Tile => tile,
+    TilesLayout => tiles_layout,
+    TurnstileAppearance => turnstile_appearance,
+    TurnstileExecution => turnstile_execution,
+    TurnstileLanguage => turnstile_language,
+    TurnstileRetry => turnstile_retry,
+    TurnstileSize => turnstile_size,
+    TurnstileTheme => turnstile_theme,
+    TwitterCard => twitter_card,
+    VisualMotion => visual_motion,
+    VisualSection => visual_section,
+}
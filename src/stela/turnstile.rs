@@ -0,0 +1,312 @@
+//! Server-side verification of Cloudflare Turnstile tokens.
+//!
+//! The widget of a [`FormInputCfTurnstile`] puts a single-use token in the
+//! submitted form. The API reads it with [`FormInputCfTurnstile::token`],
+//! sends a [`SiteverifyRequest`] to [`SITEVERIFY_URL`], and checks the
+//! [`SiteverifyResponse`] with [`FormInputCfTurnstile::check_response`].
+//! [`Verifier`] does all of that over a [`SiteverifyClient`], so tests can
+//! replace the HTTP call with a stub.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::stela::{FormCallData, FormError, FormErrorCode, FormInputCfTurnstile};
+
+/// Where to send [`SiteverifyRequest`]s.
+pub const SITEVERIFY_URL: &str = "https://challenges.cloudflare.com/turnstile/v0/siteverify";
+
+/// Form field the widget puts its token in, unless
+/// [`FormInputCfTurnstile::response_field_name`] says otherwise.
+pub const DEFAULT_RESPONSE_FIELD_NAME: &str = "cf-turnstile-response";
+
+/// Longest [`FormInputCfTurnstile::action`] Turnstile accepts.
+pub const ACTION_MAX_LEN: usize = 32;
+
+/// Longest [`FormInputCfTurnstile::cdata`] Turnstile accepts.
+pub const CDATA_MAX_LEN: usize = 255;
+
+/// Whether `value` can be a `data-action` or `data-cdata` of at most `max`
+/// characters.
+///
+/// `const` so `#[derive(FromFormCallData)]` can check `action` at compile
+/// time.
+pub const fn is_valid_data(value: &str, max: usize) -> bool {
+    let bytes = value.as_bytes();
+    if bytes.is_empty() || bytes.len() > max {
+        return false;
+    }
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if !(b.is_ascii_alphanumeric() || b == b'-' || b == b'_') {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Body of a siteverify call. Its `Debug` output leaves out the secret.
+#[derive(Clone, Deserialize, Serialize, PartialEq)]
+pub struct SiteverifyRequest {
+    /// Secret key of the widget.
+    pub secret: String,
+    /// Token from the widget.
+    pub response: String,
+    /// IP address of the visitor.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remoteip: Option<String>,
+    /// UUID that makes retries of the same call safe.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
+}
+
+impl fmt::Debug for SiteverifyRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SiteverifyRequest")
+            .field("secret", &format_args!("[redacted]"))
+            .field("response", &self.response)
+            .field("remoteip", &self.remoteip)
+            .field("idempotency_key", &self.idempotency_key)
+            .finish()
+    }
+}
+
+impl SiteverifyRequest {
+    /// Verify `token` with `secret`.
+    pub fn new(secret: impl Into<String>, token: impl Into<String>) -> Self {
+        Self {
+            secret: secret.into(),
+            response: token.into(),
+            remoteip: None,
+            idempotency_key: None,
+        }
+    }
+
+    /// Also check the token was made for the visitor at `ip`.
+    pub fn with_remote_ip(mut self, ip: impl Into<String>) -> Self {
+        self.remoteip = Some(ip.into());
+        self
+    }
+
+    /// Make retries with the same `key` safe.
+    pub fn with_idempotency_key(mut self, key: impl Into<String>) -> Self {
+        self.idempotency_key = Some(key.into());
+        self
+    }
+
+    /// The request as a JSON body.
+    pub fn body(&self) -> String {
+        serde_json::to_string(self).expect("siteverify requests are always serializable")
+    }
+}
+
+/// What siteverify says about a token.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct SiteverifyResponse {
+    /// Whether the token is valid.
+    pub success: bool,
+    /// Why it isn't.
+    #[serde(rename = "error-codes", default)]
+    pub error_codes: Vec<SiteverifyErrorCode>,
+    /// When the challenge was solved, as an RFC 3339 timestamp.
+    pub challenge_ts: Option<String>,
+    /// Hostname of the page the challenge was solved on.
+    pub hostname: Option<String>,
+    /// `data-action` of the widget.
+    pub action: Option<String>,
+    /// `data-cdata` of the widget.
+    pub cdata: Option<String>,
+}
+
+/// Why siteverify rejected a token.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SiteverifyErrorCode {
+    /// No secret was sent.
+    MissingInputSecret,
+    /// The secret is wrong or doesn't exist.
+    InvalidInputSecret,
+    /// No token was sent.
+    MissingInputResponse,
+    /// The token is malformed or expired.
+    InvalidInputResponse,
+    /// The request was rejected because it was malformed.
+    BadRequest,
+    /// The token was already checked, or is too old.
+    TimeoutOrDuplicate,
+    /// Cloudflare failed; the call can be retried.
+    InternalError,
+    /// Unrecognized error.
+    #[default]
+    #[serde(other)]
+    Unknown,
+}
+
+/// Makes the HTTP call to siteverify.
+pub trait SiteverifyClient {
+    /// Why the call failed.
+    type Error;
+
+    /// `POST` `body` to `url` as `application/json`, and return the response
+    /// body.
+    fn post_json(&self, url: &str, body: &str) -> Result<String, Self::Error>;
+}
+
+/// Why a Turnstile token doesn't pass.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TurnstileRejection {
+    /// Siteverify says the token is invalid.
+    Failed(Vec<SiteverifyErrorCode>),
+    /// The challenge was solved on another site.
+    Hostname(Option<String>),
+    /// The token is from a widget with another `action`.
+    Action(Option<String>),
+    /// The token is from a widget with other `cdata`.
+    Cdata(Option<String>),
+}
+
+/// Why a submission couldn't be verified.
+#[derive(Debug)]
+pub enum TurnstileError<E> {
+    /// The form has no token for the widget.
+    MissingToken,
+    /// The HTTP call failed.
+    Http(E),
+    /// Siteverify answered with something else than a siteverify response.
+    Malformed(serde_json::Error),
+    /// The token doesn't pass.
+    Rejected(TurnstileRejection),
+}
+
+impl<E: fmt::Display> fmt::Display for TurnstileError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingToken => write!(f, "no Turnstile token was submitted"),
+            Self::Http(error) => write!(f, "siteverify call failed: {error}"),
+            Self::Malformed(error) => write!(f, "malformed siteverify response: {error}"),
+            Self::Rejected(TurnstileRejection::Failed(codes)) => {
+                write!(f, "Turnstile token rejected: {codes:?}")
+            }
+            Self::Rejected(TurnstileRejection::Hostname(hostname)) => {
+                write!(f, "Turnstile token is for hostname {hostname:?}")
+            }
+            Self::Rejected(TurnstileRejection::Action(action)) => {
+                write!(f, "Turnstile token is for action {action:?}")
+            }
+            Self::Rejected(TurnstileRejection::Cdata(cdata)) => {
+                write!(f, "Turnstile token has cdata {cdata:?}")
+            }
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for TurnstileError<E> {}
+
+impl<E> TurnstileError<E> {
+    /// The error to show on the widget.
+    pub fn form_error(&self) -> FormError {
+        match self {
+            Self::MissingToken => FormError::new(FormErrorCode::Required, "complete the challenge"),
+            Self::Rejected(TurnstileRejection::Failed(codes))
+                if codes.contains(&SiteverifyErrorCode::TimeoutOrDuplicate) =>
+            {
+                FormError::new(FormErrorCode::Invalid, "the challenge expired, try again")
+            }
+            Self::Http(_) | Self::Malformed(_) | Self::Rejected(_) => {
+                FormError::new(FormErrorCode::Invalid, "the challenge failed, try again")
+            }
+        }
+    }
+}
+
+impl FormInputCfTurnstile {
+    /// Form field the widget puts its token in.
+    pub fn field_name(&self) -> &str {
+        self.response_field_name
+            .as_deref()
+            .unwrap_or(DEFAULT_RESPONSE_FIELD_NAME)
+    }
+
+    /// The token submitted by the widget, if any.
+    pub fn token<'a>(&self, data: &'a FormCallData) -> Option<&'a str> {
+        data.fields
+            .get(self.field_name())
+            .and_then(|value| value.as_text())
+            .filter(|token| !token.is_empty())
+    }
+
+    /// Whether a siteverify response passes for this widget. `hostname` is
+    /// the site the form was served on, if it should be checked. The widget's
+    /// `action` and `cdata` must come back unchanged.
+    pub fn check_response(
+        &self,
+        response: &SiteverifyResponse,
+        hostname: Option<&str>,
+    ) -> Result<(), TurnstileRejection> {
+        if !response.success {
+            return Err(TurnstileRejection::Failed(response.error_codes.clone()));
+        }
+        if let Some(hostname) = hostname {
+            if response.hostname.as_deref() != Some(hostname) {
+                return Err(TurnstileRejection::Hostname(response.hostname.clone()));
+            }
+        }
+        if self.action.is_some() && response.action != self.action {
+            return Err(TurnstileRejection::Action(response.action.clone()));
+        }
+        if self.cdata.is_some() && response.cdata != self.cdata {
+            return Err(TurnstileRejection::Cdata(response.cdata.clone()));
+        }
+        Ok(())
+    }
+}
+
+/// Checks submitted Turnstile tokens. Its `Debug` output leaves out the
+/// secret.
+#[derive(Clone)]
+pub struct Verifier<C> {
+    /// Makes the HTTP calls.
+    pub client: C,
+    /// Secret key of the widgets.
+    pub secret: String,
+    /// Site the forms are served on. Tokens from elsewhere are rejected.
+    pub hostname: Option<String>,
+}
+
+impl<C: fmt::Debug> fmt::Debug for Verifier<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Verifier")
+            .field("client", &self.client)
+            .field("secret", &format_args!("[redacted]"))
+            .field("hostname", &self.hostname)
+            .finish()
+    }
+}
+
+impl<C: SiteverifyClient> Verifier<C> {
+    /// Verify the token `data` has for `input`.
+    pub fn verify(
+        &self,
+        input: &FormInputCfTurnstile,
+        data: &FormCallData,
+        remote_ip: Option<&str>,
+    ) -> Result<SiteverifyResponse, TurnstileError<C::Error>> {
+        let token = input.token(data).ok_or(TurnstileError::MissingToken)?;
+        let mut request = SiteverifyRequest::new(&self.secret, token);
+        if let Some(ip) = remote_ip {
+            request = request.with_remote_ip(ip);
+        }
+        let body = self
+            .client
+            .post_json(SITEVERIFY_URL, &request.body())
+            .map_err(TurnstileError::Http)?;
+        let response: SiteverifyResponse =
+            serde_json::from_str(&body).map_err(TurnstileError::Malformed)?;
+        input
+            .check_response(&response, self.hostname.as_deref())
+            .map_err(TurnstileError::Rejected)?;
+        Ok(response)
+    }
+}
@@ -0,0 +1,472 @@
+//! Server-side checks of submitted form data against its [`SectionForm`].
+//!
+//! [`SectionForm::validate`] checks what the form itself declares: lengths,
+//! text filters, options and selection counts, number and date ranges, email
+//! and URL formats, and password confirmation. Anything the API knows beyond
+//! that, such as a taken username, can be added to the returned
+//! [`FormResponse`] with its builder methods.
+
+use std::collections::HashMap;
+
+use crate::stela::{
+    condition::is_filled, lint::is_valid_uri, FormCallData, FormError, FormErrorCode, FormInput,
+    FormResponse, FormValue, SectionForm,
+};
+
+impl SectionForm {
+    /// Check submitted values against the inputs of this form.
+    ///
+    /// The response has an error per offending input and keeps the submitted
+    /// values. If [`FormResponse::has_errors`] is false, the data is valid and
+    /// the response is empty.
+    pub fn validate(&self, data: &FormCallData) -> FormResponse {
+        self.validate_inputs(self.all_inputs(), data)
+    }
+
+    /// Check submitted values against the inputs of one step of a multi-step
+    /// form, and the inputs shown on every step.
+    ///
+    /// An unknown `step` is a form error.
+    pub fn validate_step(&self, step: usize, data: &FormCallData) -> FormResponse {
+        let Some(current) = self.steps.get(step) else {
+            let error = FormError::new(FormErrorCode::Invalid, format!("unknown step {step}"));
+            return FormResponse::default().with_form_error(error);
+        };
+        self.validate_inputs(current.inputs.iter().chain(&self.inputs), data)
+    }
+
+    fn validate_inputs<'a>(
+        &self,
+        inputs: impl IntoIterator<Item = &'a FormInput>,
+        data: &FormCallData,
+    ) -> FormResponse {
+        let mut validator = Validator {
+            form: self,
+            data,
+            response: FormResponse::default(),
+        };
+        for input in inputs {
+            validator.input(input, true);
+        }
+        let mut response = validator.response;
+        if response.has_errors() {
+            response.fields = self.preserved_fields(data);
+        }
+        response
+    }
+
+    /// The submitted values to send back with errors, leaving out passwords
+    /// and single-use Turnstile tokens.
+    pub fn preserved_fields(&self, data: &FormCallData) -> HashMap<String, FormValue> {
+        let names = self.secret_names();
+        let mut fields = data.fields.clone();
+        fields.retain(|name, _| !names.contains(&name.as_str()));
+        fields
+    }
+}
+
+impl SectionForm {
+    /// Names of the password, password confirmation and Turnstile inputs of
+    /// the form, on every step.
+    pub(crate) fn secret_names(&self) -> Vec<&str> {
+        let mut names = secret_names(&self.inputs);
+        for step in &self.steps {
+            names.extend(secret_names(&step.inputs));
+        }
+        names
+    }
+}
+
+/// Names of the password, password confirmation and Turnstile inputs in
+/// `inputs`, including nested ones.
+pub(crate) fn secret_names(inputs: &[FormInput]) -> Vec<&str> {
+    fn collect<'a>(inputs: &'a [FormInput], names: &mut Vec<&'a str>) {
+        for input in inputs {
+            match input {
+                FormInput::Password(password) => {
+                    names.push(&password.name);
+                    names.extend(password.confirm_name.as_deref());
+                }
+                FormInput::CfTurnstile(_) => names.extend(input.name()),
+                FormInput::Subsection(subsection) => collect(&subsection.inputs, names),
+                FormInput::Conditional(conditional) => collect(&conditional.inputs, names),
+                FormInput::Tabs(tabs) => {
+                    for tab in &tabs.tabs {
+                        collect(std::slice::from_ref(&tab.input), names);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    let mut names = Vec::new();
+    collect(inputs, &mut names);
+    names
+}
+
+/// Whether `email` looks like an email address: one `@`, something before
+/// it, and a dotted domain after it, without spaces.
+pub fn is_valid_email(email: &str) -> bool {
+    let Some((local, domain)) = email.split_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && !domain.contains('@')
+        && !email.chars().any(char::is_whitespace)
+        && domain.contains('.')
+        && domain.split('.').all(|label| !label.is_empty())
+}
+
+/// Whether `url` is an absolute `http` or `https` URL.
+pub fn is_valid_web_url(url: &str) -> bool {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"));
+    rest.is_some_and(|rest| !rest.is_empty() && !rest.starts_with('/')) && is_valid_uri(url)
+}
+
+struct Validator<'a> {
+    form: &'a SectionForm,
+    data: &'a FormCallData,
+    response: FormResponse,
+}
+
+impl Validator<'_> {
+    fn error(&mut self, name: &str, code: FormErrorCode, message: String) {
+        let response = std::mem::take(&mut self.response);
+        self.response = response.with_field_error(name, FormError::new(code, message));
+    }
+
+    /// `required` is false inside tabs, where only the shown tab is submitted.
+    fn input(&mut self, input: &FormInput, required: bool) {
+        match input {
+            FormInput::Markdown(markdown) => {
+                self.text(
+                    input,
+                    &markdown.name,
+                    markdown.length_min,
+                    markdown.length_max,
+                    required,
+                );
+            }
+            FormInput::Text(text) => {
+                let Some(name) = &text.name else { return };
+                self.text(input, name, text.length_min, text.length_max, required);
+                let value = self.data.text(input).unwrap_or_default();
+                if let Some(c) = text
+                    .filter
+                    .and_then(|filter| filter.first_disallowed(&value))
+                {
+                    self.error(
+                        name,
+                        FormErrorCode::DisallowedCharacters,
+                        format!("`{c}` is not allowed"),
+                    );
+                }
+            }
+            FormInput::Radio(radio) => {
+                if !self.data.fields.contains_key(&radio.name) {
+                    return;
+                }
+                let value = self.data.value(input);
+                let value = value.as_ref().and_then(FormValue::as_option);
+                if !radio
+                    .options
+                    .iter()
+                    .any(|option| Some(option.value.as_str()) == value)
+                {
+                    self.error(
+                        &radio.name,
+                        FormErrorCode::InvalidChoice,
+                        format!("`{}` is not one of the options", value.unwrap_or_default()),
+                    );
+                }
+            }
+            FormInput::Subsection(subsection) => {
+                for input in &subsection.inputs {
+                    self.input(input, required);
+                }
+            }
+            FormInput::Tabs(tabs) => {
+                for tab in &tabs.tabs {
+                    self.input(&tab.input, false);
+                }
+            }
+            FormInput::Conditional(conditional) => {
+                if !conditional.is_shown(self.form, self.data) {
+                    return;
+                }
+                for input in &conditional.inputs {
+                    self.input(input, required);
+                }
+                if required && conditional.is_required(self.form, self.data) {
+                    for input in &conditional.inputs {
+                        self.require(input);
+                    }
+                }
+            }
+            FormInput::Select(select) => {
+                let Some(value) = self.present(input, &select.name, required && select.required)
+                else {
+                    return;
+                };
+                if !select.options.iter().any(|option| option.value == value) {
+                    self.error(
+                        &select.name,
+                        FormErrorCode::InvalidChoice,
+                        format!("`{value}` is not one of the options"),
+                    );
+                }
+            }
+            FormInput::Number(number) => {
+                let Some(value) = self.present(input, &number.name, required && number.required)
+                else {
+                    return;
+                };
+                let Some(value) = value.trim().parse::<f64>().ok().filter(|v| v.is_finite()) else {
+                    self.error(
+                        &number.name,
+                        FormErrorCode::InvalidFormat,
+                        "must be a number".to_string(),
+                    );
+                    return;
+                };
+                if let Some(min) = number.min.filter(|&min| value < min) {
+                    self.error(
+                        &number.name,
+                        FormErrorCode::TooSmall,
+                        format!("must be at least {min}"),
+                    );
+                } else if let Some(max) = number.max.filter(|&max| value > max) {
+                    self.error(
+                        &number.name,
+                        FormErrorCode::TooLarge,
+                        format!("must be at most {max}"),
+                    );
+                } else if let Some(step) = number.step.filter(|&step| step > 0.0) {
+                    let steps = (value - number.min.unwrap_or(0.0)) / step;
+                    if (steps - steps.round()).abs() > 1e-9 {
+                        self.error(
+                            &number.name,
+                            FormErrorCode::InvalidStep,
+                            format!("must be in steps of {step}"),
+                        );
+                    }
+                }
+            }
+            FormInput::DateTime(date_time) => {
+                let name = &date_time.name;
+                let Some(value) = self.present(input, name, required && date_time.required) else {
+                    return;
+                };
+                if !date_time.kind.is_valid(&value) {
+                    self.error(
+                        name,
+                        FormErrorCode::InvalidFormat,
+                        "must be a valid date or time".to_string(),
+                    );
+                } else if let Some(min) = date_time.min.as_ref().filter(|min| value < **min) {
+                    self.error(
+                        name,
+                        FormErrorCode::TooSmall,
+                        format!("must be {min} or later"),
+                    );
+                } else if let Some(max) = date_time.max.as_ref().filter(|max| value > **max) {
+                    self.error(
+                        name,
+                        FormErrorCode::TooLarge,
+                        format!("must be {max} or earlier"),
+                    );
+                }
+            }
+            FormInput::Email(email) => {
+                let Some(value) = self.present(input, &email.name, required && email.required)
+                else {
+                    return;
+                };
+                if !is_valid_email(&value) {
+                    self.error(
+                        &email.name,
+                        FormErrorCode::InvalidFormat,
+                        "must be an email address".to_string(),
+                    );
+                }
+            }
+            FormInput::Url(url) => {
+                let Some(value) = self.present(input, &url.name, required && url.required) else {
+                    return;
+                };
+                if !is_valid_web_url(&value) {
+                    self.error(
+                        &url.name,
+                        FormErrorCode::InvalidFormat,
+                        "must be an http or https URL".to_string(),
+                    );
+                }
+            }
+            FormInput::Password(password) => {
+                let name = &password.name;
+                self.text(
+                    input,
+                    name,
+                    password.length_min,
+                    password.length_max,
+                    required,
+                );
+                if let Some(confirm_name) = &password.confirm_name {
+                    let confirm = self.data.fields.get(confirm_name);
+                    if confirm.and_then(FormValue::as_text) != self.data.text(input).as_deref() {
+                        self.error(
+                            confirm_name,
+                            FormErrorCode::Mismatch,
+                            "passwords do not match".to_string(),
+                        );
+                    }
+                }
+            }
+            FormInput::MultiSelect(multi) => {
+                let name = &multi.name;
+                if !required && !self.data.fields.contains_key(name) {
+                    return;
+                }
+                let Some(FormValue::List(values)) = self.data.value(input) else {
+                    return;
+                };
+                let count = values.len();
+                let min = multi.min_selected.and_then(|min| usize::try_from(min).ok());
+                let max = multi.max_selected.and_then(|max| usize::try_from(max).ok());
+                if let Some(min) = min.filter(|&min| count < min) {
+                    let code = if count == 0 {
+                        FormErrorCode::Required
+                    } else {
+                        FormErrorCode::TooFew
+                    };
+                    self.error(name, code, format!("pick at least {min}"));
+                } else if let Some(max) = max.filter(|&max| count > max) {
+                    self.error(name, FormErrorCode::TooMany, format!("pick at most {max}"));
+                }
+                for value in &values {
+                    let FormValue::Text(tag) = value else {
+                        continue;
+                    };
+                    if !multi.allow_custom {
+                        self.error(
+                            name,
+                            FormErrorCode::InvalidChoice,
+                            format!("`{tag}` is not one of the options"),
+                        );
+                    } else if tag.trim().is_empty() {
+                        self.error(
+                            name,
+                            FormErrorCode::Invalid,
+                            "tags can't be empty".to_string(),
+                        );
+                    } else if let Some(c) = multi
+                        .custom_filter
+                        .and_then(|filter| filter.first_disallowed(tag))
+                    {
+                        self.error(
+                            name,
+                            FormErrorCode::DisallowedCharacters,
+                            format!("`{c}` is not allowed"),
+                        );
+                    } else if let Some(max) = multi
+                        .custom_length_max
+                        .and_then(|max| usize::try_from(max).ok())
+                        .filter(|&max| tag.chars().count() > max)
+                    {
+                        self.error(
+                            name,
+                            FormErrorCode::TooLong,
+                            format!("tags must be at most {max} characters"),
+                        );
+                    }
+                }
+            }
+            FormInput::Checkbox(_)
+            | FormInput::CfTurnstile(_)
+            | FormInput::Image(_)
+            | FormInput::Motions(_)
+            | FormInput::Unknown => {}
+        }
+    }
+
+    /// Record that `input`, and the inputs in it, must be filled in, unless
+    /// they already have errors.
+    fn require(&mut self, input: &FormInput) {
+        match input {
+            FormInput::Subsection(subsection) => {
+                for input in &subsection.inputs {
+                    self.require(input);
+                }
+            }
+            FormInput::Conditional(conditional) => {
+                if conditional.is_shown(self.form, self.data) {
+                    for input in &conditional.inputs {
+                        self.require(input);
+                    }
+                }
+            }
+            FormInput::Tabs(_) | FormInput::Motions(_) => {}
+            _ => {
+                let Some(name) = input.name() else { return };
+                let filled = self
+                    .data
+                    .value(input)
+                    .is_some_and(|value| is_filled(&value));
+                if !filled && !self.response.field_errors.contains_key(name) {
+                    self.error(name, FormErrorCode::Required, "required".to_string());
+                }
+            }
+        }
+    }
+
+    /// The non-empty text or option submitted for `input`, or record that it's
+    /// required.
+    fn present(&mut self, input: &FormInput, name: &str, required: bool) -> Option<String> {
+        let value = match self.data.value(input) {
+            Some(FormValue::Text(value) | FormValue::Option(value)) if !value.is_empty() => {
+                Some(value)
+            }
+            _ => None,
+        };
+        if value.is_none() && required {
+            self.error(name, FormErrorCode::Required, "required".to_string());
+        }
+        value
+    }
+
+    fn text(
+        &mut self,
+        input: &FormInput,
+        name: &str,
+        min: Option<i32>,
+        max: Option<i32>,
+        required: bool,
+    ) {
+        if !required && !self.data.fields.contains_key(name) {
+            return;
+        }
+        let len = self
+            .data
+            .text(input)
+            .map_or(0, |value| value.chars().count());
+        let min = min.and_then(|min| usize::try_from(min).ok()).unwrap_or(0);
+        let max = max.and_then(|max| usize::try_from(max).ok());
+        if len == 0 && min > 0 {
+            self.error(name, FormErrorCode::Required, "required".to_string());
+        } else if len < min {
+            self.error(
+                name,
+                FormErrorCode::TooShort,
+                format!("must be at least {min} characters"),
+            );
+        } else if let Some(max) = max.filter(|&max| len > max) {
+            self.error(
+                name,
+                FormErrorCode::TooLong,
+                format!("must be at most {max} characters"),
+            );
+        }
+    }
+}
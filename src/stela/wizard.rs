@@ -0,0 +1,173 @@
+//! Multi-step forms.
+//!
+//! A form with [`SectionForm::steps`] shows one step at a time. The next
+//! button of each step is a [`Motion::Submit`](crate::stela::Motion::Submit),
+//! which submits only that step with [`FormCallData::step`] set. The API passes
+//! the submission to [`SectionForm::advance`], which checks the step and
+//! answers with the next step to show and a [`WizardState`] in
+//! [`FormResponse::extra_data`]. The client sends that back with the next step,
+//! so earlier steps aren't lost if the page is reloaded in between. After the
+//! last step, [`StepOutcome::Complete`] has the values of every step, to handle
+//! like a single-page form.
+//!
+//! The state keeps the form's own `extra_data`, such as the id of what it
+//! edits, and puts it back in the completed submission.
+//!
+//! `extra_data` comes back from the client, so the state is checked again
+//! before completing. Sign or encrypt it if the values must not be tampered
+//! with between steps. Passwords and Turnstile tokens are never kept in it,
+//! so put them on the last step.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::stela::{
+    validation::secret_names, FormCallData, FormInput, FormResponse, FormStep, FormValue,
+    SectionForm,
+};
+
+/// Progress through a multi-step form, carried in `extra_data`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct WizardState {
+    /// Index of the step to show next.
+    pub step: usize,
+    /// Values submitted with earlier steps, by input `name`, without
+    /// passwords and Turnstile tokens.
+    pub fields: HashMap<String, FormValue>,
+    /// [`SectionForm::extra_data`] of the form, which the state replaces
+    /// between steps.
+    #[serde(default)]
+    pub extra_data: Option<String>,
+}
+
+impl WizardState {
+    /// Read the state from a submission's `extra_data`. Anything else is the
+    /// form's own `extra_data`, from the first step, and starts over.
+    pub fn decode(extra_data: Option<&str>) -> Self {
+        extra_data
+            .and_then(|extra_data| serde_json::from_str(extra_data).ok())
+            .unwrap_or_else(|| Self {
+                extra_data: extra_data.map(str::to_string),
+                ..Self::default()
+            })
+    }
+
+    /// The state as `extra_data`.
+    pub fn encode(&self) -> String {
+        serde_json::to_string(self).expect("wizard state is always serializable")
+    }
+
+    /// Keep the values submitted for `step`, replacing any from an earlier
+    /// visit to it, and move on to the step after it. Passwords and Turnstile
+    /// tokens are left out, since the state is sent to the client.
+    pub fn record(&mut self, step: &FormStep, index: usize, data: &FormCallData) {
+        let secrets = secret_names(&step.inputs);
+        for name in step.input_names() {
+            if secrets.contains(&name) {
+                self.fields.remove(name);
+                continue;
+            }
+            match data.fields.get(name) {
+                Some(value) => self.fields.insert(name.to_string(), value.clone()),
+                None => self.fields.remove(name),
+            };
+        }
+        self.step = index + 1;
+    }
+
+    /// `data` with the values of earlier steps added, and the form's own
+    /// `extra_data` back.
+    pub fn merged(&self, data: &FormCallData) -> FormCallData {
+        let mut merged = data.clone();
+        for (name, value) in &self.fields {
+            merged
+                .fields
+                .entry(name.clone())
+                .or_insert_with(|| value.clone());
+        }
+        merged.step = None;
+        merged.extra_data = self.extra_data.clone();
+        merged
+    }
+}
+
+/// What to do with a submitted step.
+#[derive(Debug)]
+pub enum StepOutcome {
+    /// Send this back: errors on the step, or the next step to show.
+    Respond(Box<FormResponse>),
+    /// Every step is done and valid. Handle this submission of the whole
+    /// form.
+    Complete(FormCallData),
+}
+
+impl FormStep {
+    /// Names of the inputs on this step, including nested ones.
+    pub fn input_names(&self) -> Vec<&str> {
+        fn collect<'a>(inputs: &'a [FormInput], names: &mut Vec<&'a str>) {
+            for input in inputs {
+                match input {
+                    FormInput::Subsection(subsection) => collect(&subsection.inputs, names),
+                    FormInput::Conditional(conditional) => collect(&conditional.inputs, names),
+                    FormInput::Tabs(tabs) => {
+                        for tab in &tabs.tabs {
+                            collect(std::slice::from_ref(&tab.input), names);
+                        }
+                    }
+                    _ => names.extend(input.name()),
+                }
+            }
+        }
+        let mut names = Vec::new();
+        collect(&self.inputs, &mut names);
+        names
+    }
+}
+
+impl SectionForm {
+    /// Index of the step with the input named `name`, if it's on a step.
+    pub fn step_of(&self, name: &str) -> Option<usize> {
+        self.steps
+            .iter()
+            .position(|step| step.input_names().contains(&name))
+    }
+
+    /// Check a submission of a multi-step form and decide what comes next.
+    ///
+    /// A submission without [`FormCallData::step`], or of the last step,
+    /// completes the form if every step is valid. Otherwise the response shows
+    /// the first step with an error.
+    pub fn advance(&self, data: &FormCallData) -> StepOutcome {
+        let mut state = WizardState::decode(data.extra_data.as_deref());
+        if let Some(index) = data.step {
+            let response = self.validate_step(index, data);
+            if response.has_errors() {
+                return StepOutcome::Respond(Box::new(response.with_step(index)));
+            }
+            state.record(&self.steps[index], index, data);
+            if state.step < self.steps.len() {
+                let response = FormResponse::default()
+                    .with_step(state.step)
+                    .with_extra_data(state.encode());
+                return StepOutcome::Respond(Box::new(response));
+            }
+        }
+
+        let merged = state.merged(data);
+        let mut response = self.validate(&merged);
+        if !response.has_errors() {
+            return StepOutcome::Complete(merged);
+        }
+        let step = response
+            .field_errors
+            .keys()
+            .filter_map(|name| self.step_of(name))
+            .min();
+        if let Some(step) = step.or(data.step) {
+            state.step = step;
+            response = response.with_step(step).with_extra_data(state.encode());
+        }
+        StepOutcome::Respond(Box::new(response))
+    }
+}
@@ -0,0 +1,159 @@
+//! Accessibility audit finds missing names, alt text, labels, and heading jumps.
+
+use std::sync::Arc;
+
+use anaso_site_api_models::stela::{lint::LintCode, testing::fixtures, *};
+
+fn codes(lints: Vec<lint::Lint>) -> Vec<(String, LintCode)> {
+    lints
+        .into_iter()
+        .map(|lint| (lint.path, lint.code))
+        .collect()
+}
+
+#[test]
+fn fixtures_are_accessible() {
+    assert_eq!(fixtures::feed_page().a11y_audit(), []);
+    assert_eq!(fixtures::profile_page().a11y_audit(), []);
+    assert_eq!(fixtures::navbar().a11y_audit(), []);
+}
+
+#[test]
+fn icon_only_motion_needs_a_label() {
+    let mut navbar = fixtures::navbar();
+    navbar.right_side_motions[1].title = None;
+    assert_eq!(
+        codes(navbar.a11y_audit()),
+        [(
+            "right_side_motions[1]".to_string(),
+            LintCode::MissingAccessibleName
+        )],
+    );
+
+    navbar.right_side_motions[1].aria_label = Some("Sciigoj".to_string());
+    assert_eq!(navbar.a11y_audit(), []);
+}
+
+#[test]
+fn images_need_alt_text() {
+    let mut page = fixtures::feed_page();
+    let hero = Arc::get_mut(page.hero.as_mut().unwrap()).unwrap();
+    hero.primary_image_light.as_mut().unwrap().alt = None;
+    hero.primary_image_fallback_text = None;
+    // Backgrounds are decorative.
+    hero.background_image_light.as_mut().unwrap().alt = None;
+
+    assert_eq!(
+        codes(page.a11y_audit()),
+        [(
+            "hero.primary_image_light".to_string(),
+            LintCode::MissingImageAlt
+        )],
+    );
+}
+
+#[test]
+fn tiles_need_a_name() {
+    let mut page = fixtures::profile_page();
+    page.sections[1].section = Section::Tiles(Arc::new(SectionTiles {
+        tiles: vec![Tile {
+            header: None,
+            subheader: None,
+            motion: None,
+            image: None,
+            body_text: None,
+        }],
+        layout: TilesLayout::Grid,
+    }));
+    assert_eq!(
+        codes(page.a11y_audit()),
+        [(
+            "sections[1].section.Tiles.tiles[0]".to_string(),
+            LintCode::MissingAccessibleName
+        )],
+    );
+}
+
+#[test]
+fn pages_without_a_hero_title_start_at_section_titles() {
+    let mut page = fixtures::profile_page();
+    page.hero = None;
+    assert!(page.sections[0].title.is_some());
+    assert_eq!(page.a11y_audit(), []);
+}
+
+#[test]
+fn form_inputs_need_labels() {
+    let mut form = fixtures::nested_form();
+    form.inputs[0] = FormInput::Text(Arc::new(FormInputText {
+        title: None,
+        name: Some("username".to_string()),
+        initial_value: None,
+        length_min: None,
+        length_max: None,
+        esperanto: false,
+        filter: None,
+    }));
+    let mut page = fixtures::feed_page();
+    page.sections = vec![VisualSection {
+        id: None,
+        title: None,
+        bordered: None,
+        section: Section::Form(Arc::new(form)),
+    }];
+    assert_eq!(
+        codes(page.a11y_audit()),
+        [(
+            "sections[0].section.Form.inputs[0].Text".to_string(),
+            LintCode::MissingInputLabel
+        )],
+    );
+}
+
+#[test]
+fn password_confirmation_needs_a_label() {
+    let mut form = fixtures::signup_form();
+    let mut page = fixtures::feed_page();
+    page.sections = vec![VisualSection {
+        id: None,
+        title: None,
+        bordered: None,
+        section: Section::Form(Arc::new(fixtures::signup_form())),
+    }];
+    assert_eq!(page.a11y_audit(), []);
+
+    form.inputs[1] = FormInput::Password(Arc::new(FormInputPassword {
+        title: Some("Pasvorto".to_string()),
+        name: "password".to_string(),
+        length_min: None,
+        length_max: None,
+        confirm_name: Some("password_confirm".to_string()),
+        confirm_title: None,
+        autocomplete: None,
+    }));
+    page.sections[0].section = Section::Form(Arc::new(form));
+    assert_eq!(
+        codes(page.a11y_audit()),
+        [(
+            "sections[0].section.Form.inputs[1].Password.confirm_title".to_string(),
+            LintCode::MissingInputLabel
+        )],
+    );
+}
+
+#[test]
+fn turnstile_picks_its_own_language() {
+    let mut form = fixtures::nested_form();
+    let FormInput::CfTurnstile(turnstile) = &mut form.inputs[3] else {
+        panic!("expected the Turnstile widget");
+    };
+    Arc::get_mut(turnstile).unwrap().language = None;
+    let mut page = fixtures::feed_page();
+    page.sections = vec![VisualSection {
+        id: None,
+        title: None,
+        bordered: None,
+        section: Section::Form(Arc::new(form)),
+    }];
+    assert_eq!(page.a11y_audit(), []);
+}
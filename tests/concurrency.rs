@@ -0,0 +1,259 @@
+//! Stale submissions of edit forms are merged into the current values, or
+//! rejected with a conflict.
+
+use std::collections::HashMap;
+
+use anaso_site_api_models::stela::{
+    concurrency::{merge_text, MergeConflict},
+    testing::fixtures,
+    *,
+};
+
+const LOADED: &str = "\"user-zamenhof-v6\"";
+
+fn values(fields: &[(&str, FormValue)]) -> HashMap<String, FormValue> {
+    fields
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.clone()))
+        .collect()
+}
+
+fn text(value: &str) -> FormValue {
+    FormValue::Text(value.to_string())
+}
+
+fn option(value: &str) -> FormValue {
+    FormValue::Option(value.to_string())
+}
+
+fn base() -> HashMap<String, FormValue> {
+    values(&[
+        ("username", text("zamenhof")),
+        ("bio", text("Mi kreis lingvon.\nMi loĝis en Varsovio.\n")),
+        ("level", option("flua")),
+    ])
+}
+
+fn submit(fields: HashMap<String, FormValue>) -> FormCallData {
+    FormCallData {
+        form_name: "profile_settings".to_string(),
+        extra_data: Some("user:zamenhof".to_string()),
+        fields,
+        step: None,
+        etag: Some(LOADED.to_string()),
+    }
+}
+
+#[test]
+fn stale_submissions_are_caught() {
+    let form = fixtures::nested_form();
+    let current = form.etag.as_deref().unwrap();
+    let mut data = submit(base());
+    assert!(data.is_stale(current));
+    data.etag = form.etag.clone();
+    assert!(!data.is_stale(current));
+    // Clients that don't know about etags aren't checked.
+    data.etag = None;
+    assert!(!data.is_stale(current));
+}
+
+#[test]
+fn changes_on_both_sides_merge() {
+    let form = fixtures::nested_form();
+    let mut ours = base();
+    ours.insert("username".to_string(), text("ludoviko"));
+    ours.insert(
+        "bio".to_string(),
+        text("Mi kreis Esperanton.\nMi loĝis en Varsovio.\n"),
+    );
+    ours.insert("show_email".to_string(), FormValue::Bool(true));
+    let mut current = base();
+    current.insert("level".to_string(), option("progresanto"));
+    current.insert(
+        "bio".to_string(),
+        text("Mi kreis lingvon.\nMi loĝis en Varsovio.\nMi estis okulisto.\n"),
+    );
+
+    let merged = form.merge_stale(&submit(ours), &base(), &current).unwrap();
+    assert_eq!(merged.etag, form.etag);
+    assert_eq!(
+        merged.fields,
+        values(&[
+            ("username", text("ludoviko")),
+            (
+                "bio",
+                text("Mi kreis Esperanton.\nMi loĝis en Varsovio.\nMi estis okulisto.\n")
+            ),
+            ("level", option("progresanto")),
+            ("show_email", FormValue::Bool(true)),
+        ])
+    );
+}
+
+#[test]
+fn conflicts_carry_the_current_values() {
+    let form = fixtures::nested_form();
+    let mut ours = base();
+    ours.insert("level".to_string(), option("komencanto"));
+    ours.insert("bio".to_string(), text("Mi kreis lingvon!\n"));
+    let mut current = base();
+    current.insert("level".to_string(), option("progresanto"));
+    current.insert(
+        "bio".to_string(),
+        text("Mi kreis lingvon kaj gramatikon.\n"),
+    );
+
+    let data = submit(ours);
+    let conflict = form.merge_stale(&data, &base(), &current).unwrap_err();
+    assert_eq!(conflict.etag, form.etag);
+    assert_eq!(conflict.fields, current);
+    assert_eq!(conflict.conflicting, ["bio", "level"]);
+
+    let response = FormResponse::default()
+        .preserving(&form, &data)
+        .with_conflict(conflict.clone());
+    assert!(response.has_errors());
+    assert_eq!(response.form_errors[0].code, FormErrorCode::Conflict);
+    assert_eq!(
+        response.field_errors["level"][0].code,
+        FormErrorCode::Conflict
+    );
+    assert_eq!(response.fields, data.fields);
+    assert_eq!(response.conflict, Some(conflict));
+}
+
+#[test]
+fn unchanged_values_are_not_conflicts() {
+    let form = fixtures::nested_form();
+    let mut ours = base();
+    ours.insert("level".to_string(), option("progresanto"));
+    // Cleared on both sides.
+    ours.remove("bio");
+    let mut current = ours.clone();
+    current.remove("username");
+
+    let merged = form.merge_stale(&submit(ours), &base(), &current).unwrap();
+    assert_eq!(merged.fields, current);
+}
+
+#[test]
+fn values_compare_by_input_kind() {
+    let form = fixtures::nested_form();
+    let mut stored = base();
+    stored.insert("show_email".to_string(), FormValue::Bool(true));
+    // An older client submits the unchanged checkbox as form data.
+    let mut ours = base();
+    ours.insert("show_email".to_string(), text("on"));
+    let mut current = base();
+    current.insert("show_email".to_string(), FormValue::Bool(false));
+
+    let merged = form.merge_stale(&submit(ours), &stored, &current).unwrap();
+    assert_eq!(merged.fields["show_email"], FormValue::Bool(false));
+}
+
+#[test]
+fn submissions_without_an_etag_are_not_checked() {
+    let data = FormCallData {
+        etag: None,
+        ..submit(base())
+    };
+    assert!(!data.is_stale(LOADED));
+    assert!(!data.is_stale("\"user-zamenhof-v7\""));
+}
+
+#[test]
+fn tokens_are_not_merged() {
+    let form = fixtures::nested_form();
+    let mut ours = base();
+    ours.insert("cf_turnstile".to_string(), text("nova-ĵetono"));
+    let mut current = base();
+    current.insert("cf_turnstile".to_string(), text("malnova-ĵetono"));
+
+    // The submitted token is kept as is, to be verified.
+    let merged = form
+        .merge_stale(&submit(ours.clone()), &base(), &current)
+        .unwrap();
+    assert_eq!(merged.fields["cf_turnstile"], text("nova-ĵetono"));
+
+    ours.insert("level".to_string(), option("komencanto"));
+    current.insert("level".to_string(), option("progresanto"));
+    let conflict = form
+        .merge_stale(&submit(ours), &base(), &current)
+        .unwrap_err();
+    assert_eq!(conflict.conflicting, ["level"]);
+    assert!(!conflict.fields.contains_key("cf_turnstile"));
+}
+
+#[test]
+fn text_merges() {
+    let base = "a\nb\nc\nd\n";
+    assert_eq!(
+        merge_text(base, "A\nb\nc\nd\n", "a\nb\nc\nD\n").unwrap(),
+        "A\nb\nc\nD\n"
+    );
+    assert_eq!(
+        merge_text(base, "a\nc\nd\n", "a\nb\nc\nd\ne\n").unwrap(),
+        "a\nc\nd\ne\n"
+    );
+    assert_eq!(
+        merge_text(base, "a\nB\nc\nd\n", "a\nB\nc\nd\n").unwrap(),
+        "a\nB\nc\nd\n"
+    );
+    assert_eq!(merge_text("", "nova\n", "").unwrap(), "nova\n");
+    assert_eq!(
+        merge_text(base, "a\nB\nc\nd\n", "a\nb!\nc\nd\n").unwrap_err(),
+        MergeConflict {
+            base: "b\n".to_string(),
+            ours: "B\n".to_string(),
+            theirs: "b!\n".to_string(),
+        }
+    );
+    // Insertions at the same place can't be ordered.
+    assert!(merge_text(base, "a\nb\nx\nc\nd\n", "a\nb\ny\nc\nd\n").is_err());
+}
+
+#[test]
+fn long_texts_merge_without_comparing_every_line() {
+    let lines: Vec<String> = (0..100_000).map(|i| format!("linio {i}\n")).collect();
+    let edit = |changes: &[usize]| {
+        let mut lines = lines.clone();
+        for &i in changes {
+            lines[i] = format!("ŝanĝita {i}\n");
+        }
+        lines.concat()
+    };
+    let base = lines.concat();
+
+    // Only the lines between the common start and end are compared.
+    assert_eq!(
+        merge_text(&base, &edit(&[10]), &edit(&[90_000])).unwrap(),
+        edit(&[10, 90_000])
+    );
+
+    // Changes far apart on one side leave too many lines to compare.
+    let conflict = merge_text(&base, &edit(&[0, 99_999]), &edit(&[50_000])).unwrap_err();
+    assert_eq!(conflict.base, base);
+}
+
+#[test]
+fn conflicts_roundtrip() {
+    let response: FormResponse = serde_json::from_str(
+        r#"{
+            "conflict": {
+                "etag": "\"v8\"",
+                "fields": {"level": {"Option": "flua"}},
+                "conflicting": ["level"]
+            }
+        }"#,
+    )
+    .unwrap();
+    let conflict = response.conflict.unwrap();
+    assert_eq!(conflict.etag.as_deref(), Some("\"v8\""));
+    assert_eq!(conflict.fields["level"], option("flua"));
+
+    let data: FormCallData = serde_json::from_str(
+        r#"{"form_name": "profile_settings", "extra_data": null, "fields": {}}"#,
+    )
+    .unwrap();
+    assert_eq!(data.etag, None);
+}
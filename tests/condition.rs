@@ -0,0 +1,276 @@
+//! Conditions on form inputs are evaluated the same way for display and for
+//! validation.
+
+use std::sync::Arc;
+
+use anaso_site_api_models::stela::{
+    from_form::{FormDefinition, FromFormCallData},
+    lint::LintCode,
+    *,
+};
+
+fn checked(name: &str) -> FormCondition {
+    FormCondition::Checked(Arc::new(FormConditionInput {
+        name: name.to_string(),
+    }))
+}
+
+fn equals(name: &str, value: &str) -> FormCondition {
+    FormCondition::Equals(Arc::new(FormConditionValue {
+        name: name.to_string(),
+        value: value.to_string(),
+    }))
+}
+
+fn text_input(name: &str) -> FormInput {
+    FormInput::Text(Arc::new(FormInputText {
+        title: Some(name.to_string()),
+        name: Some(name.to_string()),
+        initial_value: None,
+        length_min: None,
+        length_max: Some(8),
+        esperanto: false,
+        filter: None,
+    }))
+}
+
+/// A post form where the content warning text shows when the box is ticked,
+/// and the source is required for news.
+fn post_form() -> SectionForm {
+    SectionForm {
+        header: None,
+        subheader: None,
+        form_name: "post".to_string(),
+        extra_data: None,
+        noscript_text: None,
+        inputs: vec![
+            FormInput::Checkbox(Arc::new(FormInputCheckbox {
+                title: Some("Enhavaverto".to_string()),
+                name: "has_warning".to_string(),
+                default_checked: None,
+            })),
+            FormInput::Conditional(Arc::new(FormInputConditional {
+                show_when: Some(checked("has_warning")),
+                require_when: Some(checked("has_warning")),
+                inputs: vec![text_input("warning")],
+            })),
+            FormInput::Radio(Arc::new(FormInputRadio {
+                title: Some("Speco".to_string()),
+                name: "kind".to_string(),
+                initial_index: None,
+                options: ["novaĵo", "opinio"]
+                    .into_iter()
+                    .map(|value| RadioButton {
+                        value: value.to_string(),
+                        title: Some(value.to_string()),
+                    })
+                    .collect(),
+            })),
+            FormInput::Conditional(Arc::new(FormInputConditional {
+                show_when: None,
+                require_when: Some(equals("kind", "novaĵo")),
+                inputs: vec![text_input("source")],
+            })),
+        ],
+        steps: Vec::new(),
+        initial_step: None,
+        step_progress: StepProgress::Unknown,
+        autosave: None,
+        etag: None,
+    }
+}
+
+fn submit(fields: &[(&str, &str)]) -> FormCallData {
+    FormCallData::from_entries(
+        "post",
+        None,
+        fields
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string())),
+    )
+}
+
+#[test]
+fn conditions_hold() {
+    let form = post_form();
+    let data = submit(&[
+        ("has_warning", "on"),
+        ("kind", "opinio"),
+        ("source", "ana.so"),
+    ]);
+
+    assert!(checked("has_warning").holds(&form, &data));
+    assert!(equals("kind", "opinio").holds(&form, &data));
+    assert!(!equals("kind", "novaĵo").holds(&form, &data));
+    assert!(FormCondition::Contains(Arc::new(FormConditionValue {
+        name: "source".to_string(),
+        value: ".so".to_string(),
+    }))
+    .holds(&form, &data));
+    assert!(
+        FormCondition::Any(vec![equals("kind", "novaĵo"), checked("has_warning")])
+            .holds(&form, &data)
+    );
+    assert!(
+        !FormCondition::All(vec![equals("kind", "novaĵo"), checked("has_warning")])
+            .holds(&form, &data)
+    );
+    assert!(FormCondition::Not(Arc::new(equals("kind", "novaĵo"))).holds(&form, &data));
+    assert!(FormCondition::Filled(Arc::new(FormConditionInput {
+        name: "source".to_string(),
+    }))
+    .holds(&form, &data));
+    // An unchecked checkbox isn't submitted at all.
+    assert!(!checked("has_warning").holds(&form, &submit(&[])));
+    assert!(!FormCondition::Unknown.holds(&form, &submit(&[])));
+    assert!(!FormCondition::Not(Arc::new(FormCondition::Unknown)).holds(&form, &submit(&[])));
+}
+
+#[test]
+fn hidden_inputs_are_skipped() {
+    let form = post_form();
+    assert!(!form.is_shown("warning", &submit(&[])));
+    assert!(form.is_shown("warning", &submit(&[("has_warning", "on")])));
+    assert!(form.is_shown("source", &submit(&[])));
+    assert!(!form.is_shown("missing", &submit(&[])));
+
+    // Too long, but hidden.
+    let response = form.validate(&submit(&[("warning", "tro longa teksto")]));
+    assert!(!response.has_errors());
+
+    let response = form.validate(&submit(&[("has_warning", "on")]));
+    assert_eq!(
+        response.field_errors["warning"][0].code,
+        FormErrorCode::Required
+    );
+    let response = form.validate(&submit(&[("has_warning", "on"), ("warning", "tro longa")]));
+    assert_eq!(response.field_errors.len(), 1);
+    assert_eq!(
+        response.field_errors["warning"][0].code,
+        FormErrorCode::TooLong
+    );
+
+    let response = form.validate(&submit(&[("kind", "novaĵo")]));
+    assert_eq!(
+        response.field_errors["source"][0].code,
+        FormErrorCode::Required
+    );
+    assert!(!form
+        .validate(&submit(&[("kind", "novaĵo"), ("source", "ana.so")]))
+        .has_errors());
+}
+
+#[derive(Debug, PartialEq, FromFormCallData)]
+#[form(name = "post")]
+struct Post {
+    #[form(title = "Enhavaverto")]
+    has_warning: bool,
+    #[form(title = "Averto", show_if = "has_warning", require_if = "has_warning")]
+    warning: Option<String>,
+    #[form(input = "radio", title = "Speco", options = ["novaĵo", "opinio"])]
+    kind: String,
+    #[form(title = "Fonto", require_if = "kind = novaĵo")]
+    source: Option<String>,
+}
+
+#[test]
+fn derived_conditions() {
+    let form = Post::section_form();
+    assert!(form.lint().is_empty());
+    assert!(matches!(
+        &form.inputs[1],
+        FormInput::Conditional(conditional)
+            if matches!(conditional.show_when, Some(FormCondition::Checked(_)))
+    ));
+
+    // Hidden values read as not submitted.
+    let data = submit(&[("warning", "ignorita"), ("kind", "opinio")]);
+    assert_eq!(
+        Post::from_form_call_data(&data).unwrap(),
+        Post {
+            has_warning: false,
+            warning: None,
+            kind: "opinio".to_string(),
+            source: None,
+        }
+    );
+
+    let data = submit(&[("kind", "novaĵo")]);
+    let response = Post::from_form_call_data(&data).unwrap_err();
+    assert_eq!(
+        response.field_errors["source"][0].code,
+        FormErrorCode::Required
+    );
+}
+
+#[test]
+fn conditions_on_unknown_inputs_are_linted() {
+    let mut form = post_form();
+    form.inputs[1] = FormInput::Conditional(Arc::new(FormInputConditional {
+        show_when: Some(FormCondition::Not(Arc::new(checked("has_warnings")))),
+        require_when: None,
+        inputs: vec![text_input("warning")],
+    }));
+    let lints = form.lint();
+    assert_eq!(lints.len(), 1);
+    assert_eq!(lints[0].code, LintCode::ConditionUnknownInput);
+    assert_eq!(lints[0].path, "inputs[1].Conditional.show_when");
+    assert_eq!(lints[0].code.code(), "STL0012");
+}
+
+#[test]
+fn unknown_conditions_show_inputs() {
+    // Conditions from newer APIs, and malformed ones, are dropped, so the
+    // inputs are shown and not required.
+    let conditional: FormInputConditional = serde_json::from_str(
+        r#"{
+            "show_when": {"Matches": {"name": "kind", "pattern": ".*"}},
+            "require_when": {"Equals": {"name": "kind"}},
+            "inputs": [{"Checkbox": {"name": "a"}}]
+        }"#,
+    )
+    .unwrap();
+    assert!(conditional.show_when.is_none());
+    assert!(conditional.require_when.is_none());
+    assert_eq!(conditional.inputs.len(), 1);
+
+    let condition: FormCondition = serde_json::from_str(r#""Unknown""#).unwrap();
+    assert!(matches!(condition, FormCondition::Unknown));
+}
+
+#[test]
+fn unknown_conditions_never_require_inputs() {
+    let mut form = post_form();
+    let unknown = || FormCondition::Unknown;
+    let negated = || FormCondition::Not(Arc::new(FormCondition::Unknown));
+    form.inputs.truncate(1);
+    form.inputs.extend([
+        FormInput::Conditional(Arc::new(FormInputConditional {
+            show_when: Some(unknown()),
+            require_when: Some(unknown()),
+            inputs: vec![text_input("warning")],
+        })),
+        FormInput::Conditional(Arc::new(FormInputConditional {
+            show_when: Some(negated()),
+            require_when: Some(negated()),
+            inputs: vec![text_input("source")],
+        })),
+        FormInput::Conditional(Arc::new(FormInputConditional {
+            show_when: Some(FormCondition::Any(vec![checked("has_warning"), unknown()])),
+            require_when: Some(FormCondition::All(vec![unknown(), checked("has_warning")])),
+            inputs: vec![text_input("kind")],
+        })),
+    ]);
+
+    let data = submit(&[]);
+    for name in ["warning", "source", "kind"] {
+        assert!(form.is_shown(name, &data), "{name}");
+    }
+    assert!(!form.validate(&data).has_errors());
+
+    // Known conditions still decide where they can.
+    let data = submit(&[("has_warning", "on")]);
+    assert!(FormCondition::Any(vec![checked("has_warning"), unknown()]).holds(&form, &data));
+    assert!(!FormCondition::All(vec![equals("kind", "x"), unknown()]).holds(&form, &data));
+    assert!(!form.validate(&data).has_errors());
+}
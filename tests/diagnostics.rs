@@ -0,0 +1,77 @@
+//! Diagnostics point at exactly what lenient parsing dropped.
+
+use anaso_site_api_models::stela::{
+    diagnostics::{self, Diagnostic, Fallback},
+    testing::fixtures,
+    *,
+};
+
+#[test]
+fn valid_page_has_no_diagnostics() {
+    let json = serde_json::to_string(&fixtures::feed_page()).unwrap();
+    let (page, diagnostics) = Page::from_json_with_diagnostics(&json);
+    assert_eq!(diagnostics, []);
+    assert_eq!(page.sections.len(), 4);
+}
+
+#[test]
+fn paths_point_at_the_fallback() {
+    let mut json = serde_json::to_value(fixtures::feed_page()).unwrap();
+    json["hero"] = "broken".into();
+    json["sections"][3]["section"]["Post"]["motions_tr"][0]["title"] = 5.into();
+    json["sections"][3]["section"]["Post"]["motions_br"] = serde_json::json!([7]);
+    json["sections"][1] = serde_json::json!(false);
+
+    let (page, diagnostics) = Page::from_json_with_diagnostics(&json.to_string());
+    assert!(page.hero.is_none());
+    assert_eq!(page.sections.len(), 3);
+
+    let found = diagnostics
+        .iter()
+        .map(|d| (d.path.as_str(), d.fallback))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        found,
+        [
+            ("hero", Fallback::Default),
+            ("sections[1]", Fallback::Skipped),
+            ("sections[3].section.Post.motions_br[0]", Fallback::Skipped),
+            (
+                "sections[3].section.Post.motions_tr[0].title",
+                Fallback::Default
+            ),
+        ],
+    );
+}
+
+#[test]
+fn invalid_json_is_reported_at_the_root() {
+    let (page, diagnostics) = Page::from_json_with_diagnostics("{");
+    assert!(page.sections.is_empty());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].path, "");
+
+    let (_, diagnostics) = Page::from_json_with_diagnostics("[1]");
+    assert_eq!(diagnostics.len(), 1);
+}
+
+#[test]
+fn from_json_works_for_any_type() {
+    let (navbar, diagnostics) = diagnostics::from_json::<Navbar>(
+        r#"{
+            "left_side_motion": {"Href": {"uri": "/"}},
+            "left_side_icon_image": {"id": "logo"},
+            "right_side_motions": [{"icon": "Bell", "motion": "Unknown"}, null]
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(navbar.right_side_motions.len(), 1);
+    assert_eq!(
+        diagnostics,
+        [Diagnostic {
+            path: "right_side_motions[1]".to_string(),
+            error: "invalid type: null, expected struct VisualMotion".to_string(),
+            fallback: Fallback::Skipped,
+        }],
+    );
+}
@@ -0,0 +1,196 @@
+//! Drafts restore what was typed into a form, but only into the same version
+//! of it.
+
+use anaso_site_api_models::stela::{
+    draft::{DraftRejection, FormDraft},
+    testing::fixtures,
+    *,
+};
+
+const NOW: u64 = 1_792_000_000_000;
+const DAY: u64 = 24 * 60 * 60 * 1000;
+
+fn submit(form: &str, fields: &[(&str, &str)]) -> FormCallData {
+    FormCallData::from_entries(
+        form,
+        None,
+        fields
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string())),
+    )
+}
+
+fn autosave() -> Option<FormAutosave> {
+    Some(FormAutosave {
+        version: 3,
+        max_age: None,
+    })
+}
+
+#[test]
+fn draft_keys() {
+    let mut form = fixtures::nested_form();
+    assert_eq!(form.draft_key(), None);
+    form.autosave = autosave();
+    assert_eq!(
+        form.draft_key().as_deref(),
+        Some(r#"stela-draft:["profile_settings","user:zamenhof"]"#)
+    );
+    form.extra_data = None;
+    assert_eq!(
+        form.draft_key().as_deref(),
+        Some(r#"stela-draft:["profile_settings",null]"#)
+    );
+    assert!(FormDraft::capture(&fixtures::signup_form(), &submit("signup", &[]), NOW).is_none());
+}
+
+#[test]
+fn drafts_restore_every_step() {
+    let form = fixtures::community_wizard();
+    let mut data = submit(
+        "create_community",
+        &[
+            ("name", "Verdaj Steloj"),
+            ("slug", "verdaj-steloj"),
+            ("description", "Longa priskribo, **ne perdu ĝin**."),
+        ],
+    );
+    data.step = Some(1);
+    let draft = FormDraft::capture(&form, &data, NOW).unwrap();
+    assert_eq!(draft.version, 1);
+    assert_eq!(draft.step, Some(1));
+
+    // Drafts are stored as JSON by the client.
+    let json = serde_json::to_string(&draft).unwrap();
+    let draft: FormDraft = serde_json::from_str(&json).unwrap();
+
+    let mut restored = fixtures::community_wizard();
+    restored.restore_draft(&draft, NOW + DAY).unwrap();
+    assert_eq!(restored.initial_step, Some(1));
+    let FormInput::Markdown(description) = restored.input("description").unwrap() else {
+        panic!("expected the description");
+    };
+    assert_eq!(
+        description.initial_value.as_deref(),
+        Some("Longa priskribo, **ne perdu ĝin**.")
+    );
+    // The draft has nothing for the public checkbox, so it stays checked.
+    let FormInput::Checkbox(public) = restored.input("public").unwrap() else {
+        panic!("expected the checkbox");
+    };
+    assert_eq!(public.default_checked, Some(true));
+}
+
+#[test]
+fn drafts_keep_default_checked_boxes() {
+    let public = |form: &SectionForm| {
+        let Some(FormInput::Checkbox(public)) = form.input("public") else {
+            panic!("expected the checkbox");
+        };
+        public.default_checked
+    };
+    let mut draft = FormDraft::capture(
+        &fixtures::community_wizard(),
+        &submit("create_community", &[("name", "Verdaj Steloj")]),
+        NOW,
+    )
+    .unwrap();
+    let mut restored = fixtures::community_wizard();
+    restored.restore_draft(&draft, NOW).unwrap();
+    assert_eq!(public(&restored), Some(true));
+
+    // Unchecking it is saved as false.
+    draft
+        .fields
+        .insert("public".to_string(), FormValue::Bool(false));
+    let mut restored = fixtures::community_wizard();
+    restored.restore_draft(&draft, NOW).unwrap();
+    assert_eq!(public(&restored), Some(false));
+}
+
+#[test]
+fn only_compatible_drafts_are_restored() {
+    let form = fixtures::community_wizard();
+    let data = submit("create_community", &[("name", "Verdaj Steloj")]);
+    let draft = FormDraft::capture(&form, &data, NOW).unwrap();
+
+    let mut expired = fixtures::community_wizard();
+    assert_eq!(
+        expired.restore_draft(&draft, NOW + 8 * DAY),
+        Err(DraftRejection::Expired)
+    );
+    let FormInput::Text(name) = expired.input("name").unwrap() else {
+        panic!("expected the name");
+    };
+    assert_eq!(name.initial_value, None);
+
+    let mut newer = fixtures::community_wizard();
+    newer.autosave = Some(FormAutosave {
+        version: 2,
+        max_age: None,
+    });
+    let rejection = newer.restore_draft(&draft, NOW).unwrap_err();
+    assert_eq!(rejection, DraftRejection::Version { draft: 1, form: 2 });
+    assert_eq!(
+        rejection.to_string(),
+        "draft is from version 1, form is version 2"
+    );
+
+    let mut disabled = fixtures::community_wizard();
+    disabled.autosave = None;
+    assert_eq!(
+        disabled.restore_draft(&draft, NOW),
+        Err(DraftRejection::Disabled)
+    );
+}
+
+#[test]
+fn drafts_skip_secrets_and_stale_values() {
+    let mut form = fixtures::signup_form();
+    form.autosave = autosave();
+    let data = FormCallData::from_entries(
+        "signup",
+        None,
+        [
+            ("email", "zamenhof@ana.so"),
+            ("password", "sekreta pasvorto"),
+            ("password_confirm", "sekreta pasvorto"),
+            ("country", "xx"),
+            ("years", "12.5"),
+            ("birthday", "1987-07-26"),
+            ("interests", "muziko"),
+            ("interests", "vortludoj"),
+        ]
+        .map(|(name, value)| (name.to_string(), value.to_string())),
+    );
+    let draft = FormDraft::capture(&form, &data, NOW).unwrap();
+    assert!(!draft.fields.contains_key("password"));
+    assert!(!draft.fields.contains_key("password_confirm"));
+
+    form.restore_draft(&draft, NOW).unwrap();
+    let input = |name| form.input(name).unwrap();
+    let FormInput::Email(email) = input("email") else {
+        panic!("expected the email");
+    };
+    assert_eq!(email.initial_value.as_deref(), Some("zamenhof@ana.so"));
+    // `xx` isn't an option any more, so the initial choice stays.
+    let FormInput::Select(country) = input("country") else {
+        panic!("expected the country");
+    };
+    assert_eq!(country.initial_value.as_deref(), Some("eo"));
+    let FormInput::Number(years) = input("years") else {
+        panic!("expected the years");
+    };
+    assert_eq!(years.initial_value, Some(12.5));
+    // Typed tags are dropped, options are kept.
+    let FormInput::MultiSelect(interests) = input("interests") else {
+        panic!("expected the interests");
+    };
+    assert_eq!(interests.initial_values, ["muziko"]);
+    // Untouched inputs keep their initial values.
+    let FormInput::Url(website) = input("website") else {
+        panic!("expected the website");
+    };
+    assert_eq!(website.initial_value, None);
+    assert!(form.lint().is_empty());
+}
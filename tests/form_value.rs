@@ -0,0 +1,140 @@
+//! Typed form values stay compatible with the old string map.
+
+use anaso_site_api_models::stela::{testing::fixtures, *};
+
+fn input(form: &SectionForm, name: &str) -> FormInput {
+    form.input(name).unwrap().clone()
+}
+
+#[test]
+fn string_map_still_parses() {
+    let data: FormCallData = serde_json::from_str(
+        r#"{
+            "form_name": "profile_settings",
+            "extra_data": null,
+            "fields": {
+                "username": "zamenhof",
+                "show_email": "on",
+                "level": "flua",
+                "avatar": "avatar-123",
+                "cf_turnstile": "token"
+            }
+        }"#,
+    )
+    .unwrap();
+    let form = fixtures::nested_form();
+
+    assert_eq!(
+        data.text(&input(&form, "username")).as_deref(),
+        Some("zamenhof")
+    );
+    assert_eq!(data.checked(&input(&form, "show_email")), Some(true));
+    assert_eq!(
+        data.value(&input(&form, "level")),
+        Some(FormValue::Option("flua".to_string()))
+    );
+    assert_eq!(
+        data.value(&input(&form, "avatar")),
+        Some(FormValue::Image("avatar-123".to_string()))
+    );
+    assert_eq!(
+        data.text(&input(&form, "cf_turnstile")).as_deref(),
+        Some("token")
+    );
+    // Not submitted.
+    assert_eq!(data.value(&input(&form, "bio")), None);
+}
+
+#[test]
+fn typed_values() {
+    let data: FormCallData = serde_json::from_str(
+        r#"{
+            "form_name": "profile_settings",
+            "extra_data": null,
+            "fields": {
+                "show_email": {"Bool": true},
+                "level": {"Option": "flua"},
+                "bio": {"Bool": false},
+                "tags": {"List": ["a", {"Option": "b"}]}
+            }
+        }"#,
+    )
+    .unwrap();
+    let form = fixtures::nested_form();
+
+    assert_eq!(data.checked(&input(&form, "show_email")), Some(true));
+    assert_eq!(
+        data.value(&input(&form, "level"))
+            .as_ref()
+            .and_then(FormValue::as_option),
+        Some("flua")
+    );
+    // Doesn't fit a markdown input.
+    assert_eq!(data.text(&input(&form, "bio")), None);
+    assert_eq!(
+        data.fields["tags"].as_list(),
+        Some(&[FormValue::from("a"), FormValue::Option("b".to_string())][..])
+    );
+}
+
+#[test]
+fn unchecked_checkboxes_are_omitted() {
+    let data = FormCallData {
+        form_name: "profile_settings".to_string(),
+        extra_data: None,
+        fields: Default::default(),
+        step: None,
+        etag: None,
+    };
+    let form = fixtures::nested_form();
+    assert_eq!(data.checked(&input(&form, "show_email")), Some(false));
+}
+
+#[test]
+fn text_serializes_as_a_bare_string() {
+    assert_eq!(
+        serde_json::to_value(FormValue::from("saluton")).unwrap(),
+        serde_json::json!("saluton")
+    );
+    assert_eq!(
+        serde_json::to_value(FormValue::Bool(true)).unwrap(),
+        serde_json::json!({"Bool": true})
+    );
+}
+
+#[test]
+fn multi_select_values() {
+    let form = fixtures::signup_form();
+    let interests = input(&form, "interests");
+    let data = FormCallData::from_entries(
+        "signup",
+        None,
+        [
+            ("interests", "muziko"),
+            ("email", "ludoviko@ana.so"),
+            ("interests", "kantado"),
+            ("interests", "literaturo"),
+        ]
+        .map(|(name, value)| (name.to_string(), value.to_string())),
+    );
+    assert_eq!(data.fields["email"], "ludoviko@ana.so".into());
+    assert_eq!(
+        data.value(&interests),
+        Some(FormValue::List(vec![
+            FormValue::Option("muziko".to_string()),
+            FormValue::Text("kantado".to_string()),
+            FormValue::Option("literaturo".to_string()),
+        ]))
+    );
+
+    // A single value is a list of one, and nothing is an empty list.
+    let mut data = FormCallData::from_entries("signup", None, []);
+    assert_eq!(data.value(&interests), Some(FormValue::List(Vec::new())));
+    data.append("interests", "muziko".into());
+    assert_eq!(
+        data.value(&interests),
+        Some(FormValue::List(vec![FormValue::Option(
+            "muziko".to_string()
+        )]))
+    );
+}
@@ -0,0 +1,246 @@
+//! Deriving `FromFormCallData` reads typed structs and generates their forms.
+
+use anaso_site_api_models::stela::{
+    from_form::{FormDefinition, FromFormCallData},
+    *,
+};
+
+#[derive(Debug, PartialEq, FromFormCallData)]
+#[form(name = "signup", header = "Aliĝi", submit = "Aliĝi")]
+struct Signup {
+    #[form(
+        title = "Uzantnomo",
+        min = 3,
+        max = 32,
+        filter = "ALPHA_ASCII | NUMERIC | DASH"
+    )]
+    username: String,
+    #[form(input = "radio", title = "Nivelo", options = ["komencanto", "flua"])]
+    level: String,
+    #[form(title = "Montri retpoŝtadreson")]
+    show_email: bool,
+    #[form(input = "text", name = "age_years", title = "Aĝo")]
+    age: Option<u32>,
+    #[form(input = "markdown", title = "Biografio", max = 100)]
+    bio: Option<String>,
+}
+
+#[derive(Debug, PartialEq, FromFormCallData)]
+#[form(name = "report")]
+struct Report {
+    #[form(input = "markdown", title = "Kialo", min = 1)]
+    reason: String,
+}
+
+#[derive(Debug, PartialEq, FromFormCallData)]
+#[form(name = "account")]
+struct Account {
+    #[form(input = "email", title = "Retpoŝtadreso")]
+    email: String,
+    #[form(
+        input = "password",
+        title = "Pasvorto",
+        min = 8,
+        confirm = "password_confirm",
+        confirm_title = "Ripetu la pasvorton"
+    )]
+    password: String,
+    #[form(input = "select", title = "Lando", options = ["eo", "fr"])]
+    country: String,
+    #[form(title = "Jaroj", min = 0, max = 99.5, step = 0.5)]
+    years: Option<f64>,
+    #[form(input = "date", title = "Naskiĝdato")]
+    birthday: Option<String>,
+    #[form(input = "url", title = "Retejo")]
+    website: Option<String>,
+    #[form(
+        input = "multiselect",
+        title = "Etikedoj",
+        options = ["novaĵoj", "lernado"],
+        max = 2,
+        custom,
+        filter = "ALPHA_EO"
+    )]
+    tags: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, FromFormCallData)]
+enum AnyForm {
+    Signup(Signup),
+    Report(Report),
+}
+
+fn submit(form_name: &str, fields: &[(&str, FormValue)]) -> FormCallData {
+    FormCallData {
+        form_name: form_name.to_string(),
+        extra_data: None,
+        fields: fields
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect(),
+        step: None,
+        etag: None,
+    }
+}
+
+#[test]
+fn generated_form() {
+    let form = Signup::section_form();
+    assert_eq!(Signup::FORM_NAME, "signup");
+    assert_eq!(form.form_name, "signup");
+    assert_eq!(form.header.as_deref(), Some("Aliĝi"));
+    assert_eq!(form.inputs.len(), 6);
+    assert!(form.lint().is_empty());
+
+    let FormInput::Text(username) = form.input("username").unwrap() else {
+        panic!("expected a text input");
+    };
+    assert_eq!(username.length_min, Some(3));
+    assert_eq!(
+        username.filter,
+        Some(TextFilter::ALPHA_ASCII | TextFilter::NUMERIC | TextFilter::DASH)
+    );
+    assert!(
+        matches!(form.input("level"), Some(FormInput::Radio(radio)) if radio.options.len() == 2)
+    );
+    assert!(matches!(
+        form.input("show_email"),
+        Some(FormInput::Checkbox(_))
+    ));
+    assert!(matches!(form.input("age_years"), Some(FormInput::Text(_))));
+    assert!(matches!(form.input("bio"), Some(FormInput::Markdown(_))));
+    assert!(matches!(&form.inputs[5], FormInput::Motions(_)));
+}
+
+#[test]
+fn generated_typed_inputs() {
+    let form = Account::section_form();
+    assert!(form.lint().is_empty());
+    assert!(matches!(form.input("email"), Some(FormInput::Email(email)) if email.required));
+    let Some(FormInput::Password(password)) = form.input("password") else {
+        panic!("expected a password input");
+    };
+    assert_eq!(password.confirm_name.as_deref(), Some("password_confirm"));
+    assert!(matches!(form.input("country"), Some(FormInput::Select(select)) if select.required));
+    let Some(FormInput::Number(years)) = form.input("years") else {
+        panic!("expected a number input");
+    };
+    assert_eq!(
+        (years.min, years.max, years.step),
+        (Some(0.0), Some(99.5), Some(0.5))
+    );
+    assert!(!years.required);
+    assert!(matches!(
+        form.input("birthday"),
+        Some(FormInput::DateTime(date)) if date.kind == DateTimeKind::Date
+    ));
+    assert!(matches!(form.input("website"), Some(FormInput::Url(_))));
+    let Some(FormInput::MultiSelect(tags)) = form.input("tags") else {
+        panic!("expected a multi-select input");
+    };
+    assert_eq!(tags.max_selected, Some(2));
+    assert!(tags.allow_custom);
+    assert_eq!(tags.custom_filter, Some(TextFilter::ALPHA_EO));
+
+    let data = submit(
+        "account",
+        &[
+            ("email", "ludoviko@ana.so".into()),
+            ("password", "esperanto1887".into()),
+            ("password_confirm", "esperanto1887".into()),
+            ("country", "eo".into()),
+            ("years", "2.5".into()),
+            (
+                "tags",
+                FormValue::List(vec!["lernado".into(), "vortaro".into()]),
+            ),
+        ],
+    );
+    assert_eq!(
+        Account::from_form_call_data(&data).unwrap(),
+        Account {
+            email: "ludoviko@ana.so".to_string(),
+            password: "esperanto1887".to_string(),
+            country: "eo".to_string(),
+            years: Some(2.5),
+            birthday: None,
+            website: None,
+            tags: vec!["lernado".to_string(), "vortaro".to_string()],
+        }
+    );
+}
+
+#[test]
+fn reads_typed_fields() {
+    let data = submit(
+        "signup",
+        &[
+            ("username", "zamenhof".into()),
+            ("level", "flua".into()),
+            ("show_email", "on".into()),
+            ("age_years", " 57 ".into()),
+            ("bio", "".into()),
+        ],
+    );
+    assert_eq!(
+        Signup::from_form_call_data(&data).unwrap(),
+        Signup {
+            username: "zamenhof".to_string(),
+            level: "flua".to_string(),
+            show_email: true,
+            age: Some(57),
+            bio: None,
+        }
+    );
+}
+
+#[test]
+fn validation_and_conversion_errors() {
+    // The form definition is enforced first.
+    let data = submit(
+        "signup",
+        &[("username", "ĉ".into()), ("level", "denaska".into())],
+    );
+    let response = Signup::from_form_call_data(&data).unwrap_err();
+    assert_eq!(response.field_errors.len(), 2);
+    assert_eq!(response.fields, data.fields);
+
+    // Then every field is converted, collecting all errors.
+    let data = submit(
+        "signup",
+        &[
+            ("username", "zamenhof".into()),
+            ("age_years", "multe".into()),
+        ],
+    );
+    let response = Signup::from_form_call_data(&data).unwrap_err();
+    let mut names = response.field_errors.keys().collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, ["age_years", "level"]);
+    assert_eq!(
+        response.field_errors["level"][0].code,
+        FormErrorCode::Required
+    );
+    assert_eq!(
+        response.field_errors["age_years"][0].code,
+        FormErrorCode::Invalid
+    );
+}
+
+#[test]
+fn dispatches_on_form_name() {
+    let data = submit("report", &[("reason", "Spamo".into())]);
+    assert_eq!(
+        AnyForm::from_form_call_data(&data).unwrap(),
+        AnyForm::Report(Report {
+            reason: "Spamo".to_string()
+        })
+    );
+
+    let response = AnyForm::from_form_call_data(&submit("login", &[])).unwrap_err();
+    assert_eq!(response.form_errors[0].code, FormErrorCode::Invalid);
+    assert_eq!(response.error.as_deref(), Some("unknown form `login`"));
+
+    let response = Report::from_form_call_data(&submit("login", &[])).unwrap_err();
+    assert!(response.has_errors());
+}
@@ -0,0 +1,313 @@
+//! Wire-format regression tests.
+//!
+//! Each value is serialized and compared against `tests/golden/<name>.json`.
+//! Run with `UPDATE_GOLDEN=1` to rewrite the files after an intentional change.
+
+use std::{collections::HashMap, fs, path::PathBuf, sync::Arc};
+
+use anaso_site_api_models::stela::{
+    structured_data::{page_structured_data, StructuredDataConfig},
+    testing::fixtures,
+    *,
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{name}.json"))
+}
+
+fn read_golden(name: &str) -> String {
+    let path = golden_path(name);
+    fs::read_to_string(&path).unwrap_or_else(|e| panic!("reading {}: {e}", path.display()))
+}
+
+/// Serialization matches the golden file, and the golden file round-trips.
+fn assert_golden<T: Serialize + DeserializeOwned>(name: &str, value: &T) {
+    let actual = serde_json::to_string_pretty(value).unwrap() + "\n";
+    let path = golden_path(name);
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&path, &actual).unwrap();
+    }
+    let expected = read_golden(name);
+    assert_eq!(actual, expected, "{} is out of date", path.display());
+
+    let reparsed: T = serde_json::from_str(&expected).unwrap();
+    assert_eq!(
+        serde_json::to_string_pretty(&reparsed).unwrap() + "\n",
+        expected,
+        "{} does not round-trip",
+        path.display(),
+    );
+}
+
+#[test]
+fn golden_feed_page() {
+    assert_golden("feed_page", &fixtures::feed_page());
+}
+
+#[test]
+fn golden_profile_page() {
+    assert_golden("profile_page", &fixtures::profile_page());
+}
+
+#[test]
+fn golden_nested_form() {
+    assert_golden("nested_form", &fixtures::nested_form());
+}
+
+#[test]
+fn golden_signup_form() {
+    assert_golden("signup_form", &fixtures::signup_form());
+}
+
+#[test]
+fn golden_community_wizard() {
+    assert_golden("community_wizard", &fixtures::community_wizard());
+}
+
+#[test]
+fn golden_navbar() {
+    assert_golden("navbar", &fixtures::navbar());
+}
+
+#[test]
+fn golden_modal() {
+    assert_golden(
+        "modal",
+        &Modal {
+            section: Section::Hero(Arc::new(SectionHero {
+                hero: fixtures::feed_page().hero.unwrap(),
+            })),
+        },
+    );
+}
+
+#[test]
+fn golden_structured_data() {
+    let image_url = |image: &Image| format!("https://imagedelivery.net/anaso/{}/public", image.id);
+    let config = StructuredDataConfig {
+        site_url: "https://ana.so",
+        site_name: "Anaso",
+        logo_url: Some("https://ana.so/logo.png"),
+        image_url: &image_url,
+    };
+    assert_golden(
+        "structured_data",
+        &page_structured_data(&fixtures::feed_page(), Some(&fixtures::navbar()), &config),
+    );
+}
+
+#[test]
+fn golden_form_call_data() {
+    assert_golden(
+        "form_call_data",
+        &FormCallData {
+            form_name: "profile_settings".to_string(),
+            extra_data: Some("user:zamenhof".to_string()),
+            fields: HashMap::from([("username".to_string(), "zamenhof".into())]),
+            step: None,
+            etag: None,
+        },
+    );
+}
+
+#[test]
+fn golden_form_response() {
+    assert_golden(
+        "form_response",
+        &FormResponse::default()
+            .with_field_error(
+                "username",
+                FormError::new(FormErrorCode::Invalid, "Uzantnomo jam uzata."),
+            )
+            .preserving(
+                &fixtures::signup_form(),
+                &FormCallData {
+                    form_name: "signup".to_string(),
+                    extra_data: None,
+                    fields: [("username".to_string(), "zamenhof".into())].into(),
+                    step: None,
+                    etag: None,
+                },
+            ),
+    );
+}
+
+#[test]
+fn golden_motion_api_call_response() {
+    assert_golden(
+        "motion_api_call_response",
+        &MotionApiCallResponse {
+            new_toggle: Some(true),
+            new_text: Some("13".to_string()),
+            modal: None,
+            redirect: Some("/ensaluti".to_string()),
+        },
+    );
+}
+
+#[test]
+fn golden_motion_interaction_request() {
+    assert_golden(
+        "motion_interaction_request",
+        &MotionInteractionRequest {
+            data: "like:1".to_string(),
+            toggle: Some(false),
+            page_path: Some("/".to_string()),
+            section_id: Some("post-1".to_string()),
+            capabilities: ClientCapabilities::UPDATE_BUTTON | ClientCapabilities::MODAL,
+        },
+    );
+}
+
+#[test]
+fn golden_motion_interaction_error() {
+    assert_golden(
+        "motion_interaction_error",
+        &MotionInteractionError {
+            code: MotionInteractionErrorCode::RateLimited,
+            message: Some("Bonvolu atendi iomete.".to_string()),
+            retryable: true,
+        },
+    );
+}
+
+#[test]
+fn golden_unknown_variants() {
+    assert_golden(
+        "unknown_variants",
+        &VisualSection {
+            id: None,
+            title: None,
+            bordered: None,
+            section: Section::Unknown,
+        },
+    );
+}
+
+#[test]
+fn text_filter_is_pipe_separated_flag_names() {
+    let filter = TextFilter::ALPHA_ASCII | TextFilter::DASH;
+    assert_eq!(
+        serde_json::to_string(&filter).unwrap(),
+        r#""ALPHA_ASCII | DASH""#,
+    );
+    let parsed: TextFilter = serde_json::from_str(r#""NUMERIC | SPACE""#).unwrap();
+    assert_eq!(parsed, TextFilter::NUMERIC | TextFilter::SPACE);
+}
+
+#[test]
+fn enums_are_externally_tagged() {
+    let motion: Motion = serde_json::from_str(r#"{"Href":{"uri":"/","new_tab":null}}"#).unwrap();
+    assert!(matches!(motion, Motion::Href(href) if href.uri == "/"));
+
+    let icon: MotionIcon = serde_json::from_str(r#""Rocket""#).unwrap();
+    assert_eq!(icon, MotionIcon::Unknown);
+}
+
+#[test]
+fn legacy_page_deserializes() {
+    let page: Page = serde_json::from_str(&read_golden("legacy/page_v0_0_17")).unwrap();
+    assert_eq!(page.title.as_deref(), Some("Esperanto"));
+    assert_eq!(page.sections.len(), 1);
+
+    let social = page.social.expect("legacy social data is kept");
+    assert_eq!(social.twitter_card, Some(TwitterCard::SummaryLargeImage));
+    let Some(SocialImage::Url(url)) = &social.image else {
+        panic!("legacy image URL string is kept");
+    };
+    assert_eq!(url, "https://ana.so/cdn/community-banner.png");
+    assert!(social.og_type.is_none());
+}
+
+#[test]
+fn legacy_form_deserializes() {
+    let form: SectionForm = serde_json::from_str(&read_golden("legacy/form_v0_0_17")).unwrap();
+    assert_eq!(form.form_name, "signup");
+    assert_eq!(form.inputs.len(), 2);
+}
+
+#[test]
+fn malformed_hero_becomes_none() {
+    let page: Page = serde_json::from_str(
+        r#"{
+            "title": "Ok",
+            "lang": null,
+            "social": null,
+            "layout": null,
+            "hero": "broken",
+            "sidebar": null,
+            "sections": []
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(page.title.as_deref(), Some("Ok"));
+    assert!(page.hero.is_none());
+}
+
+#[test]
+fn malformed_page_fields_default() {
+    let page: Page = serde_json::from_str(
+        r#"{
+            "title": ["not", "a", "string"],
+            "lang": 3,
+            "layout": 5,
+            "social": "nope",
+            "hero": 5,
+            "sidebar": true,
+            "sections": []
+        }"#,
+    )
+    .unwrap();
+    assert!(page.title.is_none());
+    assert!(page.lang.is_none());
+    assert!(page.layout.is_none());
+    assert!(page.social.is_none());
+    assert!(page.hero.is_none());
+    assert!(page.sidebar.is_none());
+}
+
+#[test]
+fn malformed_sections_are_skipped() {
+    let page: Page = serde_json::from_str(
+        r#"{
+            "title": null,
+            "lang": null,
+            "social": null,
+            "layout": null,
+            "hero": null,
+            "sidebar": null,
+            "sections": [
+                {"title": "first", "bordered": null, "section": "Unknown"},
+                7,
+                {"title": "third", "bordered": true, "section": "Unknown"}
+            ]
+        }"#,
+    )
+    .unwrap();
+    let titles = page
+        .sections
+        .iter()
+        .map(|s| s.title.as_deref())
+        .collect::<Vec<_>>();
+    assert_eq!(titles, [Some("first"), Some("third")]);
+}
+
+#[test]
+fn unknown_section_type_becomes_unknown() {
+    let section: VisualSection = serde_json::from_str(
+        r#"{"title": "x", "bordered": null, "section": {"Poll": {"question": "?"}}}"#,
+    )
+    .unwrap();
+    assert!(matches!(section.section, Section::Unknown));
+}
+
+#[test]
+fn malformed_section_body_becomes_unknown() {
+    let section: VisualSection =
+        serde_json::from_str(r#"{"title": "x", "bordered": null, "section": {"Post": 1}}"#)
+            .unwrap();
+    assert!(matches!(section.section, Section::Unknown));
+}
@@ -0,0 +1,124 @@
+//! Focal points and crops turn into the same framing in CSS and on
+//! Cloudflare Images.
+
+use anaso_site_api_models::stela::*;
+
+fn banner() -> Image {
+    Image {
+        aspect: Some(4.0),
+        width: Some(1600.0),
+        height: Some(400.0),
+        id: "community-banner".to_string(),
+        alt: Some(String::new()),
+        focal_point: None,
+        crop: None,
+    }
+}
+
+#[test]
+fn uncropped_images_keep_renderer_defaults() {
+    let image = banner();
+    assert_eq!(image.focus(), None);
+    assert_eq!(image.object_position(), None);
+    assert!(image.cloudflare_options().is_empty());
+}
+
+#[test]
+fn focal_point_without_crop() {
+    let image = Image {
+        focal_point: Some(FocalPoint { x: 0.7, y: 0.25 }),
+        ..banner()
+    };
+    assert_eq!(image.object_position().as_deref(), Some("70% 25%"));
+    assert_eq!(image.cloudflare_options(), ["gravity=0.7x0.25"]);
+}
+
+#[test]
+fn focal_point_is_relative_to_the_crop() {
+    let image = Image {
+        focal_point: Some(FocalPoint { x: 0.5, y: 0.5 }),
+        crop: Some(CropRect {
+            x: 0.25,
+            y: 0.0,
+            width: 0.5,
+            height: 1.0,
+        }),
+        ..banner()
+    };
+    assert_eq!(image.cloudflare_trim().as_deref(), Some("0;400;0;400"));
+    assert_eq!(image.object_position().as_deref(), Some("50% 50%"));
+    assert_eq!(
+        image.cloudflare_options(),
+        ["trim=0;400;0;400", "gravity=0.5x0.5"]
+    );
+
+    // Without dimensions there's no trim, so the focal point stays relative
+    // to the whole image.
+    let image = Image {
+        width: None,
+        height: None,
+        focal_point: Some(FocalPoint { x: 0.3, y: 0.5 }),
+        ..image
+    };
+    assert_eq!(image.cloudflare_trim(), None);
+    assert_eq!(image.cloudflare_options(), ["gravity=0.3x0.5"]);
+    // A focal point outside the crop is pulled to its edge.
+    assert_eq!(image.object_position().as_deref(), Some("10% 50%"));
+}
+
+#[test]
+fn crops_around_the_focal_point() {
+    let face = FocalPoint { x: 0.9, y: 0.5 };
+    let square = CropRect::around(face, 1.0, 4.0);
+    assert_eq!(
+        square,
+        CropRect {
+            x: 0.75,
+            y: 0.0,
+            width: 0.25,
+            height: 1.0,
+        }
+    );
+    assert!(square.is_valid());
+    assert_eq!(square.aspect(4.0), 1.0);
+
+    let wide = CropRect::around(FocalPoint::CENTER, 2.0, 0.5);
+    assert_eq!(wide.width, 1.0);
+    assert_eq!(wide.height, 0.25);
+    assert_eq!(wide.y, 0.375);
+
+    assert!(!CropRect {
+        x: 0.6,
+        y: 0.0,
+        width: 0.5,
+        height: 1.0,
+    }
+    .is_valid());
+}
+
+#[test]
+fn images_without_pixels() {
+    // An upload of 0 by 0 pixels has an aspect ratio of 0 / 0.
+    let (width, height) = (0.0_f32, 0.0_f32);
+    let image_aspect = width / height;
+    let face = FocalPoint { x: 0.9, y: 0.5 };
+    assert_eq!(CropRect::around(face, 1.0, image_aspect), CropRect::FULL);
+    assert_eq!(CropRect::around(face, 0.0, 4.0), CropRect::FULL);
+    assert_eq!(CropRect::around(face, f32::INFINITY, 4.0), CropRect::FULL);
+
+    let image = Image {
+        width: Some(width),
+        height: Some(height),
+        focal_point: Some(face),
+        crop: Some(CropRect {
+            x: 0.0,
+            y: 0.0,
+            width: 0.0,
+            height: 0.0,
+        }),
+        ..banner()
+    };
+    assert_eq!(image.focus(), Some(face));
+    assert_eq!(image.object_position().as_deref(), Some("90% 50%"));
+    assert_eq!(image.cloudflare_trim(), None);
+}
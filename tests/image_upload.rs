@@ -0,0 +1,232 @@
+//! Image uploads are checked against the constraints of their input, both
+//! before and after the upload.
+
+use anaso_site_api_models::stela::{
+    image_upload::{CloudflareDirectUpload, CloudflareResponse, ImageUploadTicketRequest},
+    lint::LintCode,
+    testing::fixtures,
+    *,
+};
+
+fn request(mime_type: &str, bytes: u64, width: u32, height: u32) -> ImageUploadTicketRequest {
+    ImageUploadTicketRequest {
+        form_name: "profile_settings".to_string(),
+        extra_data: Some("user:zamenhof".to_string()),
+        input_name: "avatar".to_string(),
+        mime_type: mime_type.to_string(),
+        bytes,
+        width: Some(width),
+        height: Some(height),
+        focal_point: None,
+        crop: None,
+    }
+}
+
+fn image(width: f32, height: f32) -> Image {
+    Image {
+        aspect: None,
+        width: Some(width),
+        height: Some(height),
+        id: "avatar-nova".to_string(),
+        alt: None,
+        focal_point: None,
+        crop: None,
+    }
+}
+
+fn code(result: Result<(), FormError>) -> FormErrorCode {
+    result.unwrap_err().code
+}
+
+#[test]
+fn upload_requests_are_checked() {
+    let form = fixtures::nested_form();
+    assert!(form
+        .check_upload(&request("image/png", 40_000, 512, 512))
+        .is_ok());
+    assert!(form
+        .check_upload(&request("IMAGE/JPEG", 40_000, 512, 512))
+        .is_ok());
+
+    for (request, expected) in [
+        (
+            request("image/gif", 40_000, 512, 512),
+            FormErrorCode::InvalidFormat,
+        ),
+        (
+            request("image/png", 6_000_000, 512, 512),
+            FormErrorCode::TooLarge,
+        ),
+        (
+            request("image/png", 40_000, 64, 64),
+            FormErrorCode::TooSmall,
+        ),
+        (
+            request("image/png", 40_000, 1024, 512),
+            FormErrorCode::InvalidAspect,
+        ),
+    ] {
+        assert_eq!(code(form.check_upload(&request)), expected, "{request:?}");
+    }
+
+    let mut other = request("image/png", 40_000, 512, 512);
+    other.input_name = "username".to_string();
+    assert_eq!(code(form.check_upload(&other)), FormErrorCode::Invalid);
+    let mut other = request("image/png", 40_000, 512, 512);
+    other.form_name = "signup".to_string();
+    assert_eq!(code(form.check_upload(&other)), FormErrorCode::Invalid);
+}
+
+#[test]
+fn uploaded_images_are_checked() {
+    let form = fixtures::nested_form();
+    let constraints = &form.image_input("avatar").unwrap().constraints;
+    assert!(constraints.check_image(&image(400.0, 400.0)).is_ok());
+    assert_eq!(
+        code(constraints.check_image(&image(100.0, 100.0))),
+        FormErrorCode::TooSmall
+    );
+    assert_eq!(
+        code(constraints.check_image(&image(400.0, 300.0))),
+        FormErrorCode::InvalidAspect
+    );
+
+    // The stored aspect ratio wins over the one from the dimensions.
+    let mut stretched = image(400.0, 400.0);
+    stretched.aspect = Some(2.0);
+    assert_eq!(
+        code(constraints.check_image(&stretched)),
+        FormErrorCode::InvalidAspect
+    );
+
+    // Nothing to check without dimensions.
+    let mut unknown = image(0.0, 0.0);
+    unknown.width = None;
+    unknown.height = None;
+    assert!(constraints.check_image(&unknown).is_ok());
+
+    // Inputs without constraints take any image.
+    assert!(ImageConstraints::default()
+        .check_image(&image(1.0, 9000.0))
+        .is_ok());
+}
+
+#[test]
+fn crops_are_checked() {
+    let input = FormInputImage {
+        title: Some("Standardo".to_string()),
+        name: Some("banner".to_string()),
+        initial_image: None,
+        preview_style: ImagePreviewStyle::LargeRectangle,
+        constraints: ImageConstraints::default(),
+        crop_aspect: Some(4.0),
+    };
+    let mut upload = request("image/png", 40_000, 1600, 900);
+    upload.crop = Some(CropRect {
+        x: 0.0,
+        y: 0.25,
+        width: 1.0,
+        height: 0.445,
+    });
+    upload.focal_point = Some(FocalPoint { x: 0.5, y: 0.4 });
+    assert!(input.check_crop(&upload).is_ok());
+
+    // Any crop is fine without a requested aspect.
+    let free = FormInputImage {
+        crop_aspect: None,
+        ..input
+    };
+    let mut square = upload.clone();
+    square.crop = Some(CropRect {
+        x: 0.2,
+        y: 0.0,
+        width: 0.5625,
+        height: 1.0,
+    });
+    assert!(free.check_crop(&square).is_ok());
+    let input = FormInputImage {
+        crop_aspect: Some(4.0),
+        ..free
+    };
+    assert_eq!(
+        code(input.check_crop(&square)),
+        FormErrorCode::InvalidAspect
+    );
+
+    let mut outside = upload.clone();
+    outside.crop = Some(CropRect {
+        x: 0.5,
+        y: 0.5,
+        width: 1.0,
+        height: 0.5,
+    });
+    assert_eq!(code(input.check_crop(&outside)), FormErrorCode::Invalid);
+    let mut outside = upload;
+    outside.focal_point = Some(FocalPoint { x: 1.5, y: 0.5 });
+    assert_eq!(code(input.check_crop(&outside)), FormErrorCode::Invalid);
+
+    // 0 / 0 has no aspect ratio to compare.
+    let mut empty = request("image/png", 40_000, 0, 0);
+    empty.crop = Some(CropRect::FULL);
+    assert_eq!(code(input.check_crop(&empty)), FormErrorCode::Invalid);
+}
+
+#[test]
+fn cloudflare_direct_upload_becomes_a_ticket() {
+    let response: CloudflareResponse<CloudflareDirectUpload> = serde_json::from_str(
+        r#"{
+            "result": {
+                "id": "2cdc28f0-017a-49c4-9ed7-87056c83901",
+                "uploadURL": "https://upload.imagedelivery.net/Vi7wi5KSItxGFsWRG2Us6Q/2cdc28f0-017a-49c4-9ed7-87056c83901"
+            },
+            "success": true,
+            "errors": [],
+            "messages": []
+        }"#,
+    )
+    .unwrap();
+    assert!(response.success);
+    let ticket = response
+        .result
+        .unwrap()
+        .into_ticket(Some("2026-10-19T12:30:00Z".to_string()));
+    assert_eq!(ticket.id, "2cdc28f0-017a-49c4-9ed7-87056c83901");
+    assert!(ticket
+        .upload_url
+        .starts_with("https://upload.imagedelivery.net/"));
+
+    let response: CloudflareResponse<CloudflareDirectUpload> = serde_json::from_str(
+        r#"{"result": null, "success": false, "errors": [{"code": 5400, "message": "Bad request"}]}"#,
+    )
+    .unwrap();
+    assert!(!response.success);
+    assert!(response.result.is_none());
+    assert_eq!(response.errors[0].code, 5400);
+}
+
+#[test]
+fn broken_constraints_are_linted() {
+    let mut form = fixtures::nested_form();
+    let FormInput::Tabs(tabs) = &mut form.inputs[1] else {
+        panic!("expected the avatar tabs");
+    };
+    let tabs = std::sync::Arc::get_mut(tabs).unwrap();
+    let FormInput::Image(avatar) = &mut tabs.tabs[0].input else {
+        panic!("expected the avatar upload");
+    };
+    let constraints = &mut std::sync::Arc::get_mut(avatar).unwrap().constraints;
+    constraints.max_width = Some(64);
+    constraints.aspect_min = Some(0.0);
+    constraints.aspect_max = Some(0.5);
+
+    let lints = form.lint();
+    let messages: Vec<_> = lints.iter().map(|lint| lint.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        [
+            "min_width 128 is greater than max_width 64",
+            "aspect_min 0 is not positive",
+        ],
+    );
+    assert!(lints.iter().all(|lint| lint.code == LintCode::InvalidRange));
+}
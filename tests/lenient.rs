@@ -0,0 +1,167 @@
+//! One malformed value shouldn't take down its whole section.
+
+use anaso_site_api_models::stela::{
+    diagnostics::{self, Fallback},
+    *,
+};
+
+const POST: &str = r#"{
+    "title": "Saluton",
+    "motions_tr": [
+        {"title": "ok", "motion": {"Href": {"uri": "/"}}},
+        {"title": 5, "motion": {"Href": {"uri": "/"}}},
+        "not a motion"
+    ],
+    "motions_bl": {"oops": true},
+    "is_pinned": "yes"
+}"#;
+
+#[test]
+fn malformed_motions_are_skipped() {
+    let post: SectionPost = serde_json::from_str(POST).unwrap();
+    assert_eq!(post.title.as_deref(), Some("Saluton"));
+    assert_eq!(post.motions_tr.len(), 2);
+    assert_eq!(post.motions_tr[1].title, None);
+    assert!(post.motions_bl.is_empty());
+    assert!(post.motions_tl.is_empty());
+    assert_eq!(post.is_pinned, None);
+}
+
+#[test]
+fn unknown_motion_type_becomes_unknown() {
+    let motion: VisualMotion =
+        serde_json::from_str(r#"{"title": "x", "motion": {"Teleport": {"to": "mars"}}}"#).unwrap();
+    assert!(matches!(motion.motion, Motion::Unknown));
+    assert_eq!(motion.variant, MotionVariant::Unknown);
+}
+
+#[test]
+fn malformed_tiles_and_inputs_are_skipped() {
+    let tiles: SectionTiles = serde_json::from_str(
+        r#"{"tiles": [{"header": "a"}, 3, {"header": "b"}], "layout": "Spiral"}"#,
+    )
+    .unwrap();
+    assert_eq!(tiles.tiles.len(), 2);
+    assert_eq!(tiles.layout, TilesLayout::Unknown);
+
+    let form: SectionForm = serde_json::from_str(
+        r#"{"form_name": "f", "inputs": [{"Checkbox": {"name": 1}}, {"Checkbox": {"name": "ok"}}]}"#,
+    )
+    .unwrap();
+    assert_eq!(form.inputs.len(), 1);
+}
+
+#[test]
+fn collect_reports_what_was_dropped() {
+    let (post, diagnostics) =
+        diagnostics::collect(|| serde_json::from_str::<SectionPost>(POST).unwrap());
+    assert_eq!(post.motions_tr.len(), 2);
+
+    let fallbacks = diagnostics.iter().map(|d| d.fallback).collect::<Vec<_>>();
+    assert_eq!(
+        fallbacks,
+        [
+            Fallback::Default,
+            Fallback::Skipped,
+            Fallback::Empty,
+            Fallback::Default,
+        ],
+    );
+    assert!(diagnostics[0].error.contains("invalid type: integer `5`"));
+}
+
+#[test]
+fn nothing_is_reported_for_valid_input() {
+    let json = serde_json::to_string(&testing::fixtures::feed_page()).unwrap();
+    let (_, diagnostics) = diagnostics::collect(|| serde_json::from_str::<Page>(&json).unwrap());
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn errors_inside_skipped_elements_are_not_reported() {
+    let (post, diagnostics) = diagnostics::collect(|| {
+        serde_json::from_str::<SectionPost>(r#"{"motions_tr": [{"title": 5, "image": 3}]}"#)
+            .unwrap()
+    });
+    assert_eq!(post.motions_tr.len(), 1);
+    assert_eq!(diagnostics.len(), 2);
+
+    let (sidebar, diagnostics) = diagnostics::collect(|| {
+        serde_json::from_str::<Sidebar>(r#"{"cards": [{"title": 1, "motions": 2}]}"#).unwrap()
+    });
+    assert!(sidebar.cards.is_empty());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].fallback, Fallback::Skipped);
+}
+
+#[test]
+fn malformed_options_and_input_fields_are_skipped() {
+    let (form, diagnostics) = diagnostics::collect(|| {
+        serde_json::from_str::<SectionForm>(
+            r#"{
+                "form_name": "f",
+                "inputs": [
+                    {"Radio": {"name": "level", "options": [
+                        {"value": "flua"}, {"value": 2}, {"value": "komencanto", "title": []}
+                    ]}},
+                    {"Select": {"name": "lang", "options": [{"value": "eo"}, null]}},
+                    {"MultiSelect": {"name": "tags", "options": "none", "style": "Chips"}},
+                    {"Text": {"name": "bio", "length_max": "long", "esperanto": "yes"}},
+                    {"Image": {"name": "avatar", "constraints": {"max_bytes": -1}}}
+                ]
+            }"#,
+        )
+        .unwrap()
+    });
+    assert_eq!(form.inputs.len(), 5);
+    let FormInput::Radio(radio) = &form.inputs[0] else {
+        panic!("expected the radio group");
+    };
+    let values: Vec<_> = radio.options.iter().map(|o| o.value.as_str()).collect();
+    assert_eq!(values, ["flua", "komencanto"]);
+    assert_eq!(radio.options[1].title, None);
+    let FormInput::Select(select) = &form.inputs[1] else {
+        panic!("expected the select");
+    };
+    assert_eq!(select.options.len(), 1);
+    let FormInput::Text(text) = &form.inputs[3] else {
+        panic!("expected the text input");
+    };
+    assert_eq!(text.length_max, None);
+    assert!(!text.esperanto);
+    let FormInput::Image(image) = &form.inputs[4] else {
+        panic!("expected the image input");
+    };
+    assert_eq!(image.constraints.max_bytes, None);
+
+    let fallbacks = diagnostics.iter().map(|d| d.fallback).collect::<Vec<_>>();
+    assert_eq!(
+        fallbacks,
+        [
+            Fallback::Skipped,
+            Fallback::Default,
+            Fallback::Skipped,
+            Fallback::Empty,
+            Fallback::Default,
+            Fallback::Default,
+            Fallback::Default,
+        ],
+    );
+}
+
+#[test]
+fn streams_and_values_recover_the_same_way() {
+    let from_str = diagnostics::collect(|| serde_json::from_str::<SectionPost>(POST).unwrap());
+    let value: serde_json::Value = serde_json::from_str(POST).unwrap();
+    let from_value = diagnostics::collect(|| serde_json::from_value::<SectionPost>(value).unwrap());
+    assert_eq!(
+        serde_json::to_value(&from_str.0).unwrap(),
+        serde_json::to_value(&from_value.0).unwrap(),
+    );
+    let errors = |diagnostics: Vec<diagnostics::Diagnostic>| {
+        let mut errors: Vec<_> = diagnostics.into_iter().map(|d| d.error).collect();
+        errors.sort();
+        errors
+    };
+    assert_eq!(errors(from_str.1), errors(from_value.1));
+}
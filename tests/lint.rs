@@ -0,0 +1,218 @@
+//! Structural lints catch pages that are valid by type but broken in practice.
+
+use std::sync::Arc;
+
+use anaso_site_api_models::stela::{
+    lint::{is_valid_uri, LintCode, Severity},
+    testing::fixtures,
+    *,
+};
+
+fn codes(page: &Page) -> Vec<(String, LintCode)> {
+    page.lint()
+        .into_iter()
+        .map(|lint| (lint.path, lint.code))
+        .collect()
+}
+
+#[test]
+fn fixtures_are_clean() {
+    assert_eq!(fixtures::feed_page().lint(), []);
+    assert_eq!(fixtures::profile_page().lint(), []);
+    assert_eq!(fixtures::nested_form().lint(), []);
+    assert_eq!(fixtures::signup_form().lint(), []);
+}
+
+#[test]
+fn broken_form_inputs() {
+    let mut page = fixtures::feed_page();
+    page.sections = vec![VisualSection {
+        id: None,
+        title: None,
+        bordered: None,
+        section: Section::Form(Arc::new(SectionForm {
+            header: None,
+            subheader: None,
+            form_name: "broken".to_string(),
+            extra_data: None,
+            noscript_text: None,
+            inputs: vec![
+                FormInput::Radio(Arc::new(FormInputRadio {
+                    title: None,
+                    name: "choice".to_string(),
+                    initial_index: Some(1),
+                    options: vec![RadioButton {
+                        value: "a".to_string(),
+                        title: None,
+                    }],
+                })),
+                FormInput::Tabs(Arc::new(FormInputTabs {
+                    tabs: vec![FormInputTab {
+                        title: "Tab".to_string(),
+                        input: FormInput::Markdown(Arc::new(FormInputMarkdown {
+                            title: None,
+                            name: "choice".to_string(),
+                            initial_value: None,
+                            length_min: Some(10),
+                            length_max: Some(5),
+                        })),
+                    }],
+                    initial_index: Some(3),
+                })),
+            ],
+            steps: Vec::new(),
+            initial_step: None,
+            step_progress: StepProgress::Unknown,
+            autosave: None,
+            etag: None,
+        })),
+    }];
+
+    assert_eq!(
+        codes(&page),
+        [
+            (
+                "sections[0].section.Form.inputs[0].Radio".to_string(),
+                LintCode::RadioInitialIndexOutOfRange
+            ),
+            (
+                "sections[0].section.Form.inputs[1].Tabs".to_string(),
+                LintCode::TabsInitialIndexOutOfRange
+            ),
+            (
+                "sections[0].section.Form.inputs[1].Tabs.tabs[0].input.Markdown".to_string(),
+                LintCode::DuplicateInputName
+            ),
+            (
+                "sections[0].section.Form.inputs[1].Tabs.tabs[0].input.Markdown".to_string(),
+                LintCode::LengthMinExceedsMax
+            ),
+        ],
+    );
+}
+
+#[test]
+fn broken_typed_inputs() {
+    let mut form = fixtures::signup_form();
+    form.inputs[2] = FormInput::Select(Arc::new(FormInputSelect {
+        title: None,
+        name: "country".to_string(),
+        initial_value: Some("us".to_string()),
+        placeholder: None,
+        options: Vec::new(),
+        required: false,
+    }));
+    form.inputs[3] = FormInput::Number(Arc::new(FormInputNumber {
+        title: None,
+        name: "years".to_string(),
+        initial_value: None,
+        min: Some(10.0),
+        max: Some(1.0),
+        step: Some(0.0),
+        required: false,
+    }));
+    form.inputs[6] = FormInput::MultiSelect(Arc::new(FormInputMultiSelect {
+        title: None,
+        name: "interests".to_string(),
+        style: MultiSelectStyle::Checkboxes,
+        options: Vec::new(),
+        initial_values: vec!["muziko".to_string()],
+        min_selected: Some(2),
+        max_selected: Some(1),
+        allow_custom: false,
+        custom_filter: None,
+        custom_length_max: None,
+    }));
+    form.inputs[4] = FormInput::DateTime(Arc::new(FormInputDateTime {
+        title: None,
+        name: "password_confirm".to_string(),
+        kind: DateTimeKind::Time,
+        initial_value: None,
+        min: Some("25:00".to_string()),
+        max: None,
+        required: false,
+    }));
+
+    let lints = form.lint();
+    let codes = lints
+        .iter()
+        .map(|lint| (lint.path.as_str(), lint.code))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        codes,
+        [
+            ("inputs[2].Select", LintCode::SelectInitialValueNotAnOption),
+            ("inputs[3].Number", LintCode::InvalidRange),
+            ("inputs[3].Number", LintCode::InvalidRange),
+            ("inputs[4].DateTime", LintCode::DuplicateInputName),
+            ("inputs[4].DateTime", LintCode::InvalidRange),
+            (
+                "inputs[6].MultiSelect",
+                LintCode::SelectInitialValueNotAnOption
+            ),
+            ("inputs[6].MultiSelect", LintCode::InvalidRange),
+        ],
+    );
+}
+
+#[test]
+fn submit_outside_form_and_bad_uri() {
+    let mut page = fixtures::feed_page();
+    let hero = Arc::get_mut(page.hero.as_mut().unwrap()).unwrap();
+    hero.motions[0].motion = Motion::Submit(Arc::new(MotionSubmit {}));
+    hero.motions.push(VisualMotion {
+        motion: Motion::Href(Arc::new(MotionHref {
+            uri: "not a uri".to_string(),
+            new_tab: None,
+        })),
+        ..hero.motions[0].clone()
+    });
+
+    assert_eq!(
+        codes(&page),
+        [
+            (
+                "hero.motions[0].motion".to_string(),
+                LintCode::SubmitOutsideForm
+            ),
+            (
+                "hero.motions[1].motion.Href".to_string(),
+                LintCode::InvalidUri
+            ),
+        ],
+    );
+}
+
+#[test]
+fn tabbed_sections_need_titles() {
+    let mut page = fixtures::profile_page();
+    page.sections[1].title = None;
+    let lints = page.lint();
+    assert_eq!(lints.len(), 1);
+    assert_eq!(lints[0].code, LintCode::TabbedSectionWithoutTitle);
+    assert_eq!(lints[0].severity, Severity::Warning);
+    assert_eq!(lints[0].path, "sections[1]");
+}
+
+#[test]
+fn lint_codes_are_stable() {
+    assert_eq!(LintCode::RadioInitialIndexOutOfRange.code(), "STL0001");
+    assert_eq!(LintCode::InvalidUri.code(), "STL0007");
+    assert_eq!(LintCode::InvalidRange.code(), "STL0010");
+}
+
+#[test]
+fn uri_validation() {
+    for uri in [
+        "/",
+        "/p/1#komentoj",
+        "#top",
+        "https://ana.so",
+        "mailto:a@b.c",
+    ] {
+        assert!(is_valid_uri(uri), "{uri}");
+    }
+    for uri in ["", "not a uri", "p/1", "1http://x", "https:", "/a\nb"] {
+        assert!(!is_valid_uri(uri), "{uri}");
+    }
+}
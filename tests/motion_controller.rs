@@ -0,0 +1,187 @@
+//! The motion controller applies API responses in one well-defined order.
+
+use std::sync::Arc;
+
+use anaso_site_api_models::stela::{
+    diagnostics,
+    motion_controller::{Activation, MotionController, MotionEffect},
+    testing::fixtures,
+    *,
+};
+
+/// The like toggle on the first post of the feed fixture, initially off.
+fn like_button() -> VisualMotion {
+    let page = fixtures::feed_page();
+    let Section::Post(post) = &page.sections[0].section else {
+        unreachable!()
+    };
+    post.motions_bl[0].clone()
+}
+
+fn response() -> MotionApiCallResponse {
+    MotionApiCallResponse {
+        new_toggle: None,
+        new_text: None,
+        modal: None,
+        redirect: None,
+    }
+}
+
+#[test]
+fn optimistic_toggle_confirmed() {
+    let mut controller = MotionController::new(&like_button())
+        .with_page_path("/k/esperanto")
+        .with_section_id("post-1");
+    assert_eq!(controller.state().toggle, Some(false));
+
+    let Activation::ApiCall { request, effects } = controller.activate() else {
+        panic!("expected an API call");
+    };
+    assert_eq!(request.data, "like:1");
+    assert_eq!(request.toggle, Some(false));
+    assert_eq!(request.page_path.as_deref(), Some("/k/esperanto"));
+    assert_eq!(request.section_id.as_deref(), Some("post-1"));
+    assert_eq!(
+        request.capabilities,
+        ClientCapabilities::UPDATE_BUTTON
+            | ClientCapabilities::MODAL
+            | ClientCapabilities::REDIRECT
+    );
+    assert!(matches!(effects[..], [MotionEffect::SetToggle(true)]));
+    assert!(controller.state().pending);
+    assert!(matches!(controller.activate(), Activation::Ignored));
+
+    let effects = controller.complete(&MotionApiCallResponse {
+        new_toggle: Some(true),
+        new_text: Some("13".to_string()),
+        ..response()
+    });
+    // The toggle already shows `true`, so only the text changes.
+    assert!(matches!(&effects[..], [MotionEffect::SetTitle(text)] if text == "13"));
+    assert_eq!(controller.state().title.as_deref(), Some("13"));
+    assert_eq!(controller.state().toggle, Some(true));
+    assert!(!controller.state().pending);
+}
+
+#[test]
+fn server_toggle_wins() {
+    let mut controller = MotionController::new(&like_button());
+    controller.activate();
+    let effects = controller.complete(&MotionApiCallResponse {
+        new_toggle: Some(false),
+        ..response()
+    });
+    assert!(matches!(effects[..], [MotionEffect::SetToggle(false)]));
+    assert_eq!(controller.state().toggle, Some(false));
+}
+
+#[test]
+fn failure_rolls_back() {
+    let mut controller = MotionController::new(&like_button());
+    controller.activate();
+    assert_eq!(controller.state().toggle, Some(true));
+
+    let effects = controller.fail();
+    assert!(matches!(effects[..], [MotionEffect::SetToggle(false)]));
+    assert_eq!(controller.state().toggle, Some(false));
+    assert!(!controller.state().pending);
+
+    // Late responses are ignored.
+    assert!(controller.fail().is_empty());
+    assert!(controller
+        .complete(&MotionApiCallResponse {
+            new_toggle: Some(true),
+            ..response()
+        })
+        .is_empty());
+}
+
+#[test]
+fn effects_are_ordered() {
+    let mut visual = like_button();
+    visual.initial_toggle = None;
+    let mut controller =
+        MotionController::new(&visual).with_capabilities(ClientCapabilities::UPDATE_BUTTON);
+    let Activation::ApiCall { request, effects } = controller.activate() else {
+        panic!("expected an API call");
+    };
+    assert!(effects.is_empty());
+    assert_eq!(request.toggle, None);
+    assert_eq!(request.page_path, None);
+    assert_eq!(request.capabilities, ClientCapabilities::UPDATE_BUTTON);
+
+    let effects = controller.complete(&MotionApiCallResponse {
+        new_toggle: Some(true),
+        new_text: Some("Ŝatita".to_string()),
+        modal: Some(Arc::new(Modal {
+            section: Section::Unknown,
+        })),
+        redirect: Some("/ensaluti".to_string()),
+    });
+    assert!(matches!(
+        &effects[..],
+        [
+            MotionEffect::SetToggle(true),
+            MotionEffect::SetTitle(_),
+            MotionEffect::OpenModal(_),
+            MotionEffect::Navigate { uri, new_tab: false },
+        ] if uri == "/ensaluti"
+    ));
+}
+
+#[test]
+fn other_motions_apply_immediately() {
+    let navbar = fixtures::navbar();
+    let mut controller = MotionController::new(&navbar.right_side_motions[0]);
+    let Activation::Effects(effects) = controller.activate() else {
+        panic!("expected immediate effects");
+    };
+    assert!(matches!(
+        &effects[..],
+        [MotionEffect::Navigate { uri, new_tab: false }] if uri == "/nova"
+    ));
+
+    let mut visual = like_button();
+    visual.motion = Motion::ScrollTo(Arc::new(MotionScrollTo {
+        section_id: "post-2".to_string(),
+    }));
+    let Activation::Effects(effects) = MotionController::new(&visual).activate() else {
+        panic!("expected immediate effects");
+    };
+    assert!(matches!(&effects[..], [MotionEffect::ScrollTo(id)] if id == "post-2"));
+
+    visual.motion = Motion::PreviousStep(Arc::new(MotionPreviousStep {}));
+    let Activation::Effects(effects) = MotionController::new(&visual).activate() else {
+        panic!("expected immediate effects");
+    };
+    assert!(matches!(effects[..], [MotionEffect::PreviousStep]));
+
+    visual.motion = Motion::Unknown;
+    assert!(matches!(
+        MotionController::new(&visual).activate(),
+        Activation::Ignored
+    ));
+}
+
+#[test]
+fn unknown_capabilities_are_dropped() {
+    let json = r#"{
+        "data": "like:1",
+        "toggle": null,
+        "page_path": null,
+        "section_id": null,
+        "capabilities": "MODAL | HAPTICS | REDIRECT"
+    }"#;
+    let (request, diagnostics) =
+        diagnostics::collect(|| serde_json::from_str::<MotionInteractionRequest>(json).unwrap());
+    assert_eq!(
+        request.capabilities,
+        ClientCapabilities::MODAL | ClientCapabilities::REDIRECT
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].fallback, diagnostics::Fallback::Skipped);
+    assert!(diagnostics[0].error.contains("HAPTICS"));
+
+    let empty: ClientCapabilities = serde_json::from_str(r#""""#).unwrap();
+    assert!(empty.is_empty());
+}
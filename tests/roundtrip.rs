@@ -0,0 +1,66 @@
+//! Property tests: everything the strategies generate survives a JSON round trip.
+
+use anaso_site_api_models::stela::{
+    image_upload::{ImageUploadTicket, ImageUploadTicketRequest},
+    *,
+};
+use proptest::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+
+fn assert_round_trip<T: Serialize + DeserializeOwned>(value: &T) {
+    let json = serde_json::to_value(value).unwrap();
+    let reparsed: T = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&reparsed).unwrap(), json);
+}
+
+proptest! {
+    #[test]
+    fn page_round_trips(page in any::<Page>()) {
+        assert_round_trip(&page);
+    }
+
+    #[test]
+    fn navbar_round_trips(navbar in any::<Navbar>()) {
+        assert_round_trip(&navbar);
+    }
+
+    #[test]
+    fn modal_round_trips(modal in any::<Modal>()) {
+        assert_round_trip(&modal);
+    }
+
+    #[test]
+    fn form_call_data_round_trips(data in any::<FormCallData>()) {
+        assert_round_trip(&data);
+    }
+
+    #[test]
+    fn form_response_round_trips(response in any::<FormResponse>()) {
+        assert_round_trip(&response);
+    }
+
+    #[test]
+    fn motion_api_call_response_round_trips(response in any::<MotionApiCallResponse>()) {
+        assert_round_trip(&response);
+    }
+
+    #[test]
+    fn motion_interaction_request_round_trips(request in any::<MotionInteractionRequest>()) {
+        assert_round_trip(&request);
+    }
+
+    #[test]
+    fn motion_interaction_error_round_trips(error in any::<MotionInteractionError>()) {
+        assert_round_trip(&error);
+    }
+
+    #[test]
+    fn image_upload_ticket_request_round_trips(request in any::<ImageUploadTicketRequest>()) {
+        assert_round_trip(&request);
+    }
+
+    #[test]
+    fn image_upload_ticket_round_trips(ticket in any::<ImageUploadTicket>()) {
+        assert_round_trip(&ticket);
+    }
+}
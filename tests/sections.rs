@@ -0,0 +1,102 @@
+//! Section ids give deep links and `ScrollTo` motions something to target.
+
+use std::sync::Arc;
+
+use anaso_site_api_models::stela::{lint::LintCode, testing::fixtures, *};
+
+fn titled(id: Option<&str>, title: Option<&str>) -> VisualSection {
+    VisualSection {
+        id: id.map(str::to_string),
+        title: title.map(str::to_string),
+        bordered: None,
+        section: Section::Unknown,
+    }
+}
+
+fn ids(page: &Page) -> Vec<&str> {
+    page.sections
+        .iter()
+        .map(|section| section.id.as_deref().unwrap())
+        .collect()
+}
+
+#[test]
+fn assigned_ids_are_unique_and_stable() {
+    let mut page = fixtures::feed_page();
+    page.sections = vec![
+        titled(Some("rules"), None),
+        titled(None, Some("Rules")),
+        titled(None, Some("Latest posts!")),
+        titled(None, None),
+        titled(Some("rules"), None),
+        titled(Some(""), Some("  ")),
+    ];
+    page.assign_section_ids();
+    assert_eq!(
+        ids(&page),
+        [
+            "rules",
+            "rules-3",
+            "latest-posts",
+            "section-4",
+            "rules-2",
+            "section-6"
+        ],
+    );
+
+    // Running it again changes nothing.
+    page.assign_section_ids();
+    assert_eq!(ids(&page)[1], "rules-3");
+}
+
+#[test]
+fn explicit_ids_win_over_generated_ones() {
+    let mut page = fixtures::feed_page();
+    page.sections = vec![
+        titled(None, Some("Rules")),
+        titled(None, None),
+        titled(Some("rules"), Some("Community rules")),
+        titled(Some("section-2"), None),
+    ];
+    page.assign_section_ids();
+    assert_eq!(ids(&page), ["rules-2", "section-2-2", "rules", "section-2"]);
+}
+
+#[test]
+fn find_section_by_id() {
+    let page = fixtures::feed_page();
+    let section = page.section_by_id("post-2").unwrap();
+    assert!(matches!(section.section, Section::Post(_)));
+    assert!(page.section_by_id("post-9").is_none());
+}
+
+#[test]
+fn scroll_to_must_target_a_section() {
+    let mut page = fixtures::feed_page();
+    let hero = Arc::get_mut(page.hero.as_mut().unwrap()).unwrap();
+    hero.motions[0].motion = Motion::ScrollTo(Arc::new(MotionScrollTo {
+        section_id: "post-3".to_string(),
+    }));
+    assert_eq!(page.lint(), []);
+
+    let hero = Arc::get_mut(page.hero.as_mut().unwrap()).unwrap();
+    hero.motions[0].motion = Motion::ScrollTo(Arc::new(MotionScrollTo {
+        section_id: "post-4".to_string(),
+    }));
+    page.sections[1].id = Some("post-1".to_string());
+    let found = page
+        .lint()
+        .into_iter()
+        .map(|lint| (lint.path, lint.code))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        found,
+        [
+            ("sections[1].id".to_string(), LintCode::DuplicateSectionId),
+            (
+                "hero.motions[0].motion.ScrollTo".to_string(),
+                LintCode::ScrollToUnknownSection
+            ),
+        ],
+    );
+}
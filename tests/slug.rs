@@ -0,0 +1,140 @@
+//! Slugs keep Esperanto readable in URLs and respect text filters.
+
+use std::collections::HashSet;
+
+use anaso_site_api_models::stela::{
+    slug::{SlugError, Slugger, Transliteration},
+    TextFilter,
+};
+
+fn slugger(transliteration: Transliteration) -> Slugger {
+    Slugger {
+        transliteration,
+        ..Slugger::default()
+    }
+}
+
+#[test]
+fn esperanto_transliteration() {
+    let text = "Ĉu ŝi manĝas ĵaŭde? Ĥoro!";
+    assert_eq!(
+        slugger(Transliteration::XSystem).slug(text).unwrap(),
+        "cxu-sxi-mangxas-jxauxde-hxoro"
+    );
+    assert_eq!(
+        slugger(Transliteration::HSystem).slug(text).unwrap(),
+        "chu-shi-manghas-jhaude-hhoro"
+    );
+    assert_eq!(
+        slugger(Transliteration::Ascii).slug(text).unwrap(),
+        "cu-si-mangas-jaude-horo"
+    );
+}
+
+#[test]
+fn other_diacritics_fold_to_ascii() {
+    let slugger = Slugger::default();
+    assert_eq!(slugger.slug("Crème Brûlée").unwrap(), "creme-brulee");
+    assert_eq!(slugger.slug("Straße in Łódź").unwrap(), "strasse-in-lodz");
+    assert_eq!(
+        slugger.slug("  Zamenhof's  ---  vortaro ").unwrap(),
+        "zamenhofs-vortaro"
+    );
+    assert_eq!(slugger.slug("日本語 / ?!"), Err(SlugError::Empty));
+}
+
+#[test]
+fn max_len_and_separator() {
+    let slugger = Slugger {
+        separator: '_',
+        max_len: Some(12),
+        ..Slugger::default()
+    };
+    assert_eq!(slugger.slug("Bonvenon al Anaso").unwrap(), "bonvenon_al");
+    assert_eq!(slugger.slug("ĉĉĉĉĉĉĉ").unwrap(), "cxcxcxcxcxcx");
+    // Transliterated letters aren't split.
+    let shorter = Slugger {
+        max_len: Some(11),
+        ..slugger
+    };
+    assert_eq!(shorter.slug("ĉĉĉĉĉĉĉ").unwrap(), "cxcxcxcxcx");
+    let h_system = Slugger {
+        transliteration: Transliteration::HSystem,
+        ..shorter
+    };
+    assert_eq!(h_system.slug("ŝŝŝŝŝŝŝ").unwrap(), "shshshshsh");
+}
+
+#[test]
+fn suffixes_alone_have_no_separator() {
+    let slugger = Slugger {
+        max_len: Some(2),
+        ..Slugger::default()
+    };
+    let mut taken = HashSet::new();
+    let slugs = ["Ĉu", "Ĉu", "Ĉu"].map(|text| slugger.unique_slug(text, &mut taken).unwrap());
+    assert_eq!(slugs, ["cx", "2", "3"]);
+}
+
+#[test]
+fn collisions_get_suffixes_within_max_len() {
+    let slugger = Slugger {
+        max_len: Some(8),
+        ..Slugger::default()
+    };
+    let mut taken = HashSet::new();
+    let slugs = ["Vortaro", "Vortaro", "Vortaro!", "Vortaroj"]
+        .map(|text| slugger.unique_slug(text, &mut taken).unwrap());
+    assert_eq!(slugs, ["vortaro", "vortar-2", "vortar-3", "vortaroj"]);
+
+    for _ in 0..10 {
+        let slug = slugger.unique_slug("Vortaroj", &mut taken).unwrap();
+        assert!(slug.len() <= 8, "{slug}");
+    }
+    assert_eq!(taken.len(), 14);
+    assert!(taken.contains("vorta-12"));
+}
+
+#[test]
+fn output_is_validated_against_filter() {
+    let ascii = TextFilter::ALPHA_ASCII | TextFilter::NUMERIC | TextFilter::DASH;
+    let slugger = Slugger {
+        filter: Some(ascii),
+        ..Slugger::default()
+    };
+    assert_eq!(slugger.slug("Ĉapelo 2").unwrap(), "cxapelo-2");
+
+    let eo = Slugger {
+        filter: Some(TextFilter::ALPHA_EO | TextFilter::DASH),
+        ..Slugger::default()
+    };
+    assert_eq!(
+        eo.slug("Ĉapelo"),
+        Err(SlugError::Disallowed {
+            slug: "cxapelo".to_string(),
+            character: 'x',
+        })
+    );
+    let eo = Slugger {
+        transliteration: Transliteration::HSystem,
+        ..eo
+    };
+    assert_eq!(eo.slug("Ĉapelo").unwrap(), "chapelo");
+
+    // A suffix the filter rejects doesn't reserve the slug.
+    let mut taken = HashSet::from(["chapelo".to_string()]);
+    assert!(eo.unique_slug("Ĉapelo", &mut taken).is_err());
+    assert_eq!(taken.len(), 1);
+}
+
+#[test]
+fn section_ids_use_x_system() {
+    let mut page = anaso_site_api_models::stela::testing::fixtures::profile_page();
+    page.assign_section_ids();
+    let ids = page
+        .sections
+        .iter()
+        .map(|section| section.id.as_deref().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, ["afisxoj", "komunumoj", "agordoj"]);
+}
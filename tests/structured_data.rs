@@ -0,0 +1,180 @@
+//! JSON-LD is derived from pages the way search engines expect it.
+
+use std::sync::Arc;
+
+use anaso_site_api_models::stela::{structured_data::*, testing::fixtures, *};
+
+fn image_url(image: &Image) -> String {
+    format!("https://imagedelivery.net/anaso/{}/public", image.id)
+}
+
+fn config() -> StructuredDataConfig<'static> {
+    StructuredDataConfig {
+        site_url: "https://ana.so/",
+        site_name: "Anaso",
+        logo_url: Some("https://ana.so/logo.png"),
+        image_url: &image_url,
+    }
+}
+
+fn postings(docs: &[JsonLdDocument]) -> Vec<&DiscussionForumPosting> {
+    docs.iter()
+        .filter_map(|doc| match &doc.node {
+            JsonLd::DiscussionForumPosting(posting) => Some(posting),
+            _ => None,
+        })
+        .collect()
+}
+
+fn single_post_page() -> Page {
+    let mut page = fixtures::feed_page();
+    page.sections.truncate(1);
+    let social = Arc::get_mut(page.social.as_mut().unwrap()).unwrap();
+    social.author = Some("zamenhof".to_string());
+    social.published_time = Some("2026-07-26T10:00:00Z".to_string());
+    page
+}
+
+#[test]
+fn every_page_describes_the_site() {
+    let docs = page_structured_data(&fixtures::feed_page(), Some(&fixtures::navbar()), &config());
+    assert!(docs.iter().all(|doc| doc.context == SCHEMA_ORG_CONTEXT));
+    assert_eq!(
+        docs[0].node,
+        JsonLd::Organization(Organization {
+            name: "Anaso".to_string(),
+            url: "https://ana.so/".to_string(),
+            logo: Some("https://ana.so/logo.png".to_string()),
+        })
+    );
+    assert!(matches!(&docs[1].node, JsonLd::WebSite(site) if site.url == "https://ana.so/"));
+    assert!(matches!(&docs[2].node, JsonLd::BreadcrumbList(_)));
+    assert_eq!(postings(&docs).len(), 3);
+}
+
+#[test]
+fn search_action_comes_from_the_navbar() {
+    let navbar = fixtures::navbar();
+    let action = website(Some(&navbar), &config()).potential_action.unwrap();
+    assert_eq!(action.target, "https://ana.so/serci?q={search_term_string}");
+    assert_eq!(action.query_input, "required name=search_term_string");
+
+    assert_eq!(
+        SearchAction::new("https://ana.so/serci?k=esperanto").target,
+        "https://ana.so/serci?k=esperanto&q={search_term_string}"
+    );
+
+    let mut no_search = fixtures::navbar();
+    no_search.search_motion = None;
+    assert!(website(Some(&no_search), &config())
+        .potential_action
+        .is_none());
+    let mut api_search = navbar;
+    api_search.search_motion = Some(Motion::ApiCall(Arc::new(MotionApiCall {
+        data: "search".to_string(),
+    })));
+    assert!(website(Some(&api_search), &config())
+        .potential_action
+        .is_none());
+    assert!(website(None, &config()).potential_action.is_none());
+}
+
+#[test]
+fn breadcrumbs_follow_the_url_path() {
+    let mut page = fixtures::feed_page();
+    let crumbs = breadcrumbs(&page, &config()).unwrap();
+    let crumbs: Vec<_> = crumbs
+        .item_list_element
+        .iter()
+        .map(|item| (item.position, item.name.as_str(), item.item.as_str()))
+        .collect();
+    assert_eq!(
+        crumbs,
+        [
+            (1, "Anaso", "https://ana.so/"),
+            (2, "k", "https://ana.so/k"),
+            (3, "Esperanto", "https://ana.so/k/esperanto"),
+        ]
+    );
+
+    // The canonical URL wins, and queries and fragments are ignored.
+    let social = Arc::get_mut(page.social.as_mut().unwrap()).unwrap();
+    social.canonical = Some("https://ana.so/k/eo?ordo=nova#supro".to_string());
+    let crumbs = breadcrumbs(&page, &config()).unwrap();
+    assert_eq!(crumbs.item_list_element[2].item, "https://ana.so/k/eo");
+
+    page.social = None;
+    assert!(breadcrumbs(&page, &config()).is_none());
+}
+
+#[test]
+fn a_lone_post_gets_the_page_authorship() {
+    let docs = page_structured_data(&single_post_page(), None, &config());
+    let posting = postings(&docs)[0];
+    assert_eq!(posting.headline.as_deref(), Some("Bonvenon!"));
+    assert_eq!(posting.url.as_deref(), Some("https://ana.so/p/1"));
+    assert_eq!(posting.author, Some(Person::new("zamenhof")));
+    assert_eq!(
+        posting.date_published.as_deref(),
+        Some("2026-07-26T10:00:00Z")
+    );
+
+    // With more posts on the page, the authorship isn't theirs.
+    let mut page = single_post_page();
+    page.sections = fixtures::feed_page().sections;
+    let docs = page_structured_data(&page, None, &config());
+    assert!(postings(&docs)
+        .iter()
+        .all(|posting| posting.author.is_none()));
+    assert_eq!(
+        postings(&docs)[1].image.as_deref(),
+        Some("https://imagedelivery.net/anaso/post-image-2/public")
+    );
+}
+
+#[test]
+fn post_text_is_plain() {
+    let mut page = single_post_page();
+    let Section::Post(post) = &mut page.sections[0].section else {
+        panic!("expected a post");
+    };
+    let post = Arc::get_mut(post).unwrap();
+    post.title = None;
+    post.body_html = Some(
+        "<p>Tom &amp; Jerry&#39;s &lt;3</p>\n<p>&quot;Saluton&quot;&nbsp;&#x263A; &copy; &amp</p>"
+            .to_string(),
+    );
+    page.sections[0].title = Some("Sekcio".to_string());
+
+    let docs = page_structured_data(&page, None, &config());
+    let posting = postings(&docs)[0];
+    assert_eq!(
+        posting.text.as_deref(),
+        Some("Tom & Jerry's <3 \"Saluton\" ☺ &copy; &amp")
+    );
+    // The section title stands in for a missing post title.
+    assert_eq!(posting.headline.as_deref(), Some("Sekcio"));
+}
+
+#[test]
+fn script_json_cant_close_the_tag() {
+    let mut page = single_post_page();
+    let Section::Post(post) = &mut page.sections[0].section else {
+        panic!("expected a post");
+    };
+    Arc::get_mut(post).unwrap().body_html =
+        Some("<p>&lt;/script&gt;&lt;script&gt;alert(1) &amp; pli</p>".to_string());
+
+    let docs = page_structured_data(&page, None, &config());
+    let posting = docs
+        .iter()
+        .find(|doc| matches!(doc.node, JsonLd::DiscussionForumPosting(_)))
+        .unwrap();
+    let json = posting.to_script_json();
+    assert!(!json.contains('<') && !json.contains('>') && !json.contains('&'));
+    assert!(json.contains(r"\u003c/script\u003e\u003cscript\u003ealert(1) \u0026 pli"));
+
+    // It's still the same JSON.
+    let parsed: JsonLdDocument = serde_json::from_str(&json).unwrap();
+    assert_eq!(&parsed, posting);
+}
@@ -0,0 +1,393 @@
+//! Turnstile tokens are read through the widget's definition and checked
+//! with siteverify, here over a stub client.
+
+use std::cell::RefCell;
+
+use anaso_site_api_models::stela::{
+    from_form::{FormDefinition, FromFormCallData},
+    lint::LintCode,
+    testing::fixtures,
+    turnstile::{
+        SiteverifyClient, SiteverifyErrorCode, SiteverifyRequest, SiteverifyResponse,
+        TurnstileError, TurnstileRejection, Verifier, DEFAULT_RESPONSE_FIELD_NAME, SITEVERIFY_URL,
+    },
+    *,
+};
+
+/// Answers every call with `response` and remembers the bodies it got.
+#[derive(Debug)]
+struct Stub {
+    response: Result<String, &'static str>,
+    calls: RefCell<Vec<SiteverifyRequest>>,
+}
+
+impl SiteverifyClient for Stub {
+    type Error = &'static str;
+
+    fn post_json(&self, url: &str, body: &str) -> Result<String, Self::Error> {
+        assert_eq!(url, SITEVERIFY_URL);
+        self.calls
+            .borrow_mut()
+            .push(serde_json::from_str(body).unwrap());
+        self.response.clone()
+    }
+}
+
+fn verifier(response: Result<&str, &'static str>) -> Verifier<Stub> {
+    Verifier {
+        client: Stub {
+            response: response.map(str::to_string),
+            calls: RefCell::new(Vec::new()),
+        },
+        secret: "1x0000000000000000000000000000000AA".to_string(),
+        hostname: Some("ana.so".to_string()),
+    }
+}
+
+fn widget() -> FormInputCfTurnstile {
+    let form = fixtures::nested_form();
+    let Some(FormInput::CfTurnstile(turnstile)) = form.input("cf_turnstile") else {
+        panic!("expected the Turnstile widget");
+    };
+    FormInputCfTurnstile {
+        class: turnstile.class.clone(),
+        sitekey: turnstile.sitekey.clone(),
+        response_field_name: turnstile.response_field_name.clone(),
+        size: turnstile.size,
+        language: turnstile.language.clone(),
+        theme: turnstile.theme,
+        appearance: turnstile.appearance,
+        execution: turnstile.execution,
+        retry: turnstile.retry,
+        action: turnstile.action.clone(),
+        cdata: turnstile.cdata.clone(),
+    }
+}
+
+fn submit(fields: &[(&str, &str)]) -> FormCallData {
+    FormCallData::from_entries(
+        "profile_settings",
+        None,
+        fields
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string())),
+    )
+}
+
+const PASSED: &str = r#"{
+    "success": true,
+    "error-codes": [],
+    "challenge_ts": "2026-10-19T12:30:00.000Z",
+    "hostname": "ana.so",
+    "action": "profile_settings",
+    "cdata": "sessionid-123456789"
+}"#;
+
+#[test]
+fn tokens_come_from_the_configured_field() {
+    let widget = widget();
+    assert_eq!(widget.field_name(), "cf_turnstile");
+    assert_eq!(
+        widget.token(&submit(&[("cf_turnstile", "XXXX.DUMMY")])),
+        Some("XXXX.DUMMY")
+    );
+    assert_eq!(
+        widget.token(&submit(&[("cf-turnstile-response", "XXXX")])),
+        None
+    );
+    assert_eq!(widget.token(&submit(&[("cf_turnstile", "")])), None);
+
+    let default = FormInputCfTurnstile {
+        response_field_name: None,
+        ..widget
+    };
+    assert_eq!(default.field_name(), "cf-turnstile-response");
+    assert_eq!(
+        default.token(&submit(&[("cf-turnstile-response", "XXXX")])),
+        Some("XXXX")
+    );
+}
+
+#[test]
+fn request_body() {
+    let request = SiteverifyRequest::new("secret", "token");
+    assert_eq!(request.body(), r#"{"secret":"secret","response":"token"}"#);
+    let request = request
+        .with_remote_ip("203.0.113.7")
+        .with_idempotency_key("4a7b1e2c-0000-4000-8000-000000000000");
+    assert_eq!(
+        request.body(),
+        r#"{"secret":"secret","response":"token","remoteip":"203.0.113.7","idempotency_key":"4a7b1e2c-0000-4000-8000-000000000000"}"#
+    );
+}
+
+#[test]
+fn debug_output_hides_the_secret() {
+    let request = SiteverifyRequest::new("sekreta-ŝlosilo", "token");
+    let debug = format!("{request:?}");
+    assert!(!debug.contains("sekreta"), "{debug}");
+    assert!(debug.contains("token"), "{debug}");
+
+    let verifier = verifier(Ok(PASSED));
+    let debug = format!("{verifier:?}");
+    assert!(!debug.contains(&verifier.secret), "{debug}");
+    assert!(debug.contains("[redacted]"), "{debug}");
+}
+
+#[test]
+fn passing_tokens() {
+    let verifier = verifier(Ok(PASSED));
+    let response = verifier
+        .verify(
+            &widget(),
+            &submit(&[("cf_turnstile", "XXXX.DUMMY")]),
+            Some("203.0.113.7"),
+        )
+        .unwrap();
+    assert_eq!(response.action.as_deref(), Some("profile_settings"));
+    assert_eq!(response.cdata.as_deref(), Some("sessionid-123456789"));
+
+    let calls = verifier.client.calls.borrow();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].response, "XXXX.DUMMY");
+    assert_eq!(calls[0].remoteip.as_deref(), Some("203.0.113.7"));
+}
+
+#[test]
+fn failing_tokens() {
+    let data = submit(&[("cf_turnstile", "XXXX.DUMMY")]);
+
+    let verifier = verifier(Ok(
+        r#"{"success": false, "error-codes": ["timeout-or-duplicate", "something-new"]}"#,
+    ));
+    let error = verifier.verify(&widget(), &data, None).unwrap_err();
+    assert!(matches!(
+        &error,
+        TurnstileError::Rejected(TurnstileRejection::Failed(codes))
+            if codes == &[SiteverifyErrorCode::TimeoutOrDuplicate, SiteverifyErrorCode::Unknown]
+    ));
+    assert_eq!(
+        error.form_error().message,
+        "the challenge expired, try again"
+    );
+
+    // Nothing is sent without a token.
+    let error = verifier.verify(&widget(), &submit(&[]), None).unwrap_err();
+    assert!(matches!(error, TurnstileError::MissingToken));
+    assert_eq!(error.form_error().code, FormErrorCode::Required);
+    assert_eq!(verifier.client.calls.borrow().len(), 1);
+
+    let other_site = PASSED.replace("\"ana.so\"", "\"example.com\"");
+    let error = self::verifier(Ok(&other_site))
+        .verify(&widget(), &data, None)
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        TurnstileError::Rejected(TurnstileRejection::Hostname(Some(_)))
+    ));
+
+    let other_action = PASSED.replace("\"profile_settings\"", "\"login\"");
+    let error = self::verifier(Ok(&other_action))
+        .verify(&widget(), &data, None)
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        TurnstileError::Rejected(TurnstileRejection::Action(Some(action))) if action == "login"
+    ));
+    let with_cdata = FormInputCfTurnstile {
+        cdata: Some("sessionid-987654321".to_string()),
+        ..widget()
+    };
+    let error = self::verifier(Ok(PASSED))
+        .verify(&with_cdata, &data, None)
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        TurnstileError::Rejected(TurnstileRejection::Cdata(_))
+    ));
+
+    let error = self::verifier(Err("connection reset"))
+        .verify(&widget(), &data, None)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "siteverify call failed: connection reset"
+    );
+    assert_eq!(error.form_error().code, FormErrorCode::Invalid);
+
+    let error = self::verifier(Ok("<html>"))
+        .verify(&widget(), &data, None)
+        .unwrap_err();
+    assert!(matches!(error, TurnstileError::Malformed(_)));
+}
+
+#[test]
+fn response_fields_are_optional() {
+    let response: SiteverifyResponse = serde_json::from_str(r#"{"success": true}"#).unwrap();
+    assert!(response.error_codes.is_empty());
+    assert!(response.hostname.is_none());
+    let without_action = FormInputCfTurnstile {
+        action: None,
+        ..widget()
+    };
+    assert!(without_action.check_response(&response, None).is_ok());
+    // A widget with an action needs it back.
+    assert_eq!(
+        widget().check_response(&response, None),
+        Err(TurnstileRejection::Action(None))
+    );
+}
+
+#[test]
+fn widget_options() {
+    let widget: FormInputCfTurnstile = serde_json::from_str(
+        r#"{
+            "class": null,
+            "sitekey": "1x00000000000000000000AA",
+            "response_field_name": null,
+            "size": "flexible",
+            "language": "PT-br",
+            "theme": "dark",
+            "appearance": "interaction-only",
+            "execution": "execute",
+            "retry": "never",
+            "action": "login",
+            "cdata": null
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(widget.size, Some(TurnstileSize::Flexible));
+    assert_eq!(widget.language.as_ref().map(|l| l.as_str()), Some("pt-BR"));
+    assert_eq!(widget.theme, Some(TurnstileTheme::Dark));
+    assert_eq!(
+        widget.appearance,
+        Some(TurnstileAppearance::InteractionOnly)
+    );
+    assert_eq!(widget.execution, Some(TurnstileExecution::Execute));
+    assert_eq!(widget.retry, Some(TurnstileRetry::Never));
+
+    // Options from newer widgets are unknown, and bad languages are dropped.
+    let widget: FormInputCfTurnstile = serde_json::from_str(
+        r#"{"sitekey": "1x00000000000000000000AA", "size": "huge", "language": "esperanto"}"#,
+    )
+    .unwrap();
+    assert_eq!(widget.size, Some(TurnstileSize::Unknown));
+    assert_eq!(widget.language, None);
+    assert_eq!(widget.theme, None);
+}
+
+#[test]
+fn language_tags() {
+    for (tag, normalized) in [
+        ("auto", "auto"),
+        ("eo", "eo"),
+        ("tlh", "tlh"),
+        ("zh-tw", "zh-TW"),
+    ] {
+        assert_eq!(
+            tag.parse::<TurnstileLanguage>().unwrap().as_str(),
+            normalized
+        );
+    }
+    for tag in [
+        "",
+        "e",
+        "esperanto",
+        "eo-",
+        "en-USA",
+        "e1",
+        "en_US",
+        "auto-US",
+    ] {
+        let error = tag.parse::<TurnstileLanguage>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!("`{tag}` is not a Turnstile language")
+        );
+    }
+    assert!(serde_json::from_str::<TurnstileLanguage>(r#""en-USA""#).is_err());
+    assert_eq!(
+        serde_json::to_string(&TurnstileLanguage::auto()).unwrap(),
+        r#""auto""#
+    );
+}
+
+#[test]
+fn bad_action_and_cdata_are_linted() {
+    let mut form = fixtures::nested_form();
+    let index = form
+        .inputs
+        .iter()
+        .position(|input| matches!(input, FormInput::CfTurnstile(_)))
+        .unwrap();
+    form.inputs[index] = FormInput::CfTurnstile(std::sync::Arc::new(FormInputCfTurnstile {
+        action: Some("profile settings".to_string()),
+        cdata: Some("x".repeat(256)),
+        ..widget()
+    }));
+    let lints = form.lint();
+    assert_eq!(lints.len(), 2);
+    assert!(lints
+        .iter()
+        .all(|lint| lint.code == LintCode::InvalidTurnstileData
+            && lint.path == format!("inputs[{index}].CfTurnstile")));
+    assert_eq!(LintCode::InvalidTurnstileData.code(), "STL0014");
+}
+
+#[test]
+fn widgets_on_the_default_field_are_duplicates() {
+    let mut form = fixtures::nested_form();
+    let default_field = || {
+        FormInput::CfTurnstile(std::sync::Arc::new(FormInputCfTurnstile {
+            response_field_name: None,
+            ..widget()
+        }))
+    };
+    form.inputs
+        .retain(|input| !matches!(input, FormInput::CfTurnstile(_)));
+    form.inputs.push(default_field());
+    assert!(form.lint().is_empty());
+
+    form.inputs.push(default_field());
+    let lints = form.lint();
+    assert_eq!(lints.len(), 1);
+    assert_eq!(lints[0].code, LintCode::DuplicateInputName);
+    assert!(lints[0].message.contains(DEFAULT_RESPONSE_FIELD_NAME));
+}
+
+#[derive(FromFormCallData)]
+#[form(name = "login")]
+#[allow(dead_code)]
+struct Login {
+    #[form(title = "Uzantnomo")]
+    username: String,
+    #[form(
+        input = "turnstile",
+        name = "cf_turnstile",
+        sitekey = "1x00000000000000000000AA",
+        size = "compact",
+        theme = "auto",
+        appearance = "interaction-only",
+        language = "eo",
+        action = "login"
+    )]
+    token: String,
+}
+
+#[test]
+fn derived_widget_options() {
+    let form = Login::section_form();
+    assert!(form.lint().is_empty());
+    let Some(FormInput::CfTurnstile(widget)) = form.input("cf_turnstile") else {
+        panic!("expected the Turnstile widget");
+    };
+    assert_eq!(widget.size, Some(TurnstileSize::Compact));
+    assert_eq!(widget.theme, Some(TurnstileTheme::Auto));
+    assert_eq!(
+        widget.appearance,
+        Some(TurnstileAppearance::InteractionOnly)
+    );
+    assert_eq!(widget.execution, None);
+    assert_eq!(widget.language, Some("eo".parse().unwrap()));
+    assert_eq!(widget.action.as_deref(), Some("login"));
+}
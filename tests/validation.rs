@@ -0,0 +1,252 @@
+//! Submitted values are checked against the form, with errors per input.
+
+use anaso_site_api_models::stela::{testing::fixtures, *};
+
+fn submit(fields: &[(&str, &str)]) -> FormCallData {
+    FormCallData {
+        form_name: "profile_settings".to_string(),
+        extra_data: None,
+        fields: fields
+            .iter()
+            .map(|(name, value)| (name.to_string(), FormValue::from(*value)))
+            .collect(),
+        step: None,
+        etag: None,
+    }
+}
+
+fn codes(response: &FormResponse, name: &str) -> Vec<FormErrorCode> {
+    response.field_errors[name]
+        .iter()
+        .map(|error| error.code)
+        .collect()
+}
+
+#[test]
+fn valid_submission() {
+    let form = fixtures::nested_form();
+    let data = submit(&[
+        ("username", "zamenhof"),
+        ("bio", "Mi kreis lingvon."),
+        ("level", "flua"),
+    ]);
+    let response = form.validate(&data);
+    assert!(!response.has_errors());
+    assert!(response.fields.is_empty());
+}
+
+#[test]
+fn errors_are_reported_per_input() {
+    let form = fixtures::nested_form();
+    let data = submit(&[
+        ("username", "ĉe"),
+        ("bio", &"a".repeat(4001)),
+        ("level", "denaska"),
+        ("avatar_url", &"a".repeat(3000)),
+    ]);
+    let response = form.validate(&data);
+
+    assert_eq!(
+        codes(&response, "username"),
+        [FormErrorCode::TooShort, FormErrorCode::DisallowedCharacters],
+    );
+    assert_eq!(codes(&response, "bio"), [FormErrorCode::TooLong]);
+    assert_eq!(codes(&response, "level"), [FormErrorCode::InvalidChoice]);
+    assert_eq!(codes(&response, "avatar_url"), [FormErrorCode::TooLong]);
+    assert_eq!(response.field_errors.len(), 4);
+
+    // Values are kept, and older clients still get a message.
+    assert_eq!(response.fields, data.fields);
+    assert_eq!(
+        response.error.as_deref(),
+        Some("must be at least 3 characters")
+    );
+}
+
+#[test]
+fn missing_required_values() {
+    let form = fixtures::nested_form();
+    let response = form.validate(&submit(&[]));
+    // Tab inputs and radios may be left out.
+    assert_eq!(response.field_errors.len(), 1);
+    assert_eq!(codes(&response, "username"), [FormErrorCode::Required]);
+}
+
+#[test]
+fn builder_adds_api_errors() {
+    let form = fixtures::nested_form();
+    let data = submit(&[("username", "zamenhof")]);
+    let response = FormResponse::default()
+        .with_form_error(FormError::new(
+            FormErrorCode::Invalid,
+            "Provu denove poste.",
+        ))
+        .with_field_error(
+            "username",
+            FormError::new(FormErrorCode::Invalid, "Uzantnomo jam uzata."),
+        )
+        .preserving(&form, &data);
+    assert!(response.has_errors());
+    assert_eq!(response.error.as_deref(), Some("Provu denove poste."));
+    assert_eq!(response.form_errors.len(), 1);
+    assert_eq!(response.fields["username"], "zamenhof".into());
+    assert!(!FormResponse::default().has_errors());
+}
+
+#[test]
+fn builder_does_not_echo_secrets() {
+    let form = fixtures::signup_form();
+    let mut data = submit(&[
+        ("username", "zamenhof"),
+        ("password", "esperanto1887"),
+        ("password_confirm", "esperanto1887"),
+    ]);
+    data.form_name = "signup".to_string();
+    let response = FormResponse::default()
+        .with_field_error(
+            "username",
+            FormError::new(FormErrorCode::Invalid, "Uzantnomo jam uzata."),
+        )
+        .preserving(&form, &data);
+    assert_eq!(response.fields.len(), 1);
+    assert_eq!(response.fields["username"], "zamenhof".into());
+    let json = serde_json::to_string(&response).unwrap();
+    assert!(!json.contains("esperanto1887"), "{json}");
+}
+
+#[test]
+fn old_responses_still_parse() {
+    let response: FormResponse = serde_json::from_str(
+        r#"{"error": "Ne.", "modal": null, "redirect": null, "success": null}"#,
+    )
+    .unwrap();
+    assert!(response.field_errors.is_empty());
+    assert!(response.has_errors());
+}
+
+fn signup(fields: &[(&str, &str)]) -> FormCallData {
+    FormCallData {
+        form_name: "signup".to_string(),
+        ..submit(fields)
+    }
+}
+
+#[test]
+fn typed_inputs_accept_valid_values() {
+    let form = fixtures::signup_form();
+    let data = signup(&[
+        ("email", "ludoviko@ana.so"),
+        ("password", "esperanto1887"),
+        ("password_confirm", "esperanto1887"),
+        ("country", "fr"),
+        ("years", "2.5"),
+        ("birthday", "1987-07-26"),
+        ("website", "https://ana.so/@zamenhof"),
+    ]);
+    let response = form.validate(&data);
+    assert!(!response.has_errors(), "{:?}", response.field_errors);
+
+    // Optional inputs may be left empty.
+    let data = signup(&[
+        ("email", "ludoviko@ana.so"),
+        ("password", "esperanto1887"),
+        ("password_confirm", "esperanto1887"),
+        ("country", "eo"),
+        ("years", ""),
+    ]);
+    assert!(!form.validate(&data).has_errors());
+}
+
+#[test]
+fn typed_inputs_reject_invalid_values() {
+    let form = fixtures::signup_form();
+    let response = form.validate(&signup(&[
+        ("email", "ludoviko"),
+        ("password", "sekreta"),
+        ("password_confirm", "sekretaa"),
+        ("country", "us"),
+        ("years", "101"),
+        ("birthday", "1859-13-15"),
+        ("website", "ftp://ana.so"),
+    ]));
+    assert_eq!(codes(&response, "email"), [FormErrorCode::InvalidFormat]);
+    assert_eq!(codes(&response, "password"), [FormErrorCode::TooShort]);
+    assert_eq!(
+        codes(&response, "password_confirm"),
+        [FormErrorCode::Mismatch]
+    );
+    assert_eq!(codes(&response, "country"), [FormErrorCode::InvalidChoice]);
+    assert_eq!(codes(&response, "years"), [FormErrorCode::TooLarge]);
+    assert_eq!(codes(&response, "birthday"), [FormErrorCode::InvalidFormat]);
+    assert_eq!(codes(&response, "website"), [FormErrorCode::InvalidFormat]);
+
+    // Passwords aren't sent back.
+    assert!(response.fields.contains_key("email"));
+    assert!(!response.fields.contains_key("password"));
+    assert!(!response.fields.contains_key("password_confirm"));
+
+    let response = form.validate(&signup(&[("years", "1.25"), ("birthday", "1850-01-01")]));
+    assert_eq!(codes(&response, "email"), [FormErrorCode::Required]);
+    assert_eq!(codes(&response, "password"), [FormErrorCode::Required]);
+    assert_eq!(codes(&response, "country"), [FormErrorCode::Required]);
+    assert_eq!(codes(&response, "years"), [FormErrorCode::InvalidStep]);
+    assert_eq!(codes(&response, "birthday"), [FormErrorCode::TooSmall]);
+}
+
+#[test]
+fn email_and_url_formats() {
+    for email in ["a@b.c", "ludoviko.zamenhof@ana.so"] {
+        assert!(validation::is_valid_email(email), "{email}");
+    }
+    for email in ["", "a@b", "@b.c", "a@@b.c", "a b@c.d", "a@b..c"] {
+        assert!(!validation::is_valid_email(email), "{email}");
+    }
+    for url in ["https://ana.so", "http://ana.so/p/1?x=y"] {
+        assert!(validation::is_valid_web_url(url), "{url}");
+    }
+    for url in [
+        "/p/1",
+        "https://",
+        "https:///p",
+        "mailto:a@b.c",
+        "https://a b",
+    ] {
+        assert!(!validation::is_valid_web_url(url), "{url}");
+    }
+}
+
+#[test]
+fn multi_select_counts_and_tags() {
+    let form = fixtures::signup_form();
+    let valid = [
+        ("email", "ludoviko@ana.so"),
+        ("password", "esperanto1887"),
+        ("password_confirm", "esperanto1887"),
+        ("country", "eo"),
+    ];
+    let with_interests = |interests: &[&str]| {
+        let mut data = signup(&valid);
+        data.fields.insert(
+            "interests".to_string(),
+            FormValue::List(interests.iter().map(|&tag| tag.into()).collect()),
+        );
+        form.validate(&data)
+    };
+
+    assert!(!with_interests(&["muziko", "ŝakludo"]).has_errors());
+    assert_eq!(
+        codes(
+            &with_interests(&["muziko", "literaturo", "a", "b"]),
+            "interests"
+        ),
+        [FormErrorCode::TooMany]
+    );
+    assert_eq!(
+        codes(&with_interests(&["xyz"]), "interests"),
+        [FormErrorCode::DisallowedCharacters]
+    );
+    assert_eq!(
+        codes(&with_interests(&[&"a".repeat(25)]), "interests"),
+        [FormErrorCode::TooLong]
+    );
+}
@@ -0,0 +1,211 @@
+//! Multi-step forms are checked a step at a time, carrying progress in
+//! `extra_data`.
+
+use anaso_site_api_models::stela::{
+    lint::LintCode,
+    testing::fixtures,
+    wizard::{StepOutcome, WizardState},
+    *,
+};
+
+fn submit(
+    step: Option<usize>,
+    extra_data: Option<String>,
+    fields: &[(&str, &str)],
+) -> FormCallData {
+    FormCallData {
+        step,
+        etag: None,
+        ..FormCallData::from_entries(
+            "create_community",
+            extra_data,
+            fields
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string())),
+        )
+    }
+}
+
+fn respond(outcome: StepOutcome) -> FormResponse {
+    match outcome {
+        StepOutcome::Respond(response) => *response,
+        StepOutcome::Complete(data) => panic!("completed early with {data:?}"),
+    }
+}
+
+#[test]
+fn steps_advance_and_complete() {
+    let form = fixtures::community_wizard();
+    assert_eq!(form.lint(), []);
+
+    // A step with errors stays on that step.
+    let response = respond(form.advance(&submit(Some(0), None, &[("name", "Ek")])));
+    assert_eq!(response.step, Some(0));
+    assert_eq!(response.field_errors.len(), 2);
+    assert!(response.extra_data.is_none());
+
+    let response = respond(form.advance(&submit(
+        Some(0),
+        None,
+        &[("name", "Esperantistoj"), ("slug", "esperanto")],
+    )));
+    assert!(!response.has_errors());
+    assert_eq!(response.step, Some(1));
+    let state = WizardState::decode(response.extra_data.as_deref());
+    assert_eq!(state.step, 1);
+    assert_eq!(state.fields["name"], "Esperantistoj".into());
+
+    // Only the submitted step is checked.
+    let response = respond(form.advance(&submit(
+        Some(1),
+        response.extra_data,
+        &[("description", "Por ĉiuj.")],
+    )));
+    assert_eq!(response.step, Some(2));
+
+    let StepOutcome::Complete(data) =
+        form.advance(&submit(Some(2), response.extra_data, &[("public", "on")]))
+    else {
+        panic!("expected the form to complete");
+    };
+    assert_eq!(data.step, None);
+    assert_eq!(data.fields.len(), 4);
+    assert_eq!(data.fields["slug"], "esperanto".into());
+}
+
+#[test]
+fn tampered_state_returns_to_the_step() {
+    let form = fixtures::community_wizard();
+    let mut state = WizardState::default();
+    state.fields.insert("name".to_string(), "Ek".into());
+    state.fields.insert("slug".to_string(), "ek".into());
+    state
+        .fields
+        .insert("description".to_string(), "Jes.".into());
+
+    let response = respond(form.advance(&submit(Some(2), Some(state.encode()), &[])));
+    assert_eq!(response.step, Some(0));
+    assert!(response.field_errors.contains_key("name"));
+    assert_eq!(WizardState::decode(response.extra_data.as_deref()).step, 0);
+
+    // Anything else is the form's own extra_data, and starts over.
+    assert_eq!(
+        WizardState::decode(Some("ne json")),
+        WizardState {
+            extra_data: Some("ne json".to_string()),
+            ..WizardState::default()
+        }
+    );
+    assert_eq!(WizardState::decode(None), WizardState::default());
+}
+
+#[test]
+fn the_forms_extra_data_comes_back() {
+    let mut form = fixtures::community_wizard();
+    form.extra_data = Some("community:esperanto".to_string());
+    let key = form.draft_key();
+
+    let response = respond(form.advance(&submit(
+        Some(0),
+        form.extra_data.clone(),
+        &[("name", "Esperantistoj"), ("slug", "esperanto")],
+    )));
+    let state = WizardState::decode(response.extra_data.as_deref());
+    assert_eq!(state.extra_data.as_deref(), Some("community:esperanto"));
+
+    // The client shows the next step with the state as its extra_data.
+    form.extra_data = response.extra_data;
+    assert_eq!(form.draft_key(), key);
+
+    let response = respond(form.advance(&submit(
+        Some(1),
+        form.extra_data.clone(),
+        &[("description", "Por ĉiuj.")],
+    )));
+    let StepOutcome::Complete(data) = form.advance(&submit(Some(2), response.extra_data, &[]))
+    else {
+        panic!("expected the form to complete");
+    };
+    assert_eq!(data.extra_data.as_deref(), Some("community:esperanto"));
+}
+
+#[test]
+fn revisiting_a_step_replaces_its_values() {
+    let form = fixtures::community_wizard();
+    let mut state = WizardState::default();
+    state.record(
+        &form.steps[2],
+        2,
+        &submit(Some(2), None, &[("public", "on")]),
+    );
+    assert_eq!(state.step, 3);
+    state.record(&form.steps[2], 2, &submit(Some(2), None, &[]));
+    assert!(state.fields.is_empty());
+    assert_eq!(form.step_of("description"), Some(1));
+    assert_eq!(form.step_of("missing"), None);
+}
+
+#[test]
+fn passwords_stay_out_of_the_state() {
+    let mut form = fixtures::community_wizard();
+    let password = FormInput::Password(std::sync::Arc::new(FormInputPassword {
+        title: Some("Pasvorto".to_string()),
+        name: "password".to_string(),
+        length_min: None,
+        length_max: None,
+        confirm_name: Some("password_confirm".to_string()),
+        confirm_title: Some("Ripetu la pasvorton".to_string()),
+        autocomplete: None,
+    }));
+    form.steps[0].inputs.push(password.clone());
+    let response = respond(form.advance(&submit(
+        Some(0),
+        None,
+        &[
+            ("name", "Esperantistoj"),
+            ("slug", "esperanto"),
+            ("password", "sekreta pasvorto"),
+            ("password_confirm", "sekreta pasvorto"),
+        ],
+    )));
+    assert_eq!(response.step, Some(1));
+    let extra_data = response.extra_data.unwrap();
+    assert!(!extra_data.contains("sekreta"), "{extra_data}");
+    assert!(!extra_data.contains("password"), "{extra_data}");
+
+    // On the last step, the password goes straight to the complete data.
+    form.steps[0].inputs.pop();
+    form.steps[2].inputs.push(password);
+    let mut state = WizardState::decode(Some(&extra_data));
+    state.record(
+        &form.steps[1],
+        1,
+        &submit(Some(1), None, &[("description", "Por ĉiuj.")]),
+    );
+    let StepOutcome::Complete(data) = form.advance(&submit(
+        Some(2),
+        Some(state.encode()),
+        &[
+            ("password", "sekreta pasvorto"),
+            ("password_confirm", "sekreta pasvorto"),
+        ],
+    )) else {
+        panic!("expected the form to complete");
+    };
+    assert_eq!(data.fields["password"], "sekreta pasvorto".into());
+}
+
+#[test]
+fn step_validation_and_lints() {
+    let mut form = fixtures::community_wizard();
+    let response = form.validate_step(7, &submit(Some(7), None, &[]));
+    assert_eq!(response.form_errors[0].code, FormErrorCode::Invalid);
+    // Inputs on steps are found like any other.
+    assert!(matches!(form.input("public"), Some(FormInput::Checkbox(_))));
+
+    form.initial_step = Some(3);
+    let lints = form.lint();
+    assert_eq!(lints.len(), 1);
+    assert_eq!(lints[0].code, LintCode::InitialStepOutOfRange);
+    assert_eq!(lints[0].path, "initial_step");
+}